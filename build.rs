@@ -5,11 +5,42 @@ use std::process::{Command, Stdio};
 fn main() {
     // we need to output *some* file to opt out of the default
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(has_manually_drop_in_union)");
+    println!("cargo:rustc-check-cfg=cfg(has_union_feature)");
+    println!("cargo:rustc-check-cfg=cfg(has_const_generics)");
+    println!("cargo:rustc-check-cfg=cfg(has_non_exhaustive)");
+    println!("cargo:rustc-check-cfg=cfg(has_core_error)");
+    println!("cargo:rustc-check-cfg=cfg(has_core_maybe_uninit)");
+    println!("cargo:rustc-check-cfg=cfg(has_io_slice)");
+    println!("cargo:rustc-check-cfg=cfg(has_from_foreign)");
+    println!("cargo:rustc-check-cfg=cfg(has_atomic_ptr)");
+    println!("cargo:rustc-check-cfg=cfg(has_try_from)");
 
     detect_maybe_uninit();
+    detect_const_generics();
+    detect_non_exhaustive();
+    detect_core_error();
+    detect_io_slice();
+    detect_from_foreign();
+    detect_atomic_ptr();
+    detect_try_from();
 }
 
 fn detect_maybe_uninit() {
+    let code = "
+    #![allow(warnings)]
+    const SLOTS: std::mem::MaybeUninit<[u8; 2]> = std::mem::MaybeUninit::new([1, 2]);
+
+    fn main() {
+        let value = std::mem::MaybeUninit::<[String; 2]>::uninit();
+        let first = value.as_ptr() as *const String;
+    }
+    ";
+
+    if probe(code) {
+        println!("cargo:rustc-cfg=has_core_maybe_uninit");
+    }
+
     let has_unstable_union_with_md = probe(&maybe_uninit_code(true));
     if has_unstable_union_with_md {
         println!("cargo:rustc-cfg=has_manually_drop_in_union");
@@ -23,6 +54,124 @@ fn detect_maybe_uninit() {
     }
 }
 
+fn detect_const_generics() {
+    let code = "
+    #![allow(warnings)]
+    fn indices<const N: usize>() -> [usize; N] {
+        std::array::from_fn(|i| i)
+    }
+
+    fn main() {
+        let value: [usize; 3] = indices();
+    }
+    ";
+
+    if probe(code) {
+        println!("cargo:rustc-cfg=has_const_generics");
+    }
+}
+
+fn detect_non_exhaustive() {
+    let code = "
+    #![allow(warnings)]
+    #[non_exhaustive]
+    pub enum Kind {
+        A,
+    }
+
+    fn main() {}
+    ";
+
+    if probe(code) {
+        println!("cargo:rustc-cfg=has_non_exhaustive");
+    }
+}
+
+fn detect_core_error() {
+    let code = "
+    #![allow(warnings)]
+    fn describe(_: &core::error::Error) {}
+
+    fn main() {}
+    ";
+
+    if probe(code) {
+        println!("cargo:rustc-cfg=has_core_error");
+    }
+}
+
+fn detect_io_slice() {
+    let code = "
+    #![allow(warnings)]
+    use std::io::{IoSlice, IoSliceMut};
+
+    fn main() {
+        let mut buf = [0u8; 2];
+        let read = [IoSliceMut::new(&mut buf)];
+        let written = [IoSlice::new(b\"ab\")];
+    }
+    ";
+
+    if probe(code) {
+        println!("cargo:rustc-cfg=has_io_slice");
+    }
+}
+
+fn detect_from_foreign() {
+    let code = "
+    #![allow(warnings)]
+    pub struct Local<T>(T);
+
+    impl<T> From<Local<T>> for Vec<T> {
+        fn from(local: Local<T>) -> Vec<T> {
+            vec![local.0]
+        }
+    }
+
+    fn main() {}
+    ";
+
+    if probe(code) {
+        println!("cargo:rustc-cfg=has_from_foreign");
+    }
+}
+
+// `cfg(target_has_atomic)` is an error before Rust 1.60, so it is only used
+// through this probe
+fn detect_atomic_ptr() {
+    let code = "
+    #![allow(warnings)]
+    #[cfg(target_has_atomic = \"ptr\")]
+    fn compare_exchange() {
+        let value = std::sync::atomic::AtomicUsize::new(0);
+        value.compare_exchange(0, 1, std::sync::atomic::Ordering::AcqRel, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn main() {
+        compare_exchange();
+    }
+    ";
+
+    if probe(code) {
+        println!("cargo:rustc-cfg=has_atomic_ptr");
+    }
+}
+
+fn detect_try_from() {
+    let code = "
+    #![allow(warnings)]
+    use std::convert::TryFrom;
+
+    fn main() {
+        let byte = u8::try_from(1u32);
+    }
+    ";
+
+    if probe(code) {
+        println!("cargo:rustc-cfg=has_try_from");
+    }
+}
+
 // To guard against changes in this currently unstable feature, use
 // a detection tests instead of a Rustc version and/or date test.
 fn maybe_uninit_code(use_feature: bool) -> String {
@@ -0,0 +1,40 @@
+/* C declarations for the `extern-c` feature of the arraydeque crate. */
+
+#ifndef ARRAYDEQUE_H
+#define ARRAYDEQUE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A ring buffer of `capacity` elements of `elem_size` bytes each, stored in
+ * a buffer owned by the caller. Only modify it through the functions below. */
+typedef struct arraydeque_raw_t {
+    unsigned char *buf;
+    size_t elem_size;
+    size_t capacity;
+    size_t tail;
+    size_t len;
+} arraydeque_raw_t;
+
+void arraydeque_raw_init(arraydeque_raw_t *raw, void *buf, size_t elem_size, size_t capacity);
+size_t arraydeque_raw_len(const arraydeque_raw_t *raw);
+size_t arraydeque_raw_capacity(const arraydeque_raw_t *raw);
+void arraydeque_raw_clear(arraydeque_raw_t *raw);
+
+/* Return false, leaving the ring unchanged, if it is full. */
+bool arraydeque_raw_push_back(arraydeque_raw_t *raw, const void *elem);
+bool arraydeque_raw_push_front(arraydeque_raw_t *raw, const void *elem);
+
+/* Return false, leaving `out` untouched, if the ring is empty. */
+bool arraydeque_raw_pop_front(arraydeque_raw_t *raw, void *out);
+bool arraydeque_raw_pop_back(arraydeque_raw_t *raw, void *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ARRAYDEQUE_H */
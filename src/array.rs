@@ -2,7 +2,41 @@
 
 use std::slice;
 
+/// Trait for storage whose capacity is known at compile time.
+///
+/// It is implemented by every backing accepted by `ArrayDeque`, plain arrays,
+/// `ConstArray` and `GenericArray` alike, by every array `[T; N]` where const
+/// generics are available, and by `ArrayDeque` itself, so generic code can
+/// read the capacity of any of them the same way.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::{ArrayDeque, CapacityStorage};
+///
+/// fn capacity_of<S: CapacityStorage>() -> usize {
+///     S::CAPACITY
+/// }
+///
+/// assert_eq!(capacity_of::<[u8; 16]>(), 16);
+/// assert_eq!(capacity_of::<ArrayDeque<[u8; 16]>>(), 16);
+/// ```
+pub trait CapacityStorage {
+    /// The number of elements the storage can hold.
+    const CAPACITY: usize;
+}
+
+#[cfg(has_const_generics)]
+impl<T, const N: usize> CapacityStorage for [T; N] {
+    const CAPACITY: usize = N;
+}
+
 /// Trait for fixed size arrays.
+///
+/// # Safety
+///
+/// Implementors must guarantee that `as_ptr` and `as_mut_ptr` point to
+/// `capacity()` contiguous, properly aligned slots of `Item`.
 pub unsafe trait Array {
     /// The array’s element type
     type Item;
@@ -35,13 +69,26 @@ pub unsafe trait Array {
     }
 }
 
+#[doc(hidden)]
+/// An array whose capacity is also known at compile time, as the
+/// `CapacityStorage::CAPACITY` of the array and as an index.
+///
+/// Implementors must guarantee that both equal `capacity()`.
+pub unsafe trait ConstCapacity: Array + CapacityStorage {
+    /// The capacity of the array, as an index.
+    const CAPACITY_INDEX: Self::Index;
+}
+
 #[doc(hidden)]
 pub trait Index: PartialEq + Copy {
+    const ZERO: Self;
     fn to_usize(self) -> usize;
-    fn from(usize) -> Self;
+    fn from(ix: usize) -> Self;
 }
 
 impl Index for u8 {
+    const ZERO: u8 = 0;
+
     #[inline(always)]
     fn to_usize(self) -> usize {
         self as usize
@@ -54,6 +101,8 @@ impl Index for u8 {
 }
 
 impl Index for u16 {
+    const ZERO: u16 = 0;
+
     #[inline(always)]
     fn to_usize(self) -> usize {
         self as usize
@@ -66,6 +115,8 @@ impl Index for u16 {
 }
 
 impl Index for u32 {
+    const ZERO: u32 = 0;
+
     #[inline(always)]
     fn to_usize(self) -> usize {
         self as usize
@@ -78,6 +129,8 @@ impl Index for u32 {
 }
 
 impl Index for usize {
+    const ZERO: usize = 0;
+
     #[inline(always)]
     fn to_usize(self) -> usize {
         self
@@ -89,23 +142,103 @@ impl Index for usize {
     }
 }
 
+/// Returns the smallest capacity of at least `n` for which `[T; capacity]`
+/// implements `Array`.
+///
+/// Every power of two up to `1 << 16` is such a capacity on 32 and 64 bit
+/// targets. The `array_for!` macro names the array type directly. A
+/// `ConstArray` can be used for a capacity of exactly `n` instead.
+///
+/// Requires Rust 1.63+.
+///
+/// # Panics
+///
+/// Panics, at compile time in a const context, if `n` is larger than every
+/// supported capacity.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::{capacity_for, ArrayDeque};
+///
+/// const SAMPLES: usize = 42;
+///
+/// let buf: ArrayDeque<[f32; capacity_for(SAMPLES)]> = ArrayDeque::new();
+///
+/// assert_eq!(buf.capacity(), 48);
+/// assert_eq!(capacity_for(64), 64);
+/// ```
+#[cfg(has_const_generics)]
+pub const fn capacity_for(n: usize) -> usize {
+    let mut i = 0;
+    while i < CAPACITIES.len() {
+        let mut j = 0;
+        while j < CAPACITIES[i].len() {
+            if CAPACITIES[i][j] >= n {
+                return CAPACITIES[i][j];
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    panic!("no supported array capacity is large enough")
+}
+
+/// Names the smallest array type of element `T` implementing `Array` that
+/// holds at least `n` elements.
+///
+/// `array_for!(T; n)` is `[T; capacity_for(n)]`.
+///
+/// Requires Rust 1.63+.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate arraydeque;
+///
+/// use arraydeque::ArrayDeque;
+///
+/// # fn main() {
+/// let buf: ArrayDeque<array_for!(u8; 1000)> = ArrayDeque::new();
+///
+/// assert_eq!(buf.capacity(), 1024);
+/// # }
+/// ```
+#[cfg(has_const_generics)]
+#[macro_export]
+macro_rules! array_for {
+    ($t:ty; $n:expr) => {
+        [$t; $crate::capacity_for($n)]
+    };
+}
+
 macro_rules! fix_array_impl {
     ($index_type:ty, $len:expr) => {
+        #[cfg(not(has_const_generics))]
+        impl<T> CapacityStorage for [T; $len] {
+            const CAPACITY: usize = $len;
+        }
+
+        unsafe impl<T> ConstCapacity for [T; $len] {
+            const CAPACITY_INDEX: $index_type = $len;
+        }
+
         unsafe impl<T> Array for [T; $len] {
             type Item = T;
-        
+
             type Index = $index_type;
-        
+
             #[inline(always)]
             fn as_ptr(&self) -> *const T {
                 self as *const _ as *const T
             }
-        
+
             #[inline(always)]
             fn as_mut_ptr(&mut self) -> *mut T {
                 self as *mut _ as *mut T
             }
-        
+
             #[inline(always)]
             fn capacity() -> usize {
                 $len
@@ -122,22 +255,113 @@ macro_rules! fix_array_impl_recursive {
     );
 }
 
-fix_array_impl_recursive!(
-    u8, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
-    25, 26, 27, 28, 29, 30, 31, 32, 40, 48, 50, 56, 64, 72, 96, 100, 128, 160, 192, 200, 224,
-);
+/// Implements `Array` for every group of lengths, each indexed by its own
+/// type, and lists them in `CAPACITIES`.
+macro_rules! fix_array_impls {
+    ($($(#[$attr:meta])* $index_type:ty: $($len:expr,)*;)*) => (
+        $(
+            $(#[$attr])*
+            fix_array_impl_recursive!($index_type, $($len,)*);
+        )*
+
+        /// The capacities of the arrays implementing `Array`, in increasing
+        /// order, by index type.
+        #[cfg(has_const_generics)]
+        const CAPACITIES: &[&[usize]] = &[$($(#[$attr])* &[$($len,)*],)*];
+    );
+}
 
-fix_array_impl_recursive!(u16, 256, 384, 512, 768, 1024, 2048, 4096, 8192, 16384, 32768,);
+fix_array_impls! {
+    u8: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+    25, 26, 27, 28, 29, 30, 31, 32, 40, 48, 50, 56, 64, 72, 96, 100, 128, 160, 192, 200, 224,;
 
-// This array size doesn't exist on 16-bit
-#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
-fix_array_impl_recursive!(u32, 1 << 16,);
+    u16: 256, 384, 512, 768, 1024, 2048, 4096, 8192, 16384, 32768,;
+
+    // This array size doesn't exist on 16-bit
+    #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+    u32: 1 << 16,;
+}
+
+/// An array of any length `N`, usable as the backing array of an
+/// `ArrayDeque`.
+///
+/// Plain arrays only implement `Array` for a fixed list of lengths, each
+/// indexed by the smallest integer type that fits. `ConstArray` lifts that
+/// restriction at the cost of `usize` indices. `ConstDeque<T, N>` names the
+/// resulting deque type.
+///
+/// Requires Rust 1.63+.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::{ArrayDeque, ConstArray, ConstDeque};
+///
+/// let mut buf: ConstDeque<u32, 1000> = ConstDeque::new();
+///
+/// buf.extend_back(0..2000);
+/// assert_eq!(buf.capacity(), 1000);
+/// assert_eq!(buf.back(), Some(&999));
+///
+/// let same: ArrayDeque<ConstArray<u32, 1000>> = buf;
+/// assert!(same.is_full());
+/// ```
+#[cfg(has_const_generics)]
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConstArray<T, const N: usize>(pub [T; N]);
+
+#[cfg(has_const_generics)]
+impl<T, const N: usize> CapacityStorage for ConstArray<T, N> {
+    const CAPACITY: usize = N;
+}
+
+#[cfg(has_const_generics)]
+unsafe impl<T, const N: usize> ConstCapacity for ConstArray<T, N> {
+    const CAPACITY_INDEX: usize = N;
+}
+
+#[cfg(has_const_generics)]
+unsafe impl<T, const N: usize> Array for ConstArray<T, N> {
+    type Item = T;
+
+    type Index = usize;
+
+    #[inline(always)]
+    fn as_ptr(&self) -> *const T {
+        self.0.as_ptr()
+    }
+
+    #[inline(always)]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.0.as_mut_ptr()
+    }
+
+    #[inline(always)]
+    fn capacity() -> usize {
+        N
+    }
+}
 
 #[cfg(feature = "use_generic_array")]
 mod generic_impl {
-    use super::Array;
+    use super::{Array, CapacityStorage, ConstCapacity};
     use generic_array::{ArrayLength, GenericArray};
 
+    impl<T, N> CapacityStorage for GenericArray<T, N>
+    where
+        N: ArrayLength<T>,
+    {
+        const CAPACITY: usize = N::USIZE;
+    }
+
+    unsafe impl<T, N> ConstCapacity for GenericArray<T, N>
+    where
+        N: ArrayLength<T>,
+    {
+        const CAPACITY_INDEX: usize = N::USIZE;
+    }
+
     unsafe impl<T, N> Array for GenericArray<T, N>
     where
         N: ArrayLength<T>,
@@ -0,0 +1,301 @@
+//! A fixed-capacity ring that can be pushed to and popped from concurrently.
+
+use std::array;
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use error::CapacityError;
+
+struct Slot<T> {
+    // `pos` when the slot is free for the push claiming position `pos`,
+    // `pos + 1` once that push has published its element.
+    seq: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// A ring buffer with a fixed capacity of `N` elements, shared through `&self`
+/// by any number of producers and consumers.
+///
+/// A push first claims a slot index with a single atomic compare-and-swap,
+/// then writes the element, then publishes the slot. No lock or critical
+/// section is taken, so pushes may come from several threads or interrupt
+/// priorities at once: a push preempted between claiming and publishing only
+/// holds back the pops of its own slot and the ones after it, never the other
+/// pushes.
+///
+/// `N` must be a power of two.
+///
+/// Requires Rust 1.63+ and a target with pointer-sized compare-and-swap;
+/// elsewhere the type does not exist.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use arraydeque::AtomicRing;
+///
+/// let ring: Arc<AtomicRing<usize, 64>> = Arc::new(AtomicRing::new());
+///
+/// let producers: Vec<_> = (0..4)
+///     .map(|i| {
+///         let ring = ring.clone();
+///         thread::spawn(move || ring.push(i).unwrap())
+///     })
+///     .collect();
+/// for producer in producers {
+///     producer.join().unwrap();
+/// }
+///
+/// let mut popped: Vec<_> = std::iter::from_fn(|| ring.pop()).collect();
+/// popped.sort();
+/// assert_eq!(popped, vec![0, 1, 2, 3]);
+/// ```
+pub struct AtomicRing<T, const N: usize> {
+    slots: [Slot<T>; N],
+    // position of the next push
+    head: AtomicUsize,
+    // position of the next pop
+    tail: AtomicUsize,
+}
+
+unsafe impl<T: Send, const N: usize> Send for AtomicRing<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for AtomicRing<T, N> {}
+
+impl<T, const N: usize> AtomicRing<T, N> {
+    /// Creates an empty `AtomicRing`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is not a power of two.
+    pub fn new() -> Self {
+        assert!(N.is_power_of_two(), "capacity must be a power of two");
+        AtomicRing {
+            slots: array::from_fn(|i| Slot {
+                seq: AtomicUsize::new(i),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            }),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the capacity of the ring.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of published elements.
+    ///
+    /// Concurrent pushes and pops may change it before it is looked at.
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        let len = head.wrapping_sub(tail);
+        // `tail` may have moved past the `head` read above it
+        if len > N {
+            0
+        } else {
+            len
+        }
+    }
+
+    /// Returns `true` if the ring holds no published elements.
+    ///
+    /// Concurrent pushes and pops may change it before it is looked at.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add an element to the back of the ring.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the ring is full.
+    pub fn push(&self, element: T) -> Result<(), CapacityError<T>> {
+        let mut pos = self.head.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos & (N - 1)];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq.wrapping_sub(pos) as isize;
+            if diff == 0 {
+                // claim the slot
+                match self.head.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe { (*slot.value.get()).as_mut_ptr().write(element) };
+                        // publish it
+                        slot.seq.store(pos.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // the slot still holds the element pushed one lap ago
+                return Err(CapacityError { element });
+            } else {
+                pos = self.head.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Removes the first element and returns it, or `None` if the ring holds
+    /// no published element.
+    pub fn pop(&self) -> Option<T> {
+        let mut pos = self.tail.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos & (N - 1)];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq.wrapping_sub(pos.wrapping_add(1)) as isize;
+            if diff == 0 {
+                match self.tail.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let element = unsafe { (*slot.value.get()).as_ptr().read() };
+                        // free the slot for the push one lap ahead
+                        slot.seq.store(pos.wrapping_add(N), Ordering::Release);
+                        return Some(element);
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // the slot is not published yet
+                return None;
+            } else {
+                pos = self.tail.load(Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Default for AtomicRing<T, N> {
+    #[inline]
+    fn default() -> Self {
+        AtomicRing::new()
+    }
+}
+
+impl<T, const N: usize> Drop for AtomicRing<T, N> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for AtomicRing<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AtomicRing")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::AtomicRing;
+    use CapacityError;
+
+    #[test]
+    fn test_push_pop() {
+        let ring: AtomicRing<usize, 4> = AtomicRing::new();
+        // go around the ring a few times
+        for lap in 0..3 {
+            for i in 0..4 {
+                ring.push(lap * 4 + i).unwrap();
+            }
+            assert_eq!(ring.push(99), Err(CapacityError { element: 99 }));
+            assert_eq!(ring.len(), 4);
+            for i in 0..4 {
+                assert_eq!(ring.pop(), Some(lap * 4 + i));
+            }
+            assert_eq!(ring.pop(), None);
+            assert!(ring.is_empty());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_capacity_not_power_of_two() {
+        let _ring: AtomicRing<u8, 6> = AtomicRing::new();
+    }
+
+    #[test]
+    fn test_drop_remaining() {
+        struct D(Arc<AtomicUsize>);
+        impl Drop for D {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        {
+            let ring: AtomicRing<D, 4> = AtomicRing::new();
+            for _ in 0..3 {
+                ring.push(D(dropped.clone())).ok().unwrap();
+            }
+            drop(ring.pop());
+            assert_eq!(dropped.load(Ordering::SeqCst), 1);
+        }
+        assert_eq!(dropped.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_concurrent() {
+        const PRODUCERS: usize = 4;
+        const PER_PRODUCER: usize = 1000;
+
+        let ring: Arc<AtomicRing<usize, 16>> = Arc::new(AtomicRing::new());
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let ring = ring.clone();
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        let mut element = p * PER_PRODUCER + i;
+                        while let Err(err) = ring.push(element) {
+                            element = err.element;
+                            thread::yield_now();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let mut seen = vec![false; PRODUCERS * PER_PRODUCER];
+        let mut last = [None; PRODUCERS];
+        let mut count = 0;
+        while count < seen.len() {
+            match ring.pop() {
+                Some(element) => {
+                    assert!(!seen[element]);
+                    seen[element] = true;
+                    // each producer's elements come out in order
+                    let p = element / PER_PRODUCER;
+                    assert!(last[p] < Some(element));
+                    last[p] = Some(element);
+                    count += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        assert_eq!(ring.pop(), None);
+    }
+}
@@ -1,8 +1,8 @@
 //! Behavior semantics for `ArrayDeque`.
 //!
-//! `ArrayDeque` provides two different behaviors, `Saturating` and `Wrapping`,
-//! determining whether to remove existing element automatically when pushing
-//! to a full deque.
+//! `ArrayDeque` provides three different behaviors, `Saturating`, `Wrapping`
+//! and `Decimating`, determining whether and which existing elements to
+//! remove automatically when pushing to a full deque.
 //!
 //! The behavior is indicated by a marker type parameter of `ArrayDeque`,
 //! which defaults to `Saturating`.
@@ -36,16 +36,171 @@
 //! assert_eq!(tester.push_back(2), None);
 //! assert_eq!(tester.push_back(3), Some(1));
 //! ```
+//!
+//! ## Decimating
+//!
+//! Pushing any element to the back when `ArrayDeque` is full will first drop
+//! every second element, so the deque keeps spanning the same stretch of
+//! history at a reduced resolution. This suits telemetry windows where the
+//! oldest samples are as interesting as the newest ones.
+//!
+//! ```
+//! use arraydeque::{ArrayDeque, Decimating};
+//!
+//! let mut tester: ArrayDeque<[_; 4], Decimating> = ArrayDeque::new();
+//!
+//! tester.extend_back(0..4);
+//! assert_eq!(tester.push_back(4), true);
+//! assert_eq!(tester, vec![0, 2, 4].into());
+//! ```
+//!
+//! ## Hooks
+//!
+//! Every behavior takes an optional `Hooks` parameter that is told about every
+//! rejected push or kicked out element.
+//!
+//! ```
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use arraydeque::{ArrayDeque, Hooks, Wrapping};
+//!
+//! static EVICTED: AtomicUsize = AtomicUsize::new(0);
+//!
+//! struct CountEvictions;
+//!
+//! impl<T> Hooks<T> for CountEvictions {
+//!     fn on_evict(_element: &T) {
+//!         EVICTED.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let mut tester: ArrayDeque<[_; 2], Wrapping<CountEvictions>> = ArrayDeque::new();
+//!
+//! tester.extend_back(1..6);
+//! assert_eq!(EVICTED.load(Ordering::Relaxed), 3);
+//! ```
+
+use std::marker::PhantomData;
 
 /// Marker trait for indicating behaviors of `ArrayDeque`.
 pub trait Behavior {}
 
 /// Behavior for `ArrayDeque` that specifies saturating write semantics.
-pub struct Saturating;
+///
+/// Rejected pushes are reported to the hooks `H`.
+pub struct Saturating<H = NoHooks> {
+    hooks: PhantomData<H>,
+}
+
+/// The saturating behavior without hooks, as a value, as when `Saturating` was
+/// a unit struct.
+#[allow(non_upper_case_globals)]
+pub const Saturating: Saturating = Saturating { hooks: PhantomData };
 
-impl Behavior for Saturating {}
+impl<H> Behavior for Saturating<H> {}
 
 /// Behavior for `ArrayDeque` that specifies wrapping write semantics.
-pub struct Wrapping;
+///
+/// Kicked out elements are reported to the hooks `H`.
+pub struct Wrapping<H = NoHooks> {
+    hooks: PhantomData<H>,
+}
+
+/// The wrapping behavior without hooks, as a value, as when `Wrapping` was
+/// a unit struct.
+#[allow(non_upper_case_globals)]
+pub const Wrapping: Wrapping = Wrapping { hooks: PhantomData };
+
+impl<H> Behavior for Wrapping<H> {}
+
+/// Behavior for `ArrayDeque` that thins out a full deque instead of kicking
+/// out its oldest element.
+///
+/// Kicked out elements are reported to the hooks `H`.
+pub struct Decimating<H = NoHooks> {
+    hooks: PhantomData<H>,
+}
+
+/// The decimating behavior without hooks, as a value, as when `Decimating` was
+/// a unit struct.
+#[allow(non_upper_case_globals)]
+pub const Decimating: Decimating = Decimating { hooks: PhantomData };
+
+impl<H> Behavior for Decimating<H> {}
+
+/// Callbacks invoked when a push meets a full `ArrayDeque`.
+///
+/// Hooks are picked statically through the behavior parameter, as in
+/// `ArrayDeque<A, Wrapping<MyHooks>>`, so instrumentation does not need to
+/// wrap every call site. They are invoked by the methods specific to a
+/// behavior:
+///
+/// - `Saturating`: `push_front`, `push_back`, `insert`, `push_back_unique`,
+///   `push_back_absent` and the pushes and insertions of a `Transaction`
+/// - `Wrapping`: `push_front`, `push_back`, `push_back_pinned`, every
+///   `extend_front*` and `extend_back*` method, `extend_from_slice`,
+///   `Extend`, `FromIterator` and the `push_str` of byte deques
+/// - `Decimating`: `push_back`, `extend_back`, `Extend` and `FromIterator`
+///
+/// They are not invoked by the methods shared by every behavior, such as
+/// `push_front_overwrite`, `push_back_overwrite`, `try_push_front`,
+/// `try_push_back`, `try_extend_from_slice`, `extend_from_copy_slice` and
+/// the spare regions, nor by the `extern-c` functions. Neither are they
+/// invoked by the `Saturating` `extend_front` and `extend_back`, which stop
+/// taking elements once the deque is full, nor by the `Saturating` byte
+/// helpers such as `push_str` and `push_u32_le`, which reject whole slices
+/// and values rather than elements.
+///
+/// With the `strict` feature, the same pushes also check the length against
+/// `SOFT_LIMIT`.
+///
+/// The `Wrapping` ones never kick out the first `PINNED` elements.
+///
+/// Every method does nothing by default.
+pub trait Hooks<T> {
+    /// Called with an element that a `Saturating` deque rejected because it
+    /// was full, or that a `Wrapping` deque rejected because every element
+    /// was pinned.
+    #[inline]
+    fn on_full(_element: &T) {}
+
+    /// Called with an element that a `Wrapping` or `Decimating` deque kicked
+    /// out to spare room.
+    #[inline]
+    fn on_evict(_element: &T) {}
+
+    /// The number of elements at the front of a `Wrapping` deque that are
+    /// never kicked out, such as a header kept while the rest of a ring
+    /// cycles.
+    ///
+    /// Pushes to the front go right after the pinned elements. None by
+    /// default.
+    const PINNED: usize = 0;
+
+    /// The length a deque is not expected to grow past in normal operation,
+    /// below its capacity.
+    ///
+    /// Only checked with the `strict` feature enabled. Unlimited by default.
+    const SOFT_LIMIT: usize = !0;
+
+    /// Called with the new length after a push grew a deque past
+    /// `SOFT_LIMIT`.
+    ///
+    /// Only called with the `strict` feature enabled. Panics in debug builds
+    /// by default, to catch runaway queue growth before it reaches the hard
+    /// capacity.
+    #[inline]
+    fn on_soft_limit(len: usize) {
+        if cfg!(debug_assertions) {
+            panic!(
+                "deque length {} exceeds the soft limit {}",
+                len,
+                Self::SOFT_LIMIT
+            );
+        }
+    }
+}
+
+/// Hooks that do nothing, used by default.
+pub struct NoHooks;
 
-impl Behavior for Wrapping {}
+impl<T> Hooks<T> for NoHooks {}
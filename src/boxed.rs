@@ -0,0 +1,107 @@
+//! Iteration over boxed deques without moving them onto the stack.
+
+use array::Array;
+use behavior::Behavior;
+use {ArrayDeque, Iter, IterMut};
+
+/// By-value iterator over a boxed `ArrayDeque`.
+///
+/// Elements are popped from the deque in place on the heap, so the backing
+/// array is never copied onto the stack, which matters for large deques.
+///
+/// This `struct` is created by the `into_iter` method on
+/// `Box<ArrayDeque>`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct BoxedIntoIter<A: Array, B: Behavior> {
+    inner: Box<ArrayDeque<A, B>>,
+}
+
+impl<A: Array, B: Behavior> Iterator for BoxedIntoIter<A, B> {
+    type Item = A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<A::Item> {
+        self.inner.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len();
+        (len, Some(len))
+    }
+}
+
+impl<A: Array, B: Behavior> DoubleEndedIterator for BoxedIntoIter<A, B> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A::Item> {
+        self.inner.pop_back()
+    }
+}
+
+impl<A: Array, B: Behavior> ExactSizeIterator for BoxedIntoIter<A, B> {}
+
+impl<A: Array, B: Behavior> IntoIterator for Box<ArrayDeque<A, B>> {
+    type Item = A::Item;
+    type IntoIter = BoxedIntoIter<A, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        BoxedIntoIter { inner: self }
+    }
+}
+
+impl<'a, A: Array, B: Behavior> IntoIterator for &'a Box<ArrayDeque<A, B>> {
+    type Item = &'a A::Item;
+    type IntoIter = Iter<'a, A::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, A: Array, B: Behavior> IntoIterator for &'a mut Box<ArrayDeque<A, B>> {
+    type Item = &'a mut A::Item;
+    type IntoIter = IterMut<'a, A::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_must_use)]
+    use {ArrayDeque, Wrapping};
+
+    #[test]
+    fn test_boxed_iter() {
+        const CAP: usize = 5;
+        let mut tester: Box<ArrayDeque<[String; CAP], Wrapping>> = Box::default();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            tester.extend_back((0..CAP + 1).map(|i| i.to_string()));
+            for s in &mut tester {
+                s.push('!');
+            }
+            let mut all = vec![];
+            for s in &tester {
+                all.push(s.clone());
+            }
+            assert_eq!(all, vec!["1!", "2!", "3!", "4!", "5!"]);
+
+            let mut copy: Box<ArrayDeque<[String; CAP], Wrapping>> = Box::default();
+            copy.push_back("stale".to_string());
+            copy.clone_from(&tester);
+            assert_eq!(copy, tester);
+
+            let mut iter = copy.into_iter();
+            assert_eq!(iter.len(), 5);
+            assert_eq!(iter.next_back(), Some("5!".to_string()));
+            assert_eq!(iter.next(), Some("1!".to_string()));
+            // the remaining elements are dropped along with the box
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Fluent construction of an `ArrayDeque`.
+
+use std::fmt;
+
+use array::Array;
+use behavior::{Behavior, Saturating};
+use error::CapacityError;
+use ArrayDeque;
+
+/// A builder that collects pushes into an `ArrayDeque` and checks the
+/// capacity only once, in `build`.
+///
+/// Elements that do not fit are dropped as they are pushed, except for the
+/// first one, which is handed back by `build` inside the error.
+///
+/// This `struct` is created by the `builder` method on `ArrayDeque`.
+#[must_use = "a builder does nothing unless `build` is called"]
+pub struct DequeBuilder<A: Array, B: Behavior = Saturating> {
+    deque: ArrayDeque<A, B>,
+    overflow: Option<A::Item>,
+}
+
+impl<A: Array, B: Behavior> DequeBuilder<A, B> {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        DequeBuilder {
+            deque: ArrayDeque::new(),
+            overflow: None,
+        }
+    }
+
+    /// Adds an element to the back of the deque.
+    #[inline]
+    pub fn push(mut self, element: A::Item) -> Self {
+        if self.deque.is_full() {
+            if self.overflow.is_none() {
+                self.overflow = Some(element);
+            }
+        } else {
+            unsafe { self.deque.push_back_unchecked(element) }
+        }
+        self
+    }
+
+    /// Adds an element to the front of the deque.
+    #[inline]
+    pub fn push_front(mut self, element: A::Item) -> Self {
+        if self.deque.is_full() {
+            if self.overflow.is_none() {
+                self.overflow = Some(element);
+            }
+        } else {
+            unsafe { self.deque.push_front_unchecked(element) }
+        }
+        self
+    }
+
+    /// Adds every element of an iterator to the back of the deque.
+    pub fn extend<I>(self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        iter.into_iter().fold(self, DequeBuilder::push)
+    }
+
+    /// Finishes the deque.
+    ///
+    /// Return `Ok(deque)` if every pushed element fit, or return
+    /// `Err(CapacityError { *element* })` with the first element that did not.
+    #[inline]
+    pub fn build(self) -> Result<ArrayDeque<A, B>, CapacityError<A::Item>> {
+        match self.overflow {
+            Some(element) => Err(CapacityError { element }),
+            None => Ok(self.deque),
+        }
+    }
+}
+
+impl<A: Array, B: Behavior> fmt::Debug for DequeBuilder<A, B>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DequeBuilder")
+            .field("deque", &self.deque)
+            .field("overflow", &self.overflow)
+            .finish()
+    }
+}
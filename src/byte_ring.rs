@@ -0,0 +1,234 @@
+//! A byte ring buffer for streaming I/O.
+
+use std::fmt;
+use std::io::{self, BufRead, IoSlice, IoSliceMut, Read, Write};
+
+use ConstDeque;
+
+/// A ring buffer of `N` bytes that implements `Read`, `BufRead` and `Write`.
+///
+/// Bytes are always moved in at most two `memcpy`s of the contiguous halves
+/// of the ring, never one at a time. The storage is an `ArrayDeque`, which
+/// `as_deque` exposes for the rest of its API.
+///
+/// Requires Rust 1.63+.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{BufRead, Read, Write};
+/// use arraydeque::ByteRing;
+///
+/// let mut pipe: ByteRing<8> = ByteRing::new();
+///
+/// // only the bytes that fit are written
+/// assert_eq!(pipe.write(b"hello world").unwrap(), 8);
+///
+/// let mut word = [0; 5];
+/// pipe.read_exact(&mut word).unwrap();
+/// assert_eq!(&word, b"hello");
+///
+/// pipe.write_all(b"wo").unwrap();
+/// let mut rest = String::new();
+/// pipe.read_line(&mut rest).unwrap();
+/// assert_eq!(rest, " wowo");
+/// ```
+pub struct ByteRing<const N: usize> {
+    deque: ConstDeque<u8, N>,
+}
+
+impl<const N: usize> ByteRing<N> {
+    /// Creates an empty `ByteRing`.
+    #[inline]
+    pub fn new() -> Self {
+        ByteRing {
+            deque: ConstDeque::new(),
+        }
+    }
+
+    /// Returns the capacity of the ring, in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of buffered bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    /// Returns true if the ring holds no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Returns true if the ring is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.deque.is_full()
+    }
+
+    /// Returns the number of bytes that can still be written.
+    #[inline]
+    pub fn available(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Returns the underlying deque.
+    #[inline]
+    pub fn as_deque(&self) -> &ConstDeque<u8, N> {
+        &self.deque
+    }
+
+    /// Returns the buffered bytes as a pair of slices, front part first.
+    #[inline]
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        self.deque.as_slices()
+    }
+
+    /// Returns the buffered bytes as a pair of `IoSlice`s, front part first,
+    /// ready for a vectored write.
+    #[inline]
+    pub fn as_io_slices(&self) -> [IoSlice<'_>; 2] {
+        let (a, b) = self.as_slices();
+        [IoSlice::new(a), IoSlice::new(b)]
+    }
+
+    /// Appends as many bytes of `src` as fit and returns their number.
+    #[inline]
+    pub fn push_slice(&mut self, src: &[u8]) -> usize {
+        self.deque.copy_prefix_to_back(src)
+    }
+
+    /// Copies the first bytes into `dst`, without removing them, and returns
+    /// their number.
+    pub fn peek_slice(&self, dst: &mut [u8]) -> usize {
+        self.deque.copy_prefix_from_front(dst)
+    }
+
+    /// Moves the first bytes into `dst` and returns their number.
+    #[inline]
+    pub fn pop_slice(&mut self, dst: &mut [u8]) -> usize {
+        let n = self.peek_slice(dst);
+        self.discard(n);
+        n
+    }
+
+    /// Removes the first `n` bytes, or every byte if there are fewer.
+    pub fn discard(&mut self, n: usize) {
+        self.deque.discard_front(n);
+    }
+
+    /// Removes every byte.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.deque.clear();
+    }
+}
+
+impl<const N: usize> Default for ByteRing<N> {
+    #[inline]
+    fn default() -> Self {
+        ByteRing::new()
+    }
+}
+
+impl<const N: usize> Read for ByteRing<N> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.deque.read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.deque.read_vectored(bufs)
+    }
+}
+
+impl<const N: usize> BufRead for ByteRing<N> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.discard(amt);
+    }
+}
+
+impl<const N: usize> Write for ByteRing<N> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.deque.write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.deque.write_vectored(bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Debug for ByteRing<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ByteRing")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, IoSlice, Read, Write};
+
+    use super::ByteRing;
+
+    #[test]
+    fn test_read_write() {
+        let mut ring: ByteRing<6> = ByteRing::new();
+        let mut buf = [0; 4];
+        // go around the ring a few times
+        for lap in 0..6u8 {
+            assert_eq!(ring.write(&[lap, lap + 1, lap + 2, lap + 3]).unwrap(), 4);
+            assert_eq!(ring.read(&mut buf[..3]).unwrap(), 3);
+            assert_eq!(&buf[..3], &[lap, lap + 1, lap + 2]);
+            assert_eq!(ring.read(&mut buf).unwrap(), 1);
+            assert_eq!(buf[0], lap + 3);
+            assert!(ring.is_empty());
+        }
+        assert_eq!(ring.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_vectored() {
+        let mut ring: ByteRing<5> = ByteRing::new();
+        let bufs = [
+            IoSlice::new(b"ab"),
+            IoSlice::new(b"cde"),
+            IoSlice::new(b"f"),
+        ];
+        assert_eq!(ring.write_vectored(&bufs).unwrap(), 5);
+        assert!(ring.is_full());
+        assert_eq!(ring.write(b"g").unwrap(), 0);
+
+        ring.discard(3);
+        ring.write_all(b"fg").unwrap();
+        let slices = ring.as_io_slices();
+        let joined: Vec<u8> = slices.iter().flat_map(|s| s.iter().cloned()).collect();
+        assert_eq!(joined, b"defg");
+
+        // the first half ends at the physical end of the ring
+        assert_eq!(ring.fill_buf().unwrap(), b"de");
+        ring.consume(2);
+        assert_eq!(ring.fill_buf().unwrap(), b"fg");
+        assert_eq!(ring.available(), 3);
+    }
+}
@@ -0,0 +1,493 @@
+//! Integer framing and UTF-8 text helpers for byte deques.
+
+use std::cmp;
+use std::fmt;
+use std::str;
+
+use array::Array;
+use behavior::{Behavior, Hooks, Saturating, Wrapping};
+use error::CapacityError;
+#[cfg(feature = "trace")]
+use trace;
+use ArrayDeque;
+
+/// Returns the width of the UTF-8 sequence started by `byte`.
+///
+/// Continuation and invalid bytes count as a sequence of their own.
+#[inline]
+fn utf8_width(byte: u8) -> usize {
+    match byte {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    }
+}
+
+impl<A: Array<Item = u8>, B: Behavior> ArrayDeque<A, B> {
+    /// Copies the first `buf.len()` bytes into `buf`, without removing them.
+    ///
+    /// Return `false`, leaving `buf` untouched, if the deque holds fewer bytes.
+    fn peek_bytes(&self, buf: &mut [u8]) -> bool {
+        if self.len() < buf.len() {
+            return false;
+        }
+        self.copy_prefix_from_front(buf);
+        true
+    }
+
+    /// Removes the UTF-8 encoded `char` at the front of the deque and returns
+    /// it.
+    ///
+    /// Returns `None`, leaving the deque unchanged, if the deque is empty or
+    /// does not start with a complete, valid UTF-8 sequence. A multi-byte
+    /// sequence is never split.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[u8; 8]> = ArrayDeque::new();
+    ///
+    /// buf.push_str("aé").unwrap();
+    ///
+    /// assert_eq!(buf.pop_front_char(), Some('a'));
+    /// assert_eq!(buf.pop_front_char(), Some('é'));
+    /// assert_eq!(buf.pop_front_char(), None);
+    /// ```
+    pub fn pop_front_char(&mut self) -> Option<char> {
+        let width = match self.front() {
+            Some(&byte) => utf8_width(byte),
+            None => return None,
+        };
+        let mut buf = [0; 4];
+        if !self.peek_bytes(&mut buf[..width]) {
+            return None;
+        }
+        let c = match str::from_utf8(&buf[..width]) {
+            Ok(s) => s.chars().next(),
+            Err(_) => None,
+        };
+        if c.is_some() {
+            self.discard_front(width);
+        }
+        c
+    }
+}
+
+impl<A: Array<Item = u8>, H: Hooks<u8>> ArrayDeque<A, Saturating<H>> {
+    /// Appends the UTF-8 bytes of `s` to the back of the deque.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return
+    /// `Err(CapacityError { *s* })`, leaving the deque unchanged, if there is
+    /// not enough room for the whole string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.push_str("ab"), Ok(()));
+    /// assert_eq!(buf.push_str("cde"), Err(CapacityError { element: "cde" }));
+    /// assert_eq!(buf.len(), 2);
+    /// ```
+    pub fn push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+        if self.capacity() - self.len() < s.len() {
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
+            return Err(CapacityError { element: s });
+        }
+        for &byte in s.as_bytes() {
+            unsafe {
+                self.push_back_unchecked(byte);
+            }
+        }
+        #[cfg(feature = "trace")]
+        self.trace_pushed_back(s.len());
+        Ok(())
+    }
+}
+
+impl<A: Array<Item = u8>, H: Hooks<u8>> ArrayDeque<A, Wrapping<H>> {
+    /// Appends the UTF-8 bytes of `s` to the back of the deque.
+    ///
+    /// Whole chars are kicked out from the front to spare room, so a
+    /// multi-byte sequence is never split. If `s` itself is longer than the
+    /// capacity, only its trailing chars that fit are kept.
+    ///
+    /// The first `Hooks::PINNED` bytes are never kicked out, chars are kicked
+    /// out right after them instead.
+    ///
+    /// Every byte kicked out, or left out of `s`, is reported to the hooks,
+    /// to `on_full` if the deque is full of pinned bytes and to `on_evict`
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4], Wrapping> = ArrayDeque::new();
+    ///
+    /// buf.push_str("éa");
+    /// buf.push_str("bc");
+    ///
+    /// // 'é' takes two bytes, so both are kicked out
+    /// assert_eq!(buf, vec![b'a', b'b', b'c'].into());
+    /// ```
+    pub fn push_str(&mut self, s: &str) {
+        let pinned = cmp::min(H::PINNED, self.len());
+        let room = self.capacity() - pinned;
+        let s = if s.len() > room {
+            let mut start = s.len() - room;
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+            while let Some(byte) = self.evict_after(pinned) {
+                H::on_evict(&byte);
+                #[cfg(feature = "trace")]
+                self.trace(trace::Op::Evict);
+            }
+            for byte in s[..start].bytes() {
+                if room == 0 {
+                    H::on_full(&byte);
+                    #[cfg(feature = "trace")]
+                    self.trace(trace::Op::Reject);
+                } else {
+                    H::on_evict(&byte);
+                    #[cfg(feature = "trace")]
+                    self.trace(trace::Op::Evict);
+                }
+            }
+            &s[start..]
+        } else {
+            s
+        };
+        while self.capacity() - self.len() < s.len() {
+            let width = match self.get(pinned) {
+                Some(&byte) => utf8_width(byte),
+                None => break,
+            };
+            for _ in 0..width {
+                match self.evict_after(pinned) {
+                    Some(byte) => {
+                        H::on_evict(&byte);
+                        #[cfg(feature = "trace")]
+                        self.trace(trace::Op::Evict);
+                    }
+                    None => break,
+                }
+            }
+        }
+        for &byte in s.as_bytes() {
+            unsafe {
+                self.push_back_unchecked(byte);
+            }
+        }
+        #[cfg(feature = "trace")]
+        self.trace_pushed_back(s.len());
+    }
+
+    /// Removes the first byte after the `pinned` ones, without reporting it.
+    fn evict_after(&mut self, pinned: usize) -> Option<u8> {
+        if pinned == 0 {
+            self.pop_front_untraced()
+        } else if pinned < self.len() {
+            self.remove(pinned)
+        } else {
+            None
+        }
+    }
+}
+
+/// Appends formatted text with `write!`, into a bounded buffer.
+///
+/// Every piece of the formatted text is pushed whole with `push_str`, so a
+/// piece that does not fit fails with `fmt::Error` and is left out, while
+/// the pieces before it stay in the deque.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write;
+/// use arraydeque::ArrayDeque;
+///
+/// let mut buf: ArrayDeque<[u8; 8]> = ArrayDeque::new();
+///
+/// write!(buf, "{}-{}", 12, 34).unwrap();
+/// assert!(write!(buf, "{}", 5678).is_err());
+/// assert_eq!(buf, b"12-34".to_vec().into());
+/// ```
+impl<A: Array<Item = u8>, H: Hooks<u8>> fmt::Write for ArrayDeque<A, Saturating<H>> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+/// Appends formatted text with `write!`, keeping its most recent chars.
+///
+/// Whole chars are kicked out from the front to spare room, as with
+/// `push_str`, so writing never fails.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write;
+/// use arraydeque::{ArrayDeque, Wrapping};
+///
+/// let mut log: ArrayDeque<[u8; 8], Wrapping> = ArrayDeque::new();
+///
+/// for i in 0..5 {
+///     write!(log, "{};", i).unwrap();
+/// }
+/// assert_eq!(log, b"1;2;3;4;".to_vec().into());
+/// ```
+impl<A: Array<Item = u8>, H: Hooks<u8>> fmt::Write for ArrayDeque<A, Wrapping<H>> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+macro_rules! int_helpers {
+    ($ty:ident, $size:expr, $from:ident, $to:ident, $peek:ident, $pop:ident, $push:ident, $order:expr) => {
+        impl<A: Array<Item = u8>, B: Behavior> ArrayDeque<A, B> {
+            #[doc = concat!("Reads a `", stringify!($ty), "` in ", $order, " byte order from the front")]
+            /// of the deque, without removing it.
+            ///
+            /// Returns `None` if the deque holds too few bytes.
+            #[inline]
+            pub fn $peek(&self) -> Option<$ty> {
+                let mut buf = [0; $size];
+                if self.peek_bytes(&mut buf) {
+                    Some($ty::$from(buf))
+                } else {
+                    None
+                }
+            }
+
+            #[doc = concat!("Removes a `", stringify!($ty), "` in ", $order, " byte order from the front")]
+            /// of the deque and returns it.
+            ///
+            /// Returns `None`, leaving the deque unchanged, if it holds too few
+            /// bytes.
+            #[inline]
+            pub fn $pop(&mut self) -> Option<$ty> {
+                let value = self.$peek();
+                if value.is_some() {
+                    self.discard_front($size);
+                }
+                value
+            }
+        }
+
+        impl<A: Array<Item = u8>, H: Hooks<u8>> ArrayDeque<A, Saturating<H>> {
+            #[doc = concat!("Appends a `", stringify!($ty), "` in ", $order, " byte order to the back")]
+            /// of the deque.
+            ///
+            /// Return `Ok(())` if the push succeeds, or return
+            /// `Err(CapacityError { *value* })`, leaving the deque unchanged, if
+            /// there is not enough room for every byte.
+            #[inline]
+            pub fn $push(&mut self, value: $ty) -> Result<(), CapacityError<$ty>> {
+                if self.capacity() - self.len() < $size {
+                    #[cfg(feature = "trace")]
+                    self.trace(trace::Op::Reject);
+                    return Err(CapacityError { element: value });
+                }
+                for &byte in value.$to().iter() {
+                    unsafe {
+                        self.push_back_unchecked(byte);
+                    }
+                }
+                #[cfg(feature = "trace")]
+                self.trace_pushed_back($size);
+                Ok(())
+            }
+        }
+    };
+}
+
+int_helpers!(u16, 2, from_le_bytes, to_le_bytes, peek_u16_le, pop_u16_le, push_u16_le, "little-endian");
+int_helpers!(u16, 2, from_be_bytes, to_be_bytes, peek_u16_be, pop_u16_be, push_u16_be, "big-endian");
+int_helpers!(u32, 4, from_le_bytes, to_le_bytes, peek_u32_le, pop_u32_le, push_u32_le, "little-endian");
+int_helpers!(u32, 4, from_be_bytes, to_be_bytes, peek_u32_be, pop_u32_be, push_u32_be, "big-endian");
+int_helpers!(u64, 8, from_le_bytes, to_le_bytes, peek_u64_le, pop_u64_le, push_u64_le, "little-endian");
+int_helpers!(u64, 8, from_be_bytes, to_be_bytes, peek_u64_be, pop_u64_be, push_u64_be, "big-endian");
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_must_use)]
+    use {ArrayDeque, CapacityError, Wrapping};
+
+    #[test]
+    fn test_int_helpers_wrap() {
+        const CAP: usize = 9;
+        let mut tester: ArrayDeque<[u8; CAP]> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            tester.push_u16_be(0x0102).unwrap();
+            tester.push_u16_le(0x0102).unwrap();
+            tester.push_u32_be(0x0304_0506).unwrap();
+            assert_eq!(tester, vec![1, 2, 2, 1, 3, 4, 5, 6].into());
+            assert_eq!(tester.push_u16_le(7), Err(CapacityError { element: 7 }));
+            assert_eq!(tester.len(), 8);
+
+            assert_eq!(tester.peek_u64_le(), Some(0x0605_0403_0102_0201));
+            assert_eq!(tester.pop_u16_be(), Some(0x0102));
+            assert_eq!(tester.pop_u16_le(), Some(0x0102));
+            assert_eq!(tester.pop_u64_be(), None);
+            assert_eq!(tester.pop_u32_le(), Some(0x0605_0403));
+            assert!(tester.is_empty());
+
+            tester.push_u64_be(0x0102_0304_0506_0708).unwrap();
+            assert_eq!(tester.pop_u32_be(), Some(0x0102_0304));
+            assert_eq!(tester.pop_u32_be(), Some(0x0506_0708));
+        }
+    }
+
+    #[test]
+    fn test_text_wrap() {
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[u8; CAP], Wrapping> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            tester.push_str("a€");
+            tester.push_str("ü");
+            assert_eq!(tester.len(), 6);
+            tester.push_str("bc€");
+            // 'a' and '€' are kicked out as whole chars
+            assert_eq!(tester.len(), 7);
+            assert_eq!(tester.pop_front_char(), Some('ü'));
+            assert_eq!(tester.pop_front_char(), Some('b'));
+            assert_eq!(tester.pop_front_char(), Some('c'));
+            assert_eq!(tester.pop_front_char(), Some('€'));
+
+            tester.push_str("0123456€");
+            assert_eq!(tester.len(), 7);
+            assert_eq!(tester.pop_front_char(), Some('3'));
+            assert_eq!(tester.pop_front_char(), Some('4'));
+            assert_eq!(tester.pop_front_char(), Some('5'));
+            assert_eq!(tester.pop_front_char(), Some('6'));
+            assert_eq!(tester.pop_front_char(), Some('€'));
+            assert_eq!(tester.pop_front_char(), None);
+        }
+    }
+
+    #[test]
+    fn test_text_wrap_hooks() {
+        use std::cell::RefCell;
+        use Hooks;
+
+        thread_local! {
+            static EVICTED: RefCell<Vec<u8>> = RefCell::new(Vec::new());
+        }
+
+        struct Record;
+
+        impl Hooks<u8> for Record {
+            fn on_evict(element: &u8) {
+                EVICTED.with(|evicted| evicted.borrow_mut().push(*element));
+            }
+        }
+
+        let mut tester: ArrayDeque<[u8; 4], Wrapping<Record>> = ArrayDeque::new();
+        tester.push_str("a€");
+        tester.push_str("b");
+        assert_eq!(EVICTED.with(|evicted| evicted.borrow().clone()), b"a");
+        tester.push_str("cdefg");
+        assert_eq!(tester, b"defg".to_vec().into());
+        assert_eq!(
+            EVICTED.with(|evicted| evicted.borrow().clone()),
+            "a€bc".as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_text_wrap_pinned() {
+        use std::cell::RefCell;
+        use Hooks;
+
+        thread_local! {
+            static REJECTED: RefCell<Vec<u8>> = RefCell::new(Vec::new());
+        }
+
+        struct Prompt;
+
+        impl Hooks<u8> for Prompt {
+            const PINNED: usize = 2;
+
+            fn on_full(element: &u8) {
+                REJECTED.with(|rejected| rejected.borrow_mut().push(*element));
+            }
+        }
+
+        let mut tester: ArrayDeque<[u8; 5], Wrapping<Prompt>> = ArrayDeque::new();
+        tester.push_str("> ab");
+        tester.push_str("€");
+        assert_eq!(tester, "> €".as_bytes().to_vec().into());
+        tester.push_str("cdefg");
+        assert_eq!(tester, b"> efg".to_vec().into());
+
+        let mut full: ArrayDeque<[u8; 2], Wrapping<Prompt>> = ArrayDeque::new();
+        full.push_str("> ");
+        full.push_str("ab");
+        assert_eq!(full, b"> ".to_vec().into());
+        assert_eq!(REJECTED.with(|rejected| rejected.borrow().clone()), b"ab");
+    }
+
+    #[test]
+    fn test_fmt_write() {
+        use std::fmt::Write;
+
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[u8; CAP]> = ArrayDeque::new();
+        let mut wrapping: ArrayDeque<[u8; CAP], Wrapping> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+                wrapping.set_len(0);
+                wrapping.set_tail(padding);
+            }
+            write!(tester, "{}€", 42).unwrap();
+            assert_eq!(write!(tester, "{}", "€"), Err(::std::fmt::Error));
+            tester.write_char('!').unwrap();
+            assert_eq!(tester.pop_front_char(), Some('4'));
+            assert_eq!(tester.pop_front_char(), Some('2'));
+            assert_eq!(tester.pop_front_char(), Some('€'));
+            assert_eq!(tester.pop_front_char(), Some('!'));
+            assert!(tester.is_empty());
+
+            write!(wrapping, "{}€{}", 12, 345).unwrap();
+            assert_eq!(wrapping.len(), 7);
+            assert_eq!(wrapping.pop_front_char(), Some('2'));
+            assert_eq!(wrapping.pop_front_char(), Some('€'));
+        }
+    }
+
+    #[test]
+    fn test_pop_front_char_incomplete() {
+        let mut tester: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+        tester.push_back(0xE2);
+        tester.push_back(0x82);
+        assert_eq!(tester.pop_front_char(), None);
+        assert_eq!(tester.len(), 2);
+        tester.push_back(0xAC);
+        assert_eq!(tester.pop_front_char(), Some('€'));
+    }
+}
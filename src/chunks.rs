@@ -0,0 +1,146 @@
+//! Iterators over fixed-size chunks of an `ArrayDeque`.
+
+use std::array;
+use std::iter::FusedIterator;
+
+use array::Array;
+use behavior::Behavior;
+use {ArrayDeque, Iter};
+
+/// An iterator over the elements of an `ArrayDeque` in non-overlapping
+/// chunks of `K` elements, copied into arrays.
+///
+/// When fewer than `K` elements remain, they are not yielded.
+///
+/// This `struct` is created by the `iter_chunks` method on `ArrayDeque`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Chunks<'a, T: 'a, const K: usize> {
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T, const K: usize> Chunks<'a, T, K> {
+    #[inline]
+    pub(crate) fn new(iter: Iter<'a, T>) -> Self {
+        assert!(K != 0, "chunk size must be non-zero");
+        Chunks { iter }
+    }
+}
+
+impl<'a, T: Copy, const K: usize> Iterator for Chunks<'a, T, K> {
+    type Item = [T; K];
+
+    #[inline]
+    fn next(&mut self) -> Option<[T; K]> {
+        if self.iter.len() < K {
+            return None;
+        }
+        let iter = &mut self.iter;
+        Some(array::from_fn(|_| match iter.next() {
+            Some(&element) => element,
+            None => unreachable!(),
+        }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.len() / K;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Copy, const K: usize> ExactSizeIterator for Chunks<'a, T, K> {}
+
+impl<'a, T: Copy, const K: usize> FusedIterator for Chunks<'a, T, K> {}
+
+/// An iterator over the elements of an `ArrayDeque` in non-overlapping
+/// chunks of `K` elements, as arrays of references.
+///
+/// When fewer than `K` elements remain, they are not yielded.
+///
+/// This `struct` is created by the `iter_chunk_refs` method on `ArrayDeque`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct ChunkRefs<'a, T: 'a, const K: usize> {
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T, const K: usize> ChunkRefs<'a, T, K> {
+    #[inline]
+    pub(crate) fn new(iter: Iter<'a, T>) -> Self {
+        assert!(K != 0, "chunk size must be non-zero");
+        ChunkRefs { iter }
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for ChunkRefs<'a, T, K> {
+    type Item = [&'a T; K];
+
+    #[inline]
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        if self.iter.len() < K {
+            return None;
+        }
+        let iter = &mut self.iter;
+        Some(array::from_fn(|_| match iter.next() {
+            Some(element) => element,
+            None => unreachable!(),
+        }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.len() / K;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const K: usize> ExactSizeIterator for ChunkRefs<'a, T, K> {}
+
+impl<'a, T, const K: usize> FusedIterator for ChunkRefs<'a, T, K> {}
+
+/// A draining iterator over the front elements of an `ArrayDeque` in
+/// non-overlapping chunks of `K` elements, moved into arrays.
+///
+/// Each chunk is only removed from the deque when it is yielded. When fewer
+/// than `K` elements remain, they are left in the deque.
+///
+/// This `struct` is created by the `drain_chunks` method on `ArrayDeque`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct DrainChunks<'a, A: 'a + Array, B: 'a + Behavior, const K: usize> {
+    deque: &'a mut ArrayDeque<A, B>,
+}
+
+impl<'a, A: Array, B: Behavior, const K: usize> DrainChunks<'a, A, B, K> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, B>) -> Self {
+        assert!(K != 0, "chunk size must be non-zero");
+        DrainChunks { deque }
+    }
+}
+
+impl<'a, A: Array, B: Behavior, const K: usize> Iterator for DrainChunks<'a, A, B, K> {
+    type Item = [A::Item; K];
+
+    #[inline]
+    fn next(&mut self) -> Option<[A::Item; K]> {
+        if self.deque.len() < K {
+            return None;
+        }
+        let deque = &mut *self.deque;
+        Some(array::from_fn(|_| match deque.pop_front() {
+            Some(element) => element,
+            None => unreachable!(),
+        }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.deque.len() / K;
+        (len, Some(len))
+    }
+}
+
+impl<'a, A: Array, B: Behavior, const K: usize> ExactSizeIterator for DrainChunks<'a, A, B, K> {}
+
+impl<'a, A: Array, B: Behavior, const K: usize> FusedIterator for DrainChunks<'a, A, B, K> {}
@@ -0,0 +1,270 @@
+//! A common interface over double-ended queues.
+
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+use array::Array;
+use behavior::{Hooks, Saturating, Wrapping};
+use error::CapacityError;
+use ArrayDeque;
+#[cfg(feature = "std")]
+use SmallDeque;
+
+/// Common read/write operations shared by `ArrayDeque` and `VecDeque`.
+///
+/// Libraries can accept any `DequeLike` value, which lets their users switch
+/// between a growable `VecDeque` and a fixed capacity `ArrayDeque` without
+/// code changes.
+///
+/// Pushing returns `Err(CapacityError { *element* })` if the element could not
+/// be stored. This only happens to `ArrayDeque<_, Saturating>`: a
+/// `VecDeque` grows instead, and `ArrayDeque<_, Wrapping>` drops the element
+/// at the other end to spare room.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::{ArrayDeque, DequeLike};
+/// use std::collections::VecDeque;
+///
+/// fn fill<D: DequeLike<Item = i32>>(deque: &mut D) {
+///     for i in 0..3 {
+///         let _ = deque.push_back(i);
+///     }
+/// }
+///
+/// let mut fixed: ArrayDeque<[_; 2]> = ArrayDeque::new();
+/// let mut growable = VecDeque::new();
+///
+/// fill(&mut fixed);
+/// fill(&mut growable);
+///
+/// assert_eq!(DequeLike::len(&fixed), 2);
+/// assert_eq!(DequeLike::len(&growable), 3);
+/// ```
+pub trait DequeLike {
+    /// The element type of the deque.
+    type Item;
+
+    /// Returns the number of elements in the deque.
+    fn len(&self) -> usize;
+
+    /// Returns true if the deque contains no elements.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Provides a reference to the front element, or `None` if the deque is
+    /// empty.
+    fn front(&self) -> Option<&Self::Item>;
+
+    /// Provides a mutable reference to the front element, or `None` if the
+    /// deque is empty.
+    fn front_mut(&mut self) -> Option<&mut Self::Item>;
+
+    /// Provides a reference to the back element, or `None` if the deque is
+    /// empty.
+    fn back(&self) -> Option<&Self::Item>;
+
+    /// Provides a mutable reference to the back element, or `None` if the
+    /// deque is empty.
+    fn back_mut(&mut self) -> Option<&mut Self::Item>;
+
+    /// Retrieves an element in the deque by index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    fn get(&self, index: usize) -> Option<&Self::Item>;
+
+    /// Retrieves an element in the deque mutably by index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    fn get_mut(&mut self, index: usize) -> Option<&mut Self::Item>;
+
+    /// Add an element to the front of the deque.
+    fn push_front(&mut self, element: Self::Item) -> Result<(), CapacityError<Self::Item>>;
+
+    /// Add an element to the back of the deque.
+    fn push_back(&mut self, element: Self::Item) -> Result<(), CapacityError<Self::Item>>;
+
+    /// Removes the first element and returns it, or `None` if the deque is
+    /// empty.
+    fn pop_front(&mut self) -> Option<Self::Item>;
+
+    /// Removes the last element and returns it, or `None` if the deque is
+    /// empty.
+    fn pop_back(&mut self) -> Option<Self::Item>;
+
+    /// Clears the deque, removing all values.
+    fn clear(&mut self);
+}
+
+macro_rules! forward_deque_like {
+    () => {
+        #[inline]
+        fn len(&self) -> usize {
+            self.len()
+        }
+
+        #[inline]
+        fn front(&self) -> Option<&Self::Item> {
+            self.front()
+        }
+
+        #[inline]
+        fn front_mut(&mut self) -> Option<&mut Self::Item> {
+            self.front_mut()
+        }
+
+        #[inline]
+        fn back(&self) -> Option<&Self::Item> {
+            self.back()
+        }
+
+        #[inline]
+        fn back_mut(&mut self) -> Option<&mut Self::Item> {
+            self.back_mut()
+        }
+
+        #[inline]
+        fn get(&self, index: usize) -> Option<&Self::Item> {
+            self.get(index)
+        }
+
+        #[inline]
+        fn get_mut(&mut self, index: usize) -> Option<&mut Self::Item> {
+            self.get_mut(index)
+        }
+
+        #[inline]
+        fn pop_front(&mut self) -> Option<Self::Item> {
+            self.pop_front()
+        }
+
+        #[inline]
+        fn pop_back(&mut self) -> Option<Self::Item> {
+            self.pop_back()
+        }
+
+        #[inline]
+        fn clear(&mut self) {
+            self.clear()
+        }
+    };
+}
+
+impl<A: Array, H: Hooks<A::Item>> DequeLike for ArrayDeque<A, Saturating<H>> {
+    type Item = A::Item;
+
+    forward_deque_like!();
+
+    #[inline]
+    fn push_front(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.push_front(element)
+    }
+
+    #[inline]
+    fn push_back(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.push_back(element)
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> DequeLike for ArrayDeque<A, Wrapping<H>> {
+    type Item = A::Item;
+
+    forward_deque_like!();
+
+    #[inline]
+    fn push_front(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.push_front(element);
+        Ok(())
+    }
+
+    #[inline]
+    fn push_back(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.push_back(element);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> DequeLike for VecDeque<T> {
+    type Item = T;
+
+    forward_deque_like!();
+
+    #[inline]
+    fn push_front(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        self.push_front(element);
+        Ok(())
+    }
+
+    #[inline]
+    fn push_back(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        self.push_back(element);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: Array> DequeLike for SmallDeque<A> {
+    type Item = A::Item;
+
+    forward_deque_like!();
+
+    #[inline]
+    fn push_front(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.push_front(element);
+        Ok(())
+    }
+
+    #[inline]
+    fn push_back(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.push_back(element);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DequeLike;
+    use {ArrayDeque, Saturating, Wrapping};
+
+    fn round_trip<D: DequeLike<Item = usize>>(deque: &mut D) -> Vec<usize> {
+        for i in 0..4 {
+            let _ = deque.push_back(i);
+        }
+        let _ = deque.push_front(9);
+        if let Some(x) = deque.front_mut() {
+            *x += 1;
+        }
+        let mut popped = vec![];
+        while let Some(x) = deque.pop_front() {
+            popped.push(x);
+        }
+        popped
+    }
+
+    #[test]
+    fn test_deque_like() {
+        let mut saturating: ArrayDeque<[_; 3], Saturating> = ArrayDeque::new();
+        assert_eq!(round_trip(&mut saturating), vec![1, 1, 2]);
+
+        let mut wrapping: ArrayDeque<[_; 3], Wrapping> = ArrayDeque::new();
+        assert_eq!(round_trip(&mut wrapping), vec![10, 1, 2]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_deque_like_vecdeque() {
+        let mut growable = ::std::collections::VecDeque::new();
+        assert_eq!(round_trip(&mut growable), vec![10, 0, 1, 2, 3]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_deque_like_small() {
+        let mut small: ::SmallDeque<[_; 3]> = ::SmallDeque::new();
+        assert_eq!(round_trip(&mut small), vec![10, 0, 1, 2, 3]);
+    }
+}
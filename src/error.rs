@@ -1,25 +1,88 @@
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", has_core_error))]
 use std::error::Error;
 use std::fmt;
+#[cfg(feature = "std")]
+use std::io;
 
 /// Error value indicating insufficient capacity
 ///
-/// This error only occur to `ArrayDeque<_, Saturating>`.
+/// This error is returned by the pushes of `ArrayDeque<_, Saturating>`, and
+/// by the `try_` pushes of any `ArrayDeque`.
+///
+/// It implements `Error` with the `std` feature, or without it on Rust 1.81+,
+/// where the trait is available in `core`.
 #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
 pub struct CapacityError<T = ()> {
     /// The element that caused the error.
     pub element: T,
 }
 
-const CAPERROR: &'static str = "insufficient capacity";
+/// The kind of a `CapacityError`, for matching on errors without caring
+/// about the element they carry.
+///
+/// More kinds may be added in the future.
+#[cfg_attr(has_non_exhaustive, non_exhaustive)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ErrorKind {
+    /// There was not enough room left in the deque.
+    InsufficientCapacity,
+}
+
+const CAPERROR: &str = "insufficient capacity";
 
-#[cfg(feature = "std")]
+impl<T> CapacityError<T> {
+    /// Returns the kind of this error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, ErrorKind};
+    ///
+    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    ///
+    /// let err = buf.push_back(2).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InsufficientCapacity);
+    /// ```
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::InsufficientCapacity
+    }
+}
+
+#[cfg(any(feature = "std", has_core_error))]
 impl<T> Error for CapacityError<T> {
     fn description(&self) -> &str {
         CAPERROR
     }
 }
 
+/// Converts into an `io::Error` of kind `WriteZero`, dropping the element.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use arraydeque::ArrayDeque;
+///
+/// fn push(buf: &mut ArrayDeque<[u8; 1]>, byte: u8) -> io::Result<()> {
+///     buf.push_back(byte)?;
+///     Ok(())
+/// }
+///
+/// let mut buf = ArrayDeque::new();
+///
+/// assert!(push(&mut buf, 1).is_ok());
+/// assert_eq!(push(&mut buf, 2).unwrap_err().kind(), io::ErrorKind::WriteZero);
+/// ```
+#[cfg(feature = "std")]
+impl<T> From<CapacityError<T>> for io::Error {
+    fn from(_: CapacityError<T>) -> io::Error {
+        io::Error::new(io::ErrorKind::WriteZero, CAPERROR)
+    }
+}
+
 impl<T> fmt::Display for CapacityError<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", CAPERROR)
@@ -28,6 +91,41 @@ impl<T> fmt::Display for CapacityError<T> {
 
 impl<T> fmt::Debug for CapacityError<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}: {}", "CapacityError", CAPERROR)
+        write!(f, "CapacityError: {}", CAPERROR)
+    }
+}
+
+/// Error value indicating that an iterator did not yield exactly as many
+/// elements as the capacity
+///
+/// This error is returned by `ArrayDeque::from_iter_exact`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LengthError<T> {
+    /// The iterator yielded more elements, starting with this one, the first
+    /// that did not fit.
+    TooMany(T),
+    /// The iterator yielded fewer elements.
+    TooFew {
+        /// The number of elements yielded.
+        len: usize,
+    },
+}
+
+#[cfg(any(feature = "std", has_core_error))]
+impl<T: fmt::Debug> Error for LengthError<T> {
+    fn description(&self) -> &str {
+        match *self {
+            LengthError::TooMany(_) => "too many elements",
+            LengthError::TooFew { .. } => "too few elements",
+        }
+    }
+}
+
+impl<T> fmt::Display for LengthError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LengthError::TooMany(_) => write!(f, "too many elements"),
+            LengthError::TooFew { len } => write!(f, "too few elements, only {}", len),
+        }
     }
 }
@@ -0,0 +1,275 @@
+//! A C ABI ring buffer over caller-provided storage.
+//!
+//! `arraydeque_raw_t` keeps the same `tail` and `len` bookkeeping as
+//! `ArrayDeque`, and moves elements with the same index arithmetic, but its
+//! element size and capacity are chosen at run time. Elements are copied in
+//! and out as raw bytes, so C and Rust code can share one ring.
+//!
+//! The matching declarations are in `include/arraydeque.h`:
+//!
+//! ```c
+//! uint32_t storage[8];
+//! arraydeque_raw_t ring;
+//! arraydeque_raw_init(&ring, storage, sizeof(uint32_t), 8);
+//!
+//! uint32_t sample = 42;
+//! arraydeque_raw_push_back(&ring, &sample);
+//! ```
+//!
+//! From Rust, the same functions are used through raw pointers:
+//!
+//! ```
+//! use arraydeque::ffi::*;
+//! use std::mem;
+//!
+//! let mut storage = [0u32; 4];
+//! let mut ring = unsafe { mem::zeroed() };
+//! unsafe {
+//!     arraydeque_raw_init(&mut ring, storage.as_mut_ptr() as *mut u8, 4, storage.len());
+//!     assert!(arraydeque_raw_push_back(&mut ring, &7u32 as *const u32 as *const u8));
+//!     assert_eq!(arraydeque_raw_len(&ring), 1);
+//!
+//!     let mut out = 0u32;
+//!     assert!(arraydeque_raw_pop_front(&mut ring, &mut out as *mut u32 as *mut u8));
+//!     assert_eq!(out, 7);
+//! }
+//! ```
+
+#![allow(non_camel_case_types)]
+
+use std::ptr;
+
+use {wrap_add, wrap_sub};
+
+/// A ring buffer of `capacity` elements of `elem_size` bytes each, stored in
+/// a buffer owned by the caller.
+///
+/// The fields are public for the sake of C code that needs to allocate the
+/// header, but should only be modified by the functions of this module.
+#[repr(C)]
+#[derive(Debug)]
+pub struct arraydeque_raw_t {
+    /// The storage, at least `elem_size * capacity` bytes long.
+    pub buf: *mut u8,
+    /// The size of an element, in bytes.
+    pub elem_size: usize,
+    /// The number of elements the storage holds.
+    pub capacity: usize,
+    /// The slot of the front element.
+    pub tail: usize,
+    /// The number of elements in the ring.
+    pub len: usize,
+}
+
+impl arraydeque_raw_t {
+    #[inline]
+    unsafe fn slot(&self, index: usize) -> *mut u8 {
+        self.buf.add(index * self.elem_size)
+    }
+}
+
+/// Initializes an empty ring over `buf`.
+///
+/// # Safety
+///
+/// `raw` must be valid for writes, and `buf` must be valid for reads and
+/// writes of `elem_size * capacity` bytes for as long as the ring is used.
+#[no_mangle]
+pub unsafe extern "C" fn arraydeque_raw_init(
+    raw: *mut arraydeque_raw_t,
+    buf: *mut u8,
+    elem_size: usize,
+    capacity: usize,
+) {
+    ptr::write(
+        raw,
+        arraydeque_raw_t {
+            buf,
+            elem_size,
+            capacity,
+            tail: 0,
+            len: 0,
+        },
+    );
+}
+
+/// Returns the number of elements in the ring.
+///
+/// # Safety
+///
+/// `raw` must point to a ring initialized by `arraydeque_raw_init`.
+#[no_mangle]
+pub unsafe extern "C" fn arraydeque_raw_len(raw: *const arraydeque_raw_t) -> usize {
+    (*raw).len
+}
+
+/// Returns the number of elements the ring can hold.
+///
+/// # Safety
+///
+/// `raw` must point to a ring initialized by `arraydeque_raw_init`.
+#[no_mangle]
+pub unsafe extern "C" fn arraydeque_raw_capacity(raw: *const arraydeque_raw_t) -> usize {
+    (*raw).capacity
+}
+
+/// Removes every element.
+///
+/// # Safety
+///
+/// `raw` must point to a ring initialized by `arraydeque_raw_init`.
+#[no_mangle]
+pub unsafe extern "C" fn arraydeque_raw_clear(raw: *mut arraydeque_raw_t) {
+    (*raw).tail = 0;
+    (*raw).len = 0;
+}
+
+/// Copies `elem_size` bytes from `elem` to the back of the ring.
+///
+/// Returns false, leaving the ring unchanged, if it is full.
+///
+/// # Safety
+///
+/// `raw` must point to a ring initialized by `arraydeque_raw_init`, and
+/// `elem` must be valid for reads of `elem_size` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn arraydeque_raw_push_back(
+    raw: *mut arraydeque_raw_t,
+    elem: *const u8,
+) -> bool {
+    let raw = &mut *raw;
+    if raw.len == raw.capacity {
+        return false;
+    }
+    let head = wrap_add(raw.tail, raw.len, raw.capacity);
+    ptr::copy_nonoverlapping(elem, raw.slot(head), raw.elem_size);
+    raw.len += 1;
+    true
+}
+
+/// Copies `elem_size` bytes from `elem` to the front of the ring.
+///
+/// Returns false, leaving the ring unchanged, if it is full.
+///
+/// # Safety
+///
+/// `raw` must point to a ring initialized by `arraydeque_raw_init`, and
+/// `elem` must be valid for reads of `elem_size` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn arraydeque_raw_push_front(
+    raw: *mut arraydeque_raw_t,
+    elem: *const u8,
+) -> bool {
+    let raw = &mut *raw;
+    if raw.len == raw.capacity {
+        return false;
+    }
+    raw.tail = wrap_sub(raw.tail, 1, raw.capacity);
+    ptr::copy_nonoverlapping(elem, raw.slot(raw.tail), raw.elem_size);
+    raw.len += 1;
+    true
+}
+
+/// Moves the front element into `out`.
+///
+/// Returns false, leaving `out` untouched, if the ring is empty.
+///
+/// # Safety
+///
+/// `raw` must point to a ring initialized by `arraydeque_raw_init`, and
+/// `out` must be valid for writes of `elem_size` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn arraydeque_raw_pop_front(
+    raw: *mut arraydeque_raw_t,
+    out: *mut u8,
+) -> bool {
+    let raw = &mut *raw;
+    if raw.len == 0 {
+        return false;
+    }
+    ptr::copy_nonoverlapping(raw.slot(raw.tail), out, raw.elem_size);
+    raw.tail = wrap_add(raw.tail, 1, raw.capacity);
+    raw.len -= 1;
+    true
+}
+
+/// Moves the back element into `out`.
+///
+/// Returns false, leaving `out` untouched, if the ring is empty.
+///
+/// # Safety
+///
+/// `raw` must point to a ring initialized by `arraydeque_raw_init`, and
+/// `out` must be valid for writes of `elem_size` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn arraydeque_raw_pop_back(raw: *mut arraydeque_raw_t, out: *mut u8) -> bool {
+    let raw = &mut *raw;
+    if raw.len == 0 {
+        return false;
+    }
+    raw.len -= 1;
+    let head = wrap_add(raw.tail, raw.len, raw.capacity);
+    ptr::copy_nonoverlapping(raw.slot(head), out, raw.elem_size);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+    use ArrayDeque;
+
+    #[test]
+    fn test_matches_deque() {
+        const CAP: usize = 5;
+        let mut storage = [0u16; CAP];
+        let mut raw: arraydeque_raw_t = unsafe { mem::zeroed() };
+        let mut deque: ArrayDeque<[u16; CAP]> = ArrayDeque::new();
+        unsafe { arraydeque_raw_init(&mut raw, storage.as_mut_ptr() as *mut u8, 2, CAP) };
+
+        for step in 0..64u16 {
+            let mut out = 0u16;
+            let pushed = &step as *const u16 as *const u8;
+            let popped = &mut out as *mut u16 as *mut u8;
+            let (raw_ok, expected) = unsafe {
+                match step % 7 {
+                    0 | 1 => (
+                        arraydeque_raw_push_back(&mut raw, pushed),
+                        deque.push_back(step).ok().map(|_| step),
+                    ),
+                    2 | 3 => (
+                        arraydeque_raw_push_front(&mut raw, pushed),
+                        deque.push_front(step).ok().map(|_| step),
+                    ),
+                    4 => (
+                        arraydeque_raw_pop_front(&mut raw, popped),
+                        deque.pop_front(),
+                    ),
+                    _ => (arraydeque_raw_pop_back(&mut raw, popped), deque.pop_back()),
+                }
+            };
+            assert_eq!(raw_ok, expected.is_some());
+            if step % 7 >= 4 {
+                assert_eq!(Some(out), expected);
+            }
+            assert_eq!(unsafe { arraydeque_raw_len(&raw) }, deque.len());
+            assert_eq!(raw.tail, deque.tail());
+        }
+
+        unsafe { arraydeque_raw_clear(&mut raw) };
+        assert_eq!(unsafe { arraydeque_raw_len(&raw) }, 0);
+        assert_eq!(unsafe { arraydeque_raw_capacity(&raw) }, CAP);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut raw: arraydeque_raw_t = unsafe { mem::zeroed() };
+        let mut elem = [1u8; 4];
+        unsafe {
+            arraydeque_raw_init(&mut raw, elem.as_mut_ptr(), 4, 0);
+            assert!(!arraydeque_raw_push_back(&mut raw, elem.as_ptr()));
+            assert!(!arraydeque_raw_push_front(&mut raw, elem.as_ptr()));
+            assert!(!arraydeque_raw_pop_back(&mut raw, elem.as_mut_ptr()));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! A typestate writer whose remaining capacity is part of its type.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use array::{Array, ConstCapacity};
+use behavior::Behavior;
+use ArrayDeque;
+
+/// The largest `REMAINING` for which `FixedWriter::push` is implemented.
+pub const FIXED_WRITER_MAX: usize = 64;
+
+/// A writer into an `ArrayDeque` that can push exactly `REMAINING` more
+/// elements, checked at compile time.
+///
+/// Every `push` consumes the writer and returns one with a `REMAINING` one
+/// smaller, and `FixedWriter<_, _, 0>` has no `push` method, so a frame of
+/// fixed format cannot overflow the deque by construction:
+///
+/// ```compile_fail
+/// use arraydeque::ArrayDeque;
+///
+/// let mut frame: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+/// frame.fixed_writer::<2>().push(0x7e).push(0x01).push(0x02);
+/// ```
+///
+/// Nor can the writer promise more room than the deque has:
+///
+/// ```compile_fail
+/// use arraydeque::ArrayDeque;
+///
+/// let mut frame: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+/// frame.fixed_writer::<5>();
+/// ```
+///
+/// `push` is implemented while `REMAINING` is at most `FIXED_WRITER_MAX`.
+///
+/// This `struct` is created by the `fixed_writer` method on `ArrayDeque`,
+/// which clears the deque first.
+///
+/// Requires Rust 1.63+.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::ArrayDeque;
+///
+/// let mut frame: ArrayDeque<[u8; 8]> = ArrayDeque::new();
+///
+/// let writer = frame.fixed_writer::<4>().push(0x7e).push(0x01);
+/// assert_eq!(writer.remaining(), 2);
+/// writer.push(0x02).push(0x7e).finish();
+///
+/// assert_eq!(frame, vec![0x7e, 0x01, 0x02, 0x7e].into());
+/// ```
+#[must_use = "a writer does nothing unless pushed to"]
+pub struct FixedWriter<'a, A: 'a + Array, B: 'a + Behavior, const REMAINING: usize> {
+    deque: &'a mut ArrayDeque<A, B>,
+}
+
+/// Fails to compile, when used, if `R` is larger than the capacity of `A`.
+struct Fits<A, const R: usize>(PhantomData<A>);
+
+impl<A: ConstCapacity, const R: usize> Fits<A, R> {
+    const OK: () = assert!(R <= A::CAPACITY, "the writer is larger than the deque");
+}
+
+impl<'a, A: Array, B: Behavior, const REMAINING: usize> FixedWriter<'a, A, B, REMAINING> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, B>) -> Self
+    where
+        A: ConstCapacity,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = Fits::<A, REMAINING>::OK;
+        deque.clear();
+        FixedWriter { deque }
+    }
+
+    /// Returns the number of elements that can still be pushed, that is
+    /// `REMAINING`.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        REMAINING
+    }
+
+    /// Returns the deque, with the elements pushed so far.
+    #[inline]
+    pub fn finish(self) -> &'a mut ArrayDeque<A, B> {
+        self.deque
+    }
+}
+
+macro_rules! impl_push {
+    ($($remaining:expr => $next:expr,)*) => {
+        $(
+            impl<'a, A: Array, B: Behavior> FixedWriter<'a, A, B, $remaining> {
+                /// Appends an element, which always fits.
+                #[inline]
+                pub fn push(self, element: A::Item) -> FixedWriter<'a, A, B, $next> {
+                    unsafe { self.deque.push_back_unchecked(element) }
+                    FixedWriter { deque: self.deque }
+                }
+            }
+        )*
+    }
+}
+
+impl_push!(
+    1 => 0, 2 => 1, 3 => 2, 4 => 3, 5 => 4, 6 => 5, 7 => 6, 8 => 7,
+    9 => 8, 10 => 9, 11 => 10, 12 => 11, 13 => 12, 14 => 13, 15 => 14, 16 => 15,
+    17 => 16, 18 => 17, 19 => 18, 20 => 19, 21 => 20, 22 => 21, 23 => 22, 24 => 23,
+    25 => 24, 26 => 25, 27 => 26, 28 => 27, 29 => 28, 30 => 29, 31 => 30, 32 => 31,
+    33 => 32, 34 => 33, 35 => 34, 36 => 35, 37 => 36, 38 => 37, 39 => 38, 40 => 39,
+    41 => 40, 42 => 41, 43 => 42, 44 => 43, 45 => 44, 46 => 45, 47 => 46, 48 => 47,
+    49 => 48, 50 => 49, 51 => 50, 52 => 51, 53 => 52, 54 => 53, 55 => 54, 56 => 55,
+    57 => 56, 58 => 57, 59 => 58, 60 => 59, 61 => 60, 62 => 61, 63 => 62, 64 => 63,
+);
+
+impl<'a, A: Array, B: Behavior, const REMAINING: usize> fmt::Debug
+    for FixedWriter<'a, A, B, REMAINING>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FixedWriter")
+            .field("written", &self.deque)
+            .field("remaining", &REMAINING)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ArrayDeque, Wrapping, FIXED_WRITER_MAX};
+
+    #[test]
+    fn test_fixed_writer() {
+        let mut tester: ArrayDeque<[u8; 4], Wrapping> = ArrayDeque::new();
+        tester.extend_back(10..14);
+        unsafe { tester.set_tail(3) }
+
+        let writer = tester.fixed_writer::<4>();
+        assert_eq!(writer.remaining(), 4);
+        let writer = writer.push(1).push(2).push(3);
+        assert_eq!(
+            format!("{:?}", writer),
+            "FixedWriter { written: [1, 2, 3], remaining: 1 }"
+        );
+        assert_eq!(writer.push(4).finish(), &vec![1, 2, 3, 4].into());
+
+        let mut empty: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+        assert!(empty.fixed_writer::<0>().finish().is_empty());
+    }
+
+    #[test]
+    fn test_fixed_writer_max() {
+        let mut tester: ArrayDeque<[usize; FIXED_WRITER_MAX]> = ArrayDeque::new();
+        let writer = tester.fixed_writer::<FIXED_WRITER_MAX>().push(0);
+        assert_eq!(writer.remaining(), FIXED_WRITER_MAX - 1);
+        assert_eq!(writer.finish().len(), 1);
+    }
+}
@@ -0,0 +1,249 @@
+//! A wrapping deque that folds kicked out elements into an accumulator.
+
+use std::fmt;
+
+use array::Array;
+use behavior::{Hooks, NoHooks, Wrapping};
+use {ArrayDeque, Iter};
+
+/// Folds elements kicked out of a `FoldingDeque` into an accumulator.
+///
+/// Like `Hooks`, the fold is picked statically through a type parameter,
+/// but it owns the kicked out element and the state it updates lives in the
+/// deque.
+pub trait FoldEvicted<T> {
+    /// The state kicked out elements are folded into.
+    type Acc;
+
+    /// Called with every element kicked out to spare room.
+    fn fold_evicted(acc: &mut Self::Acc, element: T);
+}
+
+/// A wrapping `ArrayDeque` that folds every element it kicks out into an
+/// accumulator, so a lossy window can still report exact aggregates over
+/// everything ever pushed.
+///
+/// Only elements kicked out by a push are folded. Elements that are popped
+/// or cleared are left to the caller.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::{FoldEvicted, FoldingDeque};
+///
+/// struct DroppedTotal;
+///
+/// impl FoldEvicted<u32> for DroppedTotal {
+///     type Acc = u64;
+///
+///     fn fold_evicted(total: &mut u64, sample: u32) {
+///         *total += sample as u64;
+///     }
+/// }
+///
+/// let mut window: FoldingDeque<[u32; 3], DroppedTotal> = FoldingDeque::new();
+///
+/// window.extend_back(1..=5);
+/// assert!(window.iter().eq(&[3, 4, 5]));
+/// assert_eq!(*window.accumulator(), 1 + 2);
+///
+/// // the exact total of every sample pushed
+/// let kept: u64 = window.iter().map(|&x| x as u64).sum();
+/// assert_eq!(kept + window.accumulator(), 15);
+/// ```
+pub struct FoldingDeque<A: Array, F: FoldEvicted<A::Item>, H: Hooks<A::Item> = NoHooks> {
+    deque: ArrayDeque<A, Wrapping<H>>,
+    acc: F::Acc,
+}
+
+impl<A: Array, F: FoldEvicted<A::Item>, H: Hooks<A::Item>> FoldingDeque<A, F, H> {
+    /// Creates an empty `FoldingDeque` with a default accumulator.
+    #[inline]
+    pub fn new() -> Self
+    where
+        F::Acc: Default,
+    {
+        FoldingDeque::with_accumulator(F::Acc::default())
+    }
+
+    /// Creates an empty `FoldingDeque` that starts folding into `acc`.
+    #[inline]
+    pub fn with_accumulator(acc: F::Acc) -> Self {
+        FoldingDeque {
+            deque: ArrayDeque::new(),
+            acc,
+        }
+    }
+
+    /// Returns the underlying deque.
+    #[inline]
+    pub fn as_deque(&self) -> &ArrayDeque<A, Wrapping<H>> {
+        &self.deque
+    }
+
+    /// Returns the accumulator.
+    #[inline]
+    pub fn accumulator(&self) -> &F::Acc {
+        &self.acc
+    }
+
+    /// Returns the accumulator mutably, for example to reset it after
+    /// reporting.
+    #[inline]
+    pub fn accumulator_mut(&mut self) -> &mut F::Acc {
+        &mut self.acc
+    }
+
+    /// Returns the underlying deque and the accumulator.
+    #[inline]
+    pub fn into_parts(self) -> (ArrayDeque<A, Wrapping<H>>, F::Acc) {
+        (self.deque, self.acc)
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Returns a front-to-back iterator.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, A::Item> {
+        self.deque.iter()
+    }
+
+    /// Appends an element, folding the front element into the accumulator
+    /// if the deque is full.
+    #[inline]
+    pub fn push_back(&mut self, element: A::Item) {
+        if let Some(existing) = self.deque.push_back(element) {
+            F::fold_evicted(&mut self.acc, existing);
+        }
+    }
+
+    /// Prepends an element, folding the back element into the accumulator
+    /// if the deque is full.
+    #[inline]
+    pub fn push_front(&mut self, element: A::Item) {
+        if let Some(existing) = self.deque.push_front(element) {
+            F::fold_evicted(&mut self.acc, existing);
+        }
+    }
+
+    /// Extends the back of the deque with the contents of an iterator,
+    /// folding every element kicked out into the accumulator.
+    pub fn extend_back<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+
+    /// Removes the front element and returns it, or `None` if the deque is
+    /// empty. The element is not folded.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<A::Item> {
+        self.deque.pop_front()
+    }
+
+    /// Removes the back element and returns it, or `None` if the deque is
+    /// empty. The element is not folded.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<A::Item> {
+        self.deque.pop_back()
+    }
+
+    /// Removes every element, without folding them. The accumulator is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.deque.clear();
+    }
+}
+
+impl<A: Array, F: FoldEvicted<A::Item>, H: Hooks<A::Item>> Default for FoldingDeque<A, F, H>
+where
+    F::Acc: Default,
+{
+    #[inline]
+    fn default() -> Self {
+        FoldingDeque::new()
+    }
+}
+
+impl<A: Array, F: FoldEvicted<A::Item>, H: Hooks<A::Item>> fmt::Debug for FoldingDeque<A, F, H>
+where
+    A::Item: fmt::Debug,
+    F::Acc: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FoldingDeque")
+            .field("deque", &self.deque)
+            .field("acc", &self.acc)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FoldEvicted, FoldingDeque};
+
+    struct MinMax;
+
+    impl FoldEvicted<i32> for MinMax {
+        type Acc = Option<(i32, i32)>;
+
+        fn fold_evicted(acc: &mut Option<(i32, i32)>, element: i32) {
+            *acc = Some(match *acc {
+                Some((min, max)) => (min.min(element), max.max(element)),
+                None => (element, element),
+            });
+        }
+    }
+
+    #[test]
+    fn test_fold_evicted() {
+        let mut tester: FoldingDeque<[i32; 2], MinMax> = FoldingDeque::new();
+        tester.push_back(5);
+        tester.push_back(-3);
+        assert_eq!(*tester.accumulator(), None);
+
+        tester.push_back(7);
+        assert_eq!(*tester.accumulator(), Some((5, 5)));
+        // pushing to the front kicks out the back
+        tester.push_front(1);
+        assert_eq!(*tester.accumulator(), Some((5, 7)));
+        assert!(tester.iter().eq(&[1, -3]));
+
+        // pops and clears are not folded
+        assert_eq!(tester.pop_back(), Some(-3));
+        tester.extend_back(vec![-9, 4]);
+        assert_eq!(*tester.accumulator(), Some((1, 7)));
+        tester.clear();
+        assert_eq!(
+            format!("{:?}", tester),
+            "FoldingDeque { deque: [], acc: Some((1, 7)) }"
+        );
+
+        *tester.accumulator_mut() = None;
+        tester.extend_back(0..3);
+        let (deque, acc) = tester.into_parts();
+        assert_eq!(deque, vec![1, 2].into());
+        assert_eq!(acc, Some((0, 0)));
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut tester: FoldingDeque<[i32; 0], MinMax> = FoldingDeque::with_accumulator(None);
+        tester.extend_back(vec![2, 1, 3]);
+        assert!(tester.is_empty());
+        assert_eq!(*tester.accumulator(), Some((1, 3)));
+    }
+}
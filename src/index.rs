@@ -0,0 +1,51 @@
+//! Range lookups over the pair of slices of a deque, for
+//! `ArrayDeque::get_range` and `ArrayDeque::get_range_mut`.
+
+use range::RangeArgument;
+
+/// Returns the pair of slices which contain, in order, the elements of
+/// `slices` in `range`, or `None` if the range is out of bounds.
+#[inline]
+pub(crate) fn get_range<'a, T, R>(
+    (a, b): (&'a [T], &'a [T]),
+    range: R,
+) -> Option<(&'a [T], &'a [T])>
+where
+    R: RangeArgument<usize>,
+{
+    let start = range.start().unwrap_or(0);
+    let end = range.end().unwrap_or(a.len() + b.len());
+    if start > end || end > a.len() + b.len() {
+        None
+    } else if end <= a.len() {
+        Some((&a[start..end], &b[..0]))
+    } else if start >= a.len() {
+        Some((&b[start - a.len()..end - a.len()], &a[..0]))
+    } else {
+        Some((&a[start..], &b[..end - a.len()]))
+    }
+}
+
+/// Returns the pair of mutable slices which contain, in order, the elements
+/// of `slices` in `range`, or `None` if the range is out of bounds.
+#[inline]
+pub(crate) fn get_range_mut<'a, T, R>(
+    (a, b): (&'a mut [T], &'a mut [T]),
+    range: R,
+) -> Option<(&'a mut [T], &'a mut [T])>
+where
+    R: RangeArgument<usize>,
+{
+    let first_len = a.len();
+    let start = range.start().unwrap_or(0);
+    let end = range.end().unwrap_or(first_len + b.len());
+    if start > end || end > first_len + b.len() {
+        None
+    } else if end <= first_len {
+        Some((&mut a[start..end], &mut b[..0]))
+    } else if start >= first_len {
+        Some((&mut b[start - first_len..end - first_len], &mut a[..0]))
+    } else {
+        Some((&mut a[start..], &mut b[..end - first_len]))
+    }
+}
@@ -0,0 +1,93 @@
+//! Reads at fractional positions between neighboring elements.
+
+use array::Array;
+use behavior::Behavior;
+use ArrayDeque;
+
+/// Types that can be linearly interpolated, for `ArrayDeque::get_interpolated`.
+pub trait Lerp: Copy {
+    /// Returns the value a fraction `t` of the way from `self` to `other`.
+    fn lerp(self, other: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    #[inline]
+    fn lerp(self, other: f32, t: f32) -> f32 {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for f64 {
+    #[inline]
+    fn lerp(self, other: f64, t: f32) -> f64 {
+        self + (other - self) * f64::from(t)
+    }
+}
+
+impl<A: Array, B: Behavior> ArrayDeque<A, B> {
+    /// Reads the value at the fractional position `pos`, interpolating
+    /// linearly between the elements on either side of it.
+    ///
+    /// Returns `None` if `pos` is negative, NaN, or past the back element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[f32; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1.0);
+    /// buf.push_back(2.0);
+    /// buf.push_back(4.0);
+    ///
+    /// assert_eq!(buf.get_interpolated(0.5), Some(1.5));
+    /// assert_eq!(buf.get_interpolated(1.25), Some(2.5));
+    /// assert_eq!(buf.get_interpolated(2.0), Some(4.0));
+    /// assert_eq!(buf.get_interpolated(2.5), None);
+    /// ```
+    #[inline]
+    pub fn get_interpolated(&self, pos: f32) -> Option<A::Item>
+    where
+        A::Item: Lerp,
+    {
+        self.get_interpolated_with(pos, |&a, &b, t| a.lerp(b, t))
+    }
+
+    /// Reads the value at the fractional position `pos`, combining the
+    /// elements on either side of it with `lerp`.
+    ///
+    /// `lerp` is given the elements before and after `pos` and the fraction
+    /// of the way between them, in `[0, 1)`. At the back element, both
+    /// neighbors are the back element itself.
+    ///
+    /// Returns `None` if `pos` is negative, NaN, or past the back element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[i32; 2]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(10);
+    /// buf.push_back(20);
+    ///
+    /// let nearest = |a: &i32, b: &i32, t: f32| if t < 0.5 { *a } else { *b };
+    ///
+    /// assert_eq!(buf.get_interpolated_with(0.4, nearest), Some(10));
+    /// assert_eq!(buf.get_interpolated_with(0.6, nearest), Some(20));
+    /// ```
+    pub fn get_interpolated_with<F>(&self, pos: f32, lerp: F) -> Option<A::Item>
+    where
+        F: FnOnce(&A::Item, &A::Item, f32) -> A::Item,
+    {
+        if pos.is_nan() || pos < 0.0 || pos > (self.len() as f32) - 1.0 {
+            return None;
+        }
+        let index = pos as usize;
+        let before = self.get(index)?;
+        let after = self.get(index + 1).unwrap_or(before);
+        Some(lerp(before, after, pos - index as f32))
+    }
+}
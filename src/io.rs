@@ -0,0 +1,136 @@
+//! `Read` and `Write` implementations for byte deques.
+
+use std::io::{self, Read, Write};
+#[cfg(has_io_slice)]
+use std::io::{IoSlice, IoSliceMut};
+
+use array::Array;
+use behavior::Behavior;
+use ArrayDeque;
+
+/// Drains bytes from the front, with at most two slice copies per buffer.
+///
+/// `read_vectored` fills every buffer in turn until the deque runs dry, and
+/// `write_vectored` appends every buffer in turn until the deque is full, so
+/// a wrapped deque is moved in a single call. These need Rust 1.36+.
+impl<A: Array<Item = u8>, B: Behavior> Read for ArrayDeque<A, B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.copy_prefix_from_front(buf);
+        self.discard_front(n);
+        Ok(n)
+    }
+
+    #[cfg(has_io_slice)]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut read = 0;
+        for buf in bufs {
+            if self.is_empty() {
+                break;
+            }
+            read += self.read(buf)?;
+        }
+        Ok(read)
+    }
+}
+
+/// Appends as many bytes as fit, whatever the behavior, with at most two
+/// slice copies. Writing to a full deque returns `Ok(0)`.
+impl<A: Array<Item = u8>, B: Behavior> Write for ArrayDeque<A, B> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.copy_prefix_to_back(buf))
+    }
+
+    #[cfg(has_io_slice)]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            let n = self.copy_prefix_to_back(buf);
+            written += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(written)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read, Write};
+
+    use {ArrayDeque, Wrapping};
+
+    #[test]
+    fn test_pipe() {
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[u8; CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.write(b"hello, world").unwrap(), CAP);
+            assert_eq!(tester.write(b"!").unwrap(), 0);
+
+            let mut buf = [0; 5];
+            assert_eq!(tester.read(&mut buf).unwrap(), 5);
+            assert_eq!(&buf, b"hello");
+            tester.write_all(b" w").unwrap();
+
+            let mut rest = Vec::new();
+            tester.read_to_end(&mut rest).unwrap();
+            assert_eq!(rest, b",  w");
+            assert!(tester.is_empty());
+            assert_eq!(tester.read(&mut buf).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    #[cfg(has_io_slice)]
+    fn test_vectored() {
+        use std::io::{IoSlice, IoSliceMut};
+
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[u8; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            let bufs = [
+                IoSlice::new(b"ab"),
+                IoSlice::new(b"cde"),
+                IoSlice::new(b"fgh"),
+            ];
+            assert_eq!(tester.write_vectored(&bufs).unwrap(), CAP);
+            assert_eq!(tester.write_vectored(&bufs).unwrap(), 0);
+
+            let (mut a, mut b, mut c) = ([0; 4], [0; 1], [0; 4]);
+            {
+                let mut bufs = [
+                    IoSliceMut::new(&mut a),
+                    IoSliceMut::new(&mut b),
+                    IoSliceMut::new(&mut c),
+                ];
+                assert_eq!(tester.read_vectored(&mut bufs).unwrap(), CAP);
+            }
+            assert_eq!((&a, &b, &c[..1]), (b"abcd", b"e", &b"f"[..]));
+            assert!(tester.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_copy() {
+        let mut tester: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+        let mut source: &[u8] = b"abcdef";
+        let err = io::copy(&mut source, &mut tester).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(tester, b"abcd".to_vec().into());
+    }
+}
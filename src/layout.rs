@@ -0,0 +1,74 @@
+//! Memory layout of an `ArrayDeque` configuration.
+
+/// The size and alignment of an `ArrayDeque` type, and how much of it is
+/// taken by the elements.
+///
+/// This `struct` is created by the `layout` method on `ArrayDeque`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DequeLayout {
+    /// The size of the deque in bytes, as returned by `mem::size_of`.
+    pub size: usize,
+    /// The alignment of the deque in bytes, as returned by `mem::align_of`.
+    pub align: usize,
+    /// The number of elements the deque can hold.
+    pub capacity: usize,
+    /// The size of one element slot in bytes.
+    pub element_size: usize,
+    /// The bytes not taken by element slots: the indices and any padding.
+    ///
+    /// There is no per-element bookkeeping, so this does not grow with the
+    /// capacity.
+    pub overhead: usize,
+}
+
+/// Fails to compile if an `ArrayDeque` type takes more than `budget` bytes.
+///
+/// Element padding counts against the budget: every slot takes
+/// `slot_size()` bytes, which rounds the size of the element up to its
+/// alignment. The check runs at compile time, so a change to an element
+/// type that blows the budget is caught before it reaches a device.
+///
+/// Requires Rust 1.63+.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate arraydeque;
+///
+/// use arraydeque::ArrayDeque;
+///
+/// struct Sample {
+///     value: u32,
+///     channel: u8,
+/// }
+///
+/// // 3 bytes of padding per sample, so 64 samples need 512 bytes, not 320
+/// assert_ram_budget!(ArrayDeque<[Sample; 64]>, 520);
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate arraydeque;
+///
+/// use arraydeque::ArrayDeque;
+///
+/// struct Sample {
+///     value: u32,
+///     channel: u8,
+/// }
+///
+/// assert_ram_budget!(ArrayDeque<[Sample; 64]>, 328);
+/// # fn main() {}
+/// ```
+#[cfg(has_const_generics)]
+#[macro_export]
+macro_rules! assert_ram_budget {
+    ($t:ty, $budget:expr) => {
+        const _: () = assert!(
+            <$t>::footprint() <= $budget,
+            concat!("`", stringify!($t), "` exceeds its RAM budget")
+        );
+    };
+}
@@ -1,5 +1,6 @@
 //! A circular buffer with fixed capacity.
-//! Requires Rust 1.20+
+//! Requires Rust 1.28+, APIs using const generics require Rust 1.63+.
+//! Running the test suite requires Rust 1.56+.
 //!
 //! It can be stored directly on the stack if needed.
 //!
@@ -15,12 +16,77 @@
 //! - `std`
 //!   - Optional, enabled by default
 //!   - Conversions between `ArrayDeque` and `Vec`
+//!   - Spilling an `ArrayDeque` into a growable `VecDeque`
+//!   - Iterating a boxed `ArrayDeque` in place
+//!   - Conversion of `CapacityError` into `io::Error`
+//!   - `SmallDeque`, which spills to the heap when it outgrows its capacity
+//!   - `DequeLike` implementation for `VecDeque` and `SmallDeque`
+//!   - `Read` and `Write` implementations for byte deques
+//!   - `ByteRing`, a byte ring buffer implementing `Read`, `BufRead` and `Write`
 //!   - Use libstd
 //! 
 //! - `use_generic_array`
 //!   - Optional
 //!   - Allow to use `GenericArray`
 //!
+//! - `bytes`
+//!   - Optional, requires Rust 1.54+
+//!   - Integer framing helpers such as `pop_u16_le` and `push_u32_be` for byte deques
+//!   - UTF-8 text helpers `push_str` and `pop_front_char` for byte deques
+//!   - `fmt::Write` implementation for saturating and wrapping byte deques
+//!   - `RecordDeque`, which indexes delimiters for constant time record counting
+//!     and popping
+//!
+//! - `extern-c`
+//!   - Optional
+//!   - `arraydeque_raw_*` functions with a C ABI, operating on a ring over
+//!     caller-provided storage, declared in `include/arraydeque.h`
+//!
+//! - `futures`
+//!   - Optional, requires Rust 1.36+
+//!   - `extend_yielding` and `drain_yielding`, futures that move elements in
+//!     bounded chunks and yield to the executor in between
+//!
+//! - `index_trap`
+//!   - Optional
+//!   - Out of bounds indexing of an `ArrayDeque` aborts instead of panicking
+//!     with a formatted message, so no formatting machinery is linked in for
+//!     it
+//!   - Without `std`, there is no abort to call, so it still panics, with a
+//!     fixed message: what happens next is up to the `#[panic_handler]` of the
+//!     application, which usually halts or resets, and it only unwinds if the
+//!     handler does
+//!
+//! - `model`
+//!   - Optional, requires `std`
+//!   - A `Vec` based reference model of `ArrayDeque`, for differential testing
+//!     and fuzzing
+//!
+//! - `serde`
+//!   - Optional
+//!   - `Serialize` and `Deserialize` for `ArrayDeque`, as a sequence of at most
+//!     `capacity()` elements
+//!
+//! - `stats`
+//!   - Optional
+//!   - One-pass `stats` summary for deques of primitive numbers
+//!
+//! - `strict`
+//!   - Optional
+//!   - Pushes report lengths past the `Hooks::SOFT_LIMIT` of the behavior,
+//!     panicking in debug builds by default
+//!
+//! - `trace`
+//!   - Optional
+//!   - The `trace` module, reporting pushes, insertions, pops, evictions,
+//!     rejected pushes and drains of every deque to a subscriber function,
+//!     for diagnosing queue behavior
+//!
+//! - `unstable_placement`
+//!   - Optional, requires Rust 1.51+
+//!   - `new_in_place`, which initializes a deque inside caller-provided storage
+//!   - Not covered by semver guarantees
+//!
 //! # Usage
 //!
 //! First, add the following to your `Cargo.toml`:
@@ -47,45 +113,150 @@
 //!
 //! # Behaviors
 //!
-//! `ArrayDeque` provides two different behaviors, `Saturating` and `Wrapping`,
-//! determining whether to remove existing element automatically when pushing
-//! to a full deque.
+//! `ArrayDeque` provides three different behaviors, `Saturating`, `Wrapping`
+//! and `Decimating`, determining whether and which existing elements to
+//! remove automatically when pushing to a full deque.
 //!
 //! See the [behavior module documentation](behavior/index.html) for more.
 
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(has_union_feature, feature(untagged_unions))]
 #![deny(missing_docs)]
+// `const` thread locals need Rust 1.59, above the MSRV of the test suite
+#![cfg_attr(test, allow(clippy::missing_const_for_thread_local))]
 
 #[cfg(not(any(feature = "std", test)))]
 extern crate core as std;
 #[cfg(feature = "use_generic_array")]
 extern crate generic_array;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_test;
 
 use std::cmp;
 use std::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+#[cfg(has_try_from)]
+use std::convert::TryFrom;
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::iter::FromIterator;
+use std::iter::{Chain, FromIterator, FusedIterator, Rev};
 use std::marker;
+use std::mem;
 use std::ops::Index;
 use std::ops::IndexMut;
+use std::option;
 use std::ptr;
+use std::slice;
+use std::sync::atomic;
 
 use array::Index as ArrayIndex;
 use behavior::Behavior;
 use maybe_uninit::MaybeUninit;
 
+#[macro_use]
 mod array;
+#[cfg(all(has_const_generics, has_atomic_ptr))]
+mod atomic_ring;
 pub mod behavior;
+#[cfg(feature = "std")]
+mod boxed;
+mod builder;
+#[cfg(all(feature = "std", has_const_generics))]
+mod byte_ring;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(has_const_generics)]
+mod chunks;
+mod deque_like;
 mod error;
+#[cfg(feature = "extern-c")]
+pub mod ffi;
+#[cfg(has_const_generics)]
+mod fixed_writer;
+mod folding;
+mod index;
+mod interpolate;
+#[cfg(feature = "std")]
+mod io;
+#[macro_use]
+mod layout;
 mod maybe_uninit;
+#[cfg(any(feature = "model", test))]
+pub mod model;
+mod peek;
 mod range;
-
-pub use array::Array;
-pub use behavior::{Saturating, Wrapping};
-pub use error::CapacityError;
+#[cfg(feature = "bytes")]
+mod records;
+mod region;
+mod retain_drain;
+mod sequenced;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(has_const_generics)]
+mod slotted;
+#[cfg(feature = "std")]
+mod small;
+#[cfg(all(has_const_generics, has_atomic_ptr))]
+pub mod spsc;
+mod stack;
+#[cfg(feature = "stats")]
+mod stats;
+mod step;
+#[cfg(feature = "trace")]
+pub mod trace;
+mod transaction;
+mod view;
+#[cfg(feature = "futures")]
+mod yielding;
+mod zip;
+
+#[cfg(has_const_generics)]
+pub use array::capacity_for;
+#[cfg(has_const_generics)]
+pub use array::ConstArray;
+#[doc(hidden)]
+pub use array::ConstCapacity;
+pub use array::{Array, CapacityStorage};
+#[cfg(all(has_const_generics, has_atomic_ptr))]
+pub use atomic_ring::AtomicRing;
+pub use behavior::{Decimating, Hooks, NoHooks, Saturating, Wrapping};
+#[cfg(feature = "std")]
+pub use boxed::BoxedIntoIter;
+pub use builder::DequeBuilder;
+#[cfg(all(feature = "std", has_const_generics))]
+pub use byte_ring::ByteRing;
+#[cfg(has_const_generics)]
+pub use chunks::{ChunkRefs, Chunks, DrainChunks};
+pub use deque_like::DequeLike;
+pub use error::{CapacityError, ErrorKind, LengthError};
+#[cfg(has_const_generics)]
+pub use fixed_writer::{FixedWriter, FIXED_WRITER_MAX};
+pub use folding::{FoldEvicted, FoldingDeque};
+pub use interpolate::Lerp;
+pub use layout::DequeLayout;
+pub use peek::PeekGuard;
 pub use range::RangeArgument;
+#[cfg(feature = "bytes")]
+pub use records::RecordDeque;
+pub use region::{FilledRegion, SpareFrontRegion, SpareRegion};
+pub use retain_drain::RetainDrain;
+pub use sequenced::SequencedDeque;
+#[cfg(has_const_generics)]
+pub use slotted::{SlotHandle, SlottedDeque};
+#[cfg(feature = "std")]
+pub use small::SmallDeque;
+pub use stack::ArrayStack;
+#[cfg(feature = "stats")]
+pub use stats::{Numeric, Stats};
+pub use step::IterStep;
+pub use transaction::Transaction;
+pub use view::RingView;
+#[cfg(feature = "futures")]
+pub use yielding::{DrainYielding, ExtendYielding};
+pub use zip::Zip;
 
 /// A fixed capacity ring buffer.
 ///
@@ -94,6 +265,10 @@ pub use range::RangeArgument;
 /// The "default" usage of this type as a queue is to use `push_back` to add to
 /// the queue, and `pop_front` to remove from the queue. Iterating over `ArrayDeque` goes front
 /// to back.
+///
+/// Very large deques are best kept in a `Box`. Iterating a boxed deque, by
+/// reference or by value, and `clone_from` between boxed deques work in place
+/// without copying the backing array onto the stack.
 pub struct ArrayDeque<A: Array, B: Behavior = Saturating> {
     xs: MaybeUninit<A>,
     tail: A::Index,
@@ -101,7 +276,15 @@ pub struct ArrayDeque<A: Array, B: Behavior = Saturating> {
     marker: marker::PhantomData<B>,
 }
 
-impl<A: Array> ArrayDeque<A, Saturating> {
+/// An `ArrayDeque` holding up to `N` elements of type `T`, for any `N`.
+///
+/// Requires Rust 1.63+.
+///
+/// See `ConstArray` for more.
+#[cfg(has_const_generics)]
+pub type ConstDeque<T, const N: usize, B = Saturating> = ArrayDeque<ConstArray<T, N>, B>;
+
+impl<A: Array, H: Hooks<A::Item>> ArrayDeque<A, Saturating<H>> {
     /// Add an element to the front of the deque.
     ///
     /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *element* })`
@@ -133,8 +316,14 @@ impl<A: Array> ArrayDeque<A, Saturating> {
             unsafe {
                 self.push_front_unchecked(element);
             }
+            self.check_soft_limit::<H>();
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::PushFront);
             Ok(())
         } else {
+            H::on_full(&element);
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
             Err(CapacityError { element })
         }
     }
@@ -170,12 +359,79 @@ impl<A: Array> ArrayDeque<A, Saturating> {
             unsafe {
                 self.push_back_unchecked(element);
             }
+            self.check_soft_limit::<H>();
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::PushBack);
             Ok(())
         } else {
+            H::on_full(&element);
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
             Err(CapacityError { element })
         }
     }
 
+    /// Add an element to the back of the deque, unless it is equal to the
+    /// current back element.
+    ///
+    /// Return `Ok(true)` if the element was pushed, `Ok(false)` if it was
+    /// skipped as a duplicate, or `Err(CapacityError { *element* })` if the
+    /// deque is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.push_back_unique(1), Ok(true));
+    /// assert_eq!(buf.push_back_unique(1), Ok(false));
+    /// assert_eq!(buf.push_back_unique(2), Ok(true));
+    /// assert_eq!(buf.push_back_unique(1), Ok(true));
+    /// assert_eq!(buf.push_back_unique(1), Ok(false));
+    /// assert_eq!(buf.push_back_unique(3), Err(CapacityError { element: 3 }));
+    /// assert_eq!(buf, vec![1, 2, 1].into());
+    /// ```
+    pub fn push_back_unique(&mut self, element: A::Item) -> Result<bool, CapacityError<A::Item>>
+    where
+        A::Item: PartialEq,
+    {
+        if self.back() == Some(&element) {
+            return Ok(false);
+        }
+        self.push_back(element).map(|()| true)
+    }
+
+    /// Add an element to the back of the deque, unless an equal element is
+    /// already contained anywhere in the deque.
+    ///
+    /// Return `Ok(true)` if the element was pushed, `Ok(false)` if it was
+    /// skipped as a duplicate, or `Err(CapacityError { *element* })` if the
+    /// deque is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.push_back_absent(1), Ok(true));
+    /// assert_eq!(buf.push_back_absent(2), Ok(true));
+    /// assert_eq!(buf.push_back_absent(1), Ok(false));
+    /// assert_eq!(buf, vec![1, 2].into());
+    /// ```
+    pub fn push_back_absent(&mut self, element: A::Item) -> Result<bool, CapacityError<A::Item>>
+    where
+        A::Item: PartialEq,
+    {
+        if self.contains(&element) {
+            return Ok(false);
+        }
+        self.push_back(element).map(|()| true)
+    }
+
     /// Inserts an element at `index` within the `ArrayDeque`. Whichever
     /// end is closer to the insertion point will be moved to make room,
     /// and all the affected elements will be moved to new positions.
@@ -214,12 +470,18 @@ impl<A: Array> ArrayDeque<A, Saturating> {
         assert!(index <= self.len(), "index out of bounds");
 
         if self.is_full() {
+            H::on_full(&element);
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
             return Err(CapacityError { element });
         }
 
         unsafe {
             self.insert_unchecked(index, element);
         }
+        self.check_soft_limit::<H>();
+        #[cfg(feature = "trace")]
+        self.trace(trace::Op::Insert(index));
 
         Ok(())
     }
@@ -299,10 +561,45 @@ impl<A: Array> ArrayDeque<A, Saturating> {
             self.push_back(element);
         }
     }
+
+    /// Starts a batch of pushes that is either kept as a whole or rolled back.
+    ///
+    /// Elements pushed through the returned `Transaction` are only kept if
+    /// `Transaction::commit` is called. Dropping the transaction removes them
+    /// again, so a multi-element message is never left half-written in the
+    /// deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// fn write_message(
+    ///     buf: &mut ArrayDeque<[u8; 4]>,
+    ///     payload: &[u8],
+    /// ) -> Result<(), CapacityError<u8>> {
+    ///     let mut tx = buf.transaction();
+    ///     tx.push_back(payload.len() as u8)?;
+    ///     tx.extend_back(payload.iter().cloned())?;
+    ///     tx.commit();
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    ///
+    /// assert!(write_message(&mut buf, &[7, 8]).is_ok());
+    /// assert!(write_message(&mut buf, &[9]).is_err());
+    ///
+    /// assert_eq!(buf, vec![2, 7, 8].into());
+    /// ```
+    #[inline]
+    pub fn transaction(&mut self) -> Transaction<'_, A, H> {
+        Transaction::new(self)
+    }
 }
 
 #[allow(unused_must_use)]
-impl<A: Array> Extend<A::Item> for ArrayDeque<A, Saturating> {
+impl<A: Array, H: Hooks<A::Item>> Extend<A::Item> for ArrayDeque<A, Saturating<H>> {
     fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = A::Item>,
@@ -311,32 +608,41 @@ impl<A: Array> Extend<A::Item> for ArrayDeque<A, Saturating> {
     }
 }
 
-impl<A: Array> FromIterator<A::Item> for ArrayDeque<A, Saturating> {
+impl<A: Array, H: Hooks<A::Item>> FromIterator<A::Item> for ArrayDeque<A, Saturating<H>> {
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = A::Item>,
     {
-        let mut array: ArrayDeque<_, Saturating> = ArrayDeque::new();
+        let mut array: ArrayDeque<_, Saturating<H>> = ArrayDeque::new();
         array.extend_back(iter);
         array
     }
 }
 
-impl<A: Array> Clone for ArrayDeque<A, Saturating>
+impl<A: Array, H: Hooks<A::Item>> Clone for ArrayDeque<A, Saturating<H>>
 where
     A::Item: Clone,
 {
     fn clone(&self) -> Self {
         self.iter().cloned().collect()
     }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.clear();
+        self.extend(source.iter().cloned());
+    }
 }
 
-impl<A: Array> ArrayDeque<A, Wrapping> {
+impl<A: Array, H: Hooks<A::Item>> ArrayDeque<A, Wrapping<H>> {
     /// Add an element to the front of the deque.
     ///
     /// Return `None` if deque still has capacity, or `Some(existing)`
     /// if the deque is full, where `existing` is the backmost element being kicked out.
     ///
+    /// With `Hooks::PINNED` elements, the element goes right after them
+    /// instead, and is rejected, reported to `Hooks::on_full` and handed back
+    /// if the deque is full of pinned elements.
+    ///
     /// # Examples
     ///
     /// ```
@@ -361,16 +667,42 @@ impl<A: Array> ArrayDeque<A, Wrapping> {
     pub fn push_front(&mut self, element: A::Item) -> Option<A::Item> {
         let existing = if self.is_full() {
             if self.capacity() == 0 {
+                H::on_evict(&element);
+                #[cfg(feature = "trace")]
+                self.trace(trace::Op::Evict);
+                return Some(element);
+            } else if H::PINNED >= self.len() {
+                H::on_full(&element);
+                #[cfg(feature = "trace")]
+                self.trace(trace::Op::Reject);
                 return Some(element);
             } else {
-                self.pop_back()
+                self.pop_back_untraced()
             }
         } else {
             None
         };
+        if let Some(ref existing) = existing {
+            H::on_evict(existing);
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Evict);
+        }
 
-        unsafe {
-            self.push_front_unchecked(element);
+        if H::PINNED == 0 {
+            unsafe {
+                self.push_front_unchecked(element);
+            }
+            self.check_soft_limit::<H>();
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::PushFront);
+        } else {
+            let index = cmp::min(H::PINNED, self.len());
+            unsafe {
+                self.insert_unchecked(index, element);
+            }
+            self.check_soft_limit::<H>();
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Insert(index));
         }
 
         existing
@@ -381,6 +713,9 @@ impl<A: Array> ArrayDeque<A, Wrapping> {
     /// Return `None` if deque still has capacity, or `Some(existing)`
     /// if the deque is full, where `existing` is the frontmost element being kicked out.
     ///
+    /// The first `Hooks::PINNED` elements are never kicked out, see
+    /// `push_back_pinned`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -403,19 +738,72 @@ impl<A: Array> ArrayDeque<A, Wrapping> {
     /// assert_eq!(buf.back(), Some(&4));
     /// ```
     pub fn push_back(&mut self, element: A::Item) -> Option<A::Item> {
+        self.push_back_pinned(H::PINNED, element)
+    }
+
+    /// Appends an element to the back of a buffer, never kicking out the
+    /// first `pinned` elements, nor the first `Hooks::PINNED` ones.
+    ///
+    /// Return `None` if deque still has capacity, or `Some(existing)`
+    /// if the deque is full, where `existing` is the element at index `pinned`
+    /// being kicked out. If every element is pinned, `element` itself is
+    /// rejected, reported to `Hooks::on_full` and handed back instead.
+    ///
+    /// This keeps a header, such as a preamble, at the front of a ring while
+    /// cycling the rest. `Hooks::PINNED` does so for every push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // [h, 1, 2] <-(+)- 3 => [h, 2, 3] -> Some(1)
+    ///
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 3], Wrapping> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.push_back_pinned(1, 3), Some(1));
+    /// assert_eq!(buf.push_back_pinned(1, 4), Some(2));
+    /// assert_eq!(buf, vec![0, 3, 4].into());
+    ///
+    /// assert_eq!(buf.push_back_pinned(3, 5), Some(5));
+    /// ```
+    pub fn push_back_pinned(&mut self, pinned: usize, element: A::Item) -> Option<A::Item> {
+        let pinned = cmp::max(pinned, H::PINNED);
         let existing = if self.is_full() {
             if self.capacity() == 0 {
+                H::on_evict(&element);
+                #[cfg(feature = "trace")]
+                self.trace(trace::Op::Evict);
                 return Some(element);
+            } else if pinned >= self.len() {
+                H::on_full(&element);
+                #[cfg(feature = "trace")]
+                self.trace(trace::Op::Reject);
+                return Some(element);
+            } else if pinned == 0 {
+                self.pop_front_untraced()
             } else {
-                self.pop_front()
+                self.remove(pinned)
             }
         } else {
             None
         };
+        if let Some(ref existing) = existing {
+            H::on_evict(existing);
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Evict);
+        }
 
         unsafe {
             self.push_back_unchecked(element);
         }
+        self.check_soft_limit::<H>();
+        #[cfg(feature = "trace")]
+        self.trace(trace::Op::PushBack);
 
         existing
     }
@@ -450,9 +838,31 @@ impl<A: Array> ArrayDeque<A, Wrapping> {
     where
         I: IntoIterator<Item = A::Item>,
     {
-        for element in iter.into_iter() {
-            self.push_front(element);
-        }
+        self.extend_front_with(iter, |_| ());
+    }
+
+    /// Extend deque from front with the contents of an iterator, like
+    /// `extend_front`, and returns the number of elements kicked out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 7], Wrapping> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.extend_front_counting(vec![9, 8, 7]), 0);
+    /// assert_eq!(buf.extend_front_counting(vec![6, 5, 4]), 0);
+    /// assert_eq!(buf.extend_front_counting(vec![3, 2, 1]), 2);
+    /// assert_eq!(buf, vec![1, 2, 3, 4, 5, 6, 7].into());
+    /// ```
+    pub fn extend_front_counting<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        let mut evicted = 0;
+        self.extend_front_with(iter, |_| evicted += 1);
+        evicted
     }
 
     /// Extend deque from back with the contents of an iterator.
@@ -484,66 +894,415 @@ impl<A: Array> ArrayDeque<A, Wrapping> {
     pub fn extend_back<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = A::Item>,
+    {
+        self.extend_back_with(iter, |_| ());
+    }
+
+    /// Extend deque from back with the contents of an iterator, like
+    /// `extend_back`, and returns the number of elements kicked out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 7], Wrapping> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.extend_back_counting(vec![1, 2, 3]), 0);
+    /// assert_eq!(buf.extend_back_counting(vec![4, 5, 6]), 0);
+    /// assert_eq!(buf.extend_back_counting(vec![7, 8, 9]), 2);
+    /// assert_eq!(buf, vec![3, 4, 5, 6, 7, 8, 9].into());
+    /// ```
+    pub fn extend_back_counting<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        let mut evicted = 0;
+        self.extend_back_with(iter, |_| evicted += 1);
+        evicted
+    }
+
+    /// Appends clones of the elements of `src` to the back of the deque,
+    /// cloned straight into the free slots rather than pushed one at a time.
+    ///
+    /// Kicks out as many frontmost elements as needed, as `extend_back`
+    /// would. If `src` itself is longer than the capacity, only its trailing
+    /// elements that fit are kept. With `Hooks::PINNED` elements, the
+    /// elements are pushed one at a time instead.
+    ///
+    /// Every element kicked out, or left out of `src`, is reported to the
+    /// hooks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4], Wrapping> = ArrayDeque::new();
+    ///
+    /// buf.extend_from_slice(b"abc");
+    /// buf.extend_from_slice(b"de");
+    /// assert_eq!(buf, b"bcde".to_vec().into());
+    ///
+    /// buf.extend_from_slice(b"fghij");
+    /// assert_eq!(buf, b"ghij".to_vec().into());
+    /// ```
+    pub fn extend_from_slice(&mut self, src: &[A::Item])
+    where
+        A::Item: Clone,
+    {
+        if H::PINNED > 0 {
+            return self.extend_back(src.iter().cloned());
+        }
+        let room = cmp::min(src.len(), self.capacity());
+        while self.capacity() - self.len() < room {
+            if let Some(existing) = self.pop_front_untraced() {
+                H::on_evict(&existing);
+                #[cfg(feature = "trace")]
+                self.trace(trace::Op::Evict);
+            }
+        }
+        let (left_out, src) = src.split_at(src.len() - room);
+        for element in left_out {
+            H::on_evict(element);
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Evict);
+        }
+        unsafe { self.clone_slice_to_back(src) }
+        #[cfg(feature = "trace")]
+        self.trace_pushed_back(src.len());
+    }
+
+    /// Extend deque from front with the contents of an iterator, passing every
+    /// backmost element kicked out to `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 3], Wrapping> = ArrayDeque::new();
+    /// let mut evicted = vec![];
+    ///
+    /// buf.extend_front_with(1..6, |x| evicted.push(x));
+    ///
+    /// assert_eq!(buf, vec![5, 4, 3].into());
+    /// assert_eq!(evicted, vec![1, 2]);
+    /// ```
+    pub fn extend_front_with<I, F>(&mut self, iter: I, mut f: F)
+    where
+        I: IntoIterator<Item = A::Item>,
+        F: FnMut(A::Item),
     {
         for element in iter.into_iter() {
-            self.push_back(element);
+            if let Some(existing) = self.push_front(element) {
+                f(existing);
+            }
+        }
+    }
+
+    /// Extend deque from back with the contents of an iterator, passing every
+    /// frontmost element kicked out to `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 3], Wrapping> = ArrayDeque::new();
+    /// let mut evicted = vec![];
+    ///
+    /// buf.extend_back_with(1..6, |x| evicted.push(x));
+    ///
+    /// assert_eq!(buf, vec![3, 4, 5].into());
+    /// assert_eq!(evicted, vec![1, 2]);
+    /// ```
+    pub fn extend_back_with<I, F>(&mut self, iter: I, mut f: F)
+    where
+        I: IntoIterator<Item = A::Item>,
+        F: FnMut(A::Item),
+    {
+        for element in iter.into_iter() {
+            if let Some(existing) = self.push_back(element) {
+                f(existing);
+            }
         }
     }
 }
 
 #[allow(unused_must_use)]
-impl<A: Array> Extend<A::Item> for ArrayDeque<A, Wrapping> {
+impl<A: Array, H: Hooks<A::Item>> Extend<A::Item> for ArrayDeque<A, Wrapping<H>> {
     fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = A::Item>,
     {
-        let take = self.capacity() - self.len();
-        for elt in iter.into_iter().take(take) {
-            self.push_back(elt);
-        }
+        self.extend_back(iter);
     }
 }
 
-impl<A: Array> FromIterator<A::Item> for ArrayDeque<A, Wrapping> {
+impl<A: Array, H: Hooks<A::Item>> FromIterator<A::Item> for ArrayDeque<A, Wrapping<H>> {
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = A::Item>,
     {
-        let mut array: ArrayDeque<_, Wrapping> = ArrayDeque::new();
+        let mut array: ArrayDeque<_, Wrapping<H>> = ArrayDeque::new();
         array.extend_back(iter);
         array
     }
 }
 
-impl<A: Array> Clone for ArrayDeque<A, Wrapping>
+impl<A: Array, H: Hooks<A::Item>> Clone for ArrayDeque<A, Wrapping<H>>
 where
     A::Item: Clone,
 {
     fn clone(&self) -> Self {
         self.iter().cloned().collect()
     }
-}
 
-// primitive private methods
-impl<A: Array, B: Behavior> ArrayDeque<A, B> {
-    #[inline]
-    fn wrap_add(index: usize, addend: usize) -> usize {
-        wrap_add(index, addend, A::capacity())
+    fn clone_from(&mut self, source: &Self) {
+        self.clear();
+        self.extend(source.iter().cloned());
     }
+}
 
-    #[inline]
-    fn wrap_sub(index: usize, subtrahend: usize) -> usize {
-        wrap_sub(index, subtrahend, A::capacity())
-    }
+impl<A: Array, H: Hooks<A::Item>> ArrayDeque<A, Decimating<H>> {
+    /// Appends an element to the back of a buffer
+    ///
+    /// If the deque is full, every second element is dropped first, starting
+    /// with the second one from the front, so the deque keeps spanning the
+    /// same stretch of history at half the resolution.
+    ///
+    /// Return `true` if the deque was full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // [_, _, _, 1] <-(+)- 2 => [_, _, 1, 2] -> false
+    /// // [1, 2, 3, 4] <-(+)- 5 => [_, 1, 3, 5] -> true
+    ///
+    /// use arraydeque::{ArrayDeque, Decimating};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4], Decimating> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(1..5);
+    ///
+    /// assert_eq!(buf.push_back(5), true);
+    /// assert_eq!(buf, vec![1, 3, 5].into());
+    /// ```
+    pub fn push_back(&mut self, element: A::Item) -> bool {
+        let full = self.is_full();
+        if full {
+            if self.capacity() < 2 {
+                // nothing to thin out, kick out the front element instead
+                match self.pop_front_untraced() {
+                    Some(existing) => H::on_evict(&existing),
+                    None => {
+                        H::on_evict(&element);
+                        #[cfg(feature = "trace")]
+                        self.trace(trace::Op::Evict);
+                        return true;
+                    }
+                }
+                #[cfg(feature = "trace")]
+                self.trace(trace::Op::Evict);
+            } else {
+                #[cfg(feature = "trace")]
+                let mut evicted = 0;
+                let mut index = 0;
+                for existing in self.retain_drain(|_| {
+                    index += 1;
+                    index % 2 == 1
+                }) {
+                    H::on_evict(&existing);
+                    #[cfg(feature = "trace")]
+                    {
+                        evicted += 1;
+                    }
+                }
+                #[cfg(feature = "trace")]
+                for _ in 0..evicted {
+                    self.trace(trace::Op::Evict);
+                }
+            }
+        }
 
-    #[inline]
-    fn ptr(&self) -> *const A::Item {
-        self.xs.as_ptr()
+        unsafe {
+            self.push_back_unchecked(element);
+        }
+        self.check_soft_limit::<H>();
+        #[cfg(feature = "trace")]
+        self.trace(trace::Op::PushBack);
+
+        full
     }
 
-    #[inline]
-    fn ptr_mut(&mut self) -> *mut A::Item {
-        self.xs.as_mut_ptr()
+    /// Extend deque from back with the contents of an iterator.
+    ///
+    /// Extracts all items from iterator, decimating the deque whenever it is
+    /// full.
+    ///
+    /// Returns the number of times the deque was decimated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Decimating};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4], Decimating> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.extend_back(0..8), 2);
+    /// assert_eq!(buf, vec![0, 4, 6, 7].into());
+    /// ```
+    pub fn extend_back<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        let mut decimated = 0;
+        for element in iter {
+            if self.push_back(element) {
+                decimated += 1;
+            }
+        }
+        decimated
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> Extend<A::Item> for ArrayDeque<A, Decimating<H>> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        self.extend_back(iter);
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> FromIterator<A::Item> for ArrayDeque<A, Decimating<H>> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        let mut array: ArrayDeque<_, Decimating<H>> = ArrayDeque::new();
+        array.extend_back(iter);
+        array
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> Clone for ArrayDeque<A, Decimating<H>>
+where
+    A::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.clear();
+        self.extend(source.iter().cloned());
+    }
+}
+
+// primitive private methods
+impl<A: Array, B: Behavior> ArrayDeque<A, B> {
+    /// Reports a length past the soft limit of `H`, in strict mode.
+    #[inline]
+    fn check_soft_limit<H: Hooks<A::Item>>(&self) {
+        #[cfg(feature = "strict")]
+        {
+            if self.len() > H::SOFT_LIMIT {
+                H::on_soft_limit(self.len());
+            }
+        }
+    }
+
+    /// Reports `op` to the trace subscriber.
+    #[cfg(feature = "trace")]
+    #[inline]
+    fn trace(&self, op: trace::Op) {
+        trace::emit(op, self.len(), A::capacity());
+    }
+
+    /// Reports the last `n` elements, pushed at once, as pushed to the back
+    /// one at a time.
+    #[cfg(feature = "trace")]
+    fn trace_pushed_back(&self, n: usize) {
+        for len in self.len() - n + 1..=self.len() {
+            trace::emit(trace::Op::PushBack, len, A::capacity());
+        }
+    }
+
+    /// Removes the first element without reporting it, for evictions.
+    #[inline]
+    fn pop_front_untraced(&mut self) -> Option<A::Item> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe {
+            let tail = self.tail();
+            self.set_tail_forward();
+            Some(self.buffer_read(tail))
+        }
+    }
+
+    /// Removes the last element without reporting it, for evictions.
+    #[inline]
+    fn pop_back_untraced(&mut self) -> Option<A::Item> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe {
+            self.set_head_backward();
+            let head = self.head();
+            Some(self.buffer_read(head))
+        }
+    }
+
+    #[inline]
+    fn wrap_add(index: usize, addend: usize) -> usize {
+        wrap_add(index, addend, A::capacity())
+    }
+
+    #[inline]
+    fn wrap_sub(index: usize, subtrahend: usize) -> usize {
+        wrap_sub(index, subtrahend, A::capacity())
+    }
+
+    #[inline]
+    fn ptr(&self) -> *const A::Item {
+        self.xs.as_ptr()
+    }
+
+    #[inline]
+    fn ptr_mut(&mut self) -> *mut A::Item {
+        self.xs.as_mut_ptr()
+    }
+
+    /// Returns the `len` slots from buffer index `tail` on, in order, as a
+    /// pair of slices.
+    ///
+    /// The slots must be initialized. The slices are built from raw pointers,
+    /// so no reference to the rest of the storage is created.
+    #[inline]
+    unsafe fn slots(&self, tail: usize, len: usize) -> (&[A::Item], &[A::Item]) {
+        let ptr = self.ptr();
+        let first = cmp::min(len, A::capacity() - tail);
+        (
+            slice::from_raw_parts(ptr.add(tail), first),
+            slice::from_raw_parts(ptr, len - first),
+        )
+    }
+
+    /// Returns the `len` slots from buffer index `tail` on, in order, as a
+    /// pair of mutable slices.
+    ///
+    /// The slots must be initialized.
+    #[inline]
+    unsafe fn slots_mut(&mut self, tail: usize, len: usize) -> (&mut [A::Item], &mut [A::Item]) {
+        let ptr = self.ptr_mut();
+        let first = cmp::min(len, A::capacity() - tail);
+        (
+            slice::from_raw_parts_mut(ptr.add(tail), first),
+            slice::from_raw_parts_mut(ptr, len - first),
+        )
     }
 
     #[inline]
@@ -585,7 +1344,7 @@ impl<A: Array, B: Behavior> ArrayDeque<A, B> {
 
     #[inline]
     unsafe fn set_tail_forward(&mut self) {
-        debug_assert!(self.len() > 0);
+        debug_assert!(!self.is_empty());
 
         let new_tail = Self::wrap_add(self.tail(), 1);
         let new_len = self.len() - 1;
@@ -595,7 +1354,7 @@ impl<A: Array, B: Behavior> ArrayDeque<A, B> {
 
     #[inline]
     unsafe fn set_head_backward(&mut self) {
-        debug_assert!(self.len() > 0);
+        debug_assert!(!self.is_empty());
 
         let new_len = self.len() - 1;
         self.len = ArrayIndex::from(new_len);
@@ -859,18 +1618,15 @@ impl<A: Array, B: Behavior> ArrayDeque<A, B> {
             len,
             A::capacity()
         );
-        ptr::copy(
-            self.ptr_mut().offset(src as isize),
-            self.ptr_mut().offset(dst as isize),
-            len,
-        );
+        let ptr = self.ptr_mut();
+        ptr::copy(ptr.add(src), ptr.add(dst), len);
     }
 
     /// Copies a potentially wrapping block of memory len long from src to dest.
     /// (abs(dst - src) + len) must be no larger than cap() (There must be at
     /// most one continuous overlapping region between src and dest).
     unsafe fn wrap_copy(&mut self, dst: usize, src: usize, len: usize) {
-        #[allow(dead_code)]
+        #[allow(dead_code, clippy::manual_abs_diff)]
         fn diff(a: usize, b: usize) -> usize {
             if a <= b {
                 b - a
@@ -992,14 +1748,79 @@ impl<A: Array, B: Behavior> ArrayDeque<A, B> {
         }
     }
 
+    /// Bitwise copies `src` to the back of the deque.
+    ///
+    /// The caller must ensure that there is room for `src.len()` more
+    /// elements, and that the copied elements are not also dropped elsewhere.
+    unsafe fn copy_slice_to_back(&mut self, src: &[A::Item]) {
+        debug_assert!(src.len() <= A::capacity() - self.len());
+        if src.is_empty() {
+            return;
+        }
+        let head = self.head();
+        let first = cmp::min(src.len(), A::capacity() - head);
+        ptr::copy_nonoverlapping(src.as_ptr(), self.ptr_mut().add(head), first);
+        ptr::copy_nonoverlapping(src.as_ptr().add(first), self.ptr_mut(), src.len() - first);
+        let len = self.len() + src.len();
+        self.set_len(len);
+    }
+
+    /// Clones `src` to the back of the deque.
+    ///
+    /// The caller must ensure that there is room for `src.len()` more
+    /// elements. If a clone panics, the elements cloned before it since the
+    /// wrap point are leaked.
+    unsafe fn clone_slice_to_back(&mut self, src: &[A::Item])
+    where
+        A::Item: Clone,
+    {
+        debug_assert!(src.len() <= A::capacity() - self.len());
+        let head = self.head();
+        let (a, b) = src.split_at(cmp::min(src.len(), A::capacity() - head));
+        for (i, element) in a.iter().enumerate() {
+            ptr::write(self.ptr_mut().add(head + i), element.clone());
+        }
+        let len = self.len() + a.len();
+        self.set_len(len);
+        for (i, element) in b.iter().enumerate() {
+            ptr::write(self.ptr_mut().add(i), element.clone());
+        }
+        let len = self.len() + b.len();
+        self.set_len(len);
+    }
+
+    /// Moves the first `n` elements to the back of `dst`, with bulk copies.
+    ///
+    /// The caller must ensure that `dst` has room for `n` more elements.
+    unsafe fn move_front_into<A2, B2>(&mut self, dst: &mut ArrayDeque<A2, B2>, n: usize)
+    where
+        A2: Array<Item = A::Item>,
+        B2: Behavior,
+    {
+        debug_assert!(n <= self.len());
+        {
+            let (a, b) = self.as_slices();
+            if n <= a.len() {
+                dst.copy_slice_to_back(&a[..n]);
+            } else {
+                dst.copy_slice_to_back(a);
+                dst.copy_slice_to_back(&b[..n - a.len()]);
+            }
+        }
+        let tail = Self::wrap_add(self.tail(), n);
+        let len = self.len() - n;
+        self.set_tail(tail);
+        self.set_len(len);
+    }
+
     #[inline]
     unsafe fn buffer_read(&mut self, offset: usize) -> A::Item {
-        ptr::read(self.ptr().offset(offset as isize))
+        ptr::read(self.ptr().add(offset))
     }
 
     #[inline]
     unsafe fn buffer_write(&mut self, offset: usize, element: A::Item) {
-        ptr::write(self.ptr_mut().offset(offset as isize), element);
+        ptr::write(self.ptr_mut().add(offset), element);
     }
 }
 
@@ -1025,1557 +1846,6531 @@ impl<A: Array, B: Behavior> ArrayDeque<A, B> {
         }
     }
 
-    /// Return the capacity of the `ArrayDeque`.
+    /// Initializes an empty `ArrayDeque` inside `slot` and returns it.
+    ///
+    /// The deque is never constructed by value, so it can be placed in a
+    /// static, an arena or a heap allocation without passing through the
+    /// stack.
+    ///
+    /// `MaybeUninit` never drops its contents, so elements still in the deque
+    /// when `slot` goes away are leaked unless the deque is cleared first.
+    ///
+    /// Requires the `unstable_placement` feature and Rust 1.51+.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::mem::MaybeUninit;
     /// use arraydeque::ArrayDeque;
     ///
-    /// let buf: ArrayDeque<[usize; 2]> = ArrayDeque::new();
+    /// let mut slot = Box::new(MaybeUninit::uninit());
+    /// let buf: &mut ArrayDeque<[u8; 4096]> = ArrayDeque::new_in_place(&mut slot);
     ///
-    /// assert_eq!(buf.capacity(), 2);
+    /// buf.push_back(1).unwrap();
+    /// assert_eq!(buf.len(), 1);
     /// ```
+    #[cfg(feature = "unstable_placement")]
     #[inline]
-    pub fn capacity(&self) -> usize {
-        A::capacity()
+    pub fn new_in_place(slot: &mut mem::MaybeUninit<Self>) -> &mut Self {
+        let deque = slot.as_mut_ptr();
+        unsafe {
+            ptr::addr_of_mut!((*deque).tail).write(ArrayIndex::from(0));
+            ptr::addr_of_mut!((*deque).len).write(ArrayIndex::from(0));
+            &mut *deque
+        }
     }
 
-    /// Returns the number of elements in the `ArrayDeque`.
+    /// Creates an `ArrayDeque` holding `n` default values, or returns
+    /// `Err(CapacityError { *n* })` if `n` exceeds the capacity.
+    ///
+    /// This suits deques used as a fixed set of slots updated in place
+    /// rather than pushed to.
     ///
     /// # Examples
     ///
     /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
-    ///
-    /// assert_eq!(buf.len(), 0);
+    /// use arraydeque::{ArrayDeque, CapacityError};
     ///
-    /// buf.push_back(1);
+    /// let mut slots: ArrayDeque<[u32; 4]> = ArrayDeque::with_len(3).unwrap();
+    /// slots[1] = 7;
+    /// assert_eq!(slots, vec![0, 7, 0].into());
     ///
-    /// assert_eq!(buf.len(), 1);
+    /// let too_long = ArrayDeque::<[u32; 4]>::with_len(5).unwrap_err();
+    /// assert_eq!(too_long, CapacityError { element: 5 });
     /// ```
-    #[inline]
-    pub fn len(&self) -> usize {
-        self.len.to_usize()
+    pub fn with_len(n: usize) -> Result<ArrayDeque<A, B>, CapacityError<usize>>
+    where
+        A::Item: Default,
+    {
+        if n > A::capacity() {
+            return Err(CapacityError { element: n });
+        }
+        let mut deque = ArrayDeque::new();
+        for _ in 0..n {
+            unsafe { deque.push_back_unchecked(A::Item::default()) }
+        }
+        Ok(deque)
     }
 
-    /// Returns true if the buffer contains no elements
+    /// Returns a `DequeBuilder` for constructing a deque with chained pushes.
+    ///
+    /// The capacity is only checked once, when the deque is built.
     ///
     /// # Examples
     ///
     /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
-    ///
-    /// assert!(buf.is_empty());
+    /// use arraydeque::{ArrayDeque, CapacityError};
     ///
-    /// buf.push_back(1);
+    /// let buf: ArrayDeque<[_; 4]> = ArrayDeque::builder()
+    ///     .push(2)
+    ///     .push(3)
+    ///     .push_front(1)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(buf, vec![1, 2, 3].into());
     ///
-    /// assert!(!buf.is_empty());
+    /// let overflow = ArrayDeque::<[_; 2]>::builder().extend(1..5).build();
+    /// assert_eq!(overflow, Err(CapacityError { element: 3 }));
     /// ```
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    pub fn builder() -> DequeBuilder<A, B> {
+        DequeBuilder::new()
     }
 
-    /// Returns true if the buffer is full.
+    /// Creates a full `ArrayDeque` holding the elements of `xs`, in order.
+    ///
+    /// This is a `const fn`, so lookup tables can be baked into statics and
+    /// still be used through the deque API.
+    ///
+    /// Requires Rust 1.63+.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
-    ///
-    /// assert!(!buf.is_full());
-    ///
-    /// buf.push_back(1);
+    /// static TABLE: ArrayDeque<[u16; 4]> = ArrayDeque::from_array_const([1, 2, 4, 8]);
     ///
-    /// assert!(buf.is_full());
+    /// assert!(TABLE.is_full());
+    /// assert_eq!(TABLE.get_mod(5), Some(&2));
     /// ```
+    #[cfg(has_const_generics)]
     #[inline]
-    pub fn is_full(&self) -> bool {
-        self.len() == self.capacity()
+    pub const fn from_array_const(xs: A) -> ArrayDeque<A, B>
+    where
+        A: ConstCapacity,
+    {
+        ArrayDeque {
+            xs: MaybeUninit::from_array(xs),
+            tail: <A::Index as ArrayIndex>::ZERO,
+            len: A::CAPACITY_INDEX,
+            marker: marker::PhantomData,
+        }
     }
 
-    /// Returns `true` if the `ArrayDeque` contains an element equal to the
-    /// given value.
+    /// Creates a full `ArrayDeque` from an iterator yielding exactly
+    /// `capacity()` elements.
+    ///
+    /// Return `Err(LengthError::TooMany(element))` with the first element
+    /// that did not fit if the iterator yields more elements, or
+    /// `Err(LengthError::TooFew { len })` with the number of elements it
+    /// yielded if it yields fewer.
     ///
     /// # Examples
     ///
     /// ```
-    /// use arraydeque::ArrayDeque;
+    /// use arraydeque::{ArrayDeque, LengthError};
     ///
-    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    /// let wavetable: ArrayDeque<[_; 4]> = ArrayDeque::from_iter_exact(0..4).unwrap();
+    /// assert!(wavetable.is_full());
     ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let short = ArrayDeque::<[_; 4]>::from_iter_exact(0..3);
+    /// assert_eq!(short.unwrap_err(), LengthError::TooFew { len: 3 });
     ///
-    /// assert_eq!(buf.contains(&1), true);
-    /// assert_eq!(buf.contains(&3), false);
+    /// let long = ArrayDeque::<[_; 4]>::from_iter_exact(0..6);
+    /// assert_eq!(long.unwrap_err(), LengthError::TooMany(4));
     /// ```
-    pub fn contains(&self, x: &A::Item) -> bool
+    pub fn from_iter_exact<I>(iter: I) -> Result<Self, LengthError<A::Item>>
     where
-        A::Item: PartialEq<A::Item>,
+        I: IntoIterator<Item = A::Item>,
     {
-        let (a, b) = self.as_slices();
-        a.contains(x) || b.contains(x)
+        let mut iter = iter.into_iter();
+        let mut deque = Self::new();
+        while !deque.is_full() {
+            match iter.next() {
+                Some(element) => unsafe { deque.push_back_unchecked(element) },
+                None => return Err(LengthError::TooFew { len: deque.len() }),
+            }
+        }
+        match iter.next() {
+            Some(element) => Err(LengthError::TooMany(element)),
+            None => Ok(deque),
+        }
     }
 
-    /// Provides a reference to the front element, or `None` if the sequence is
-    /// empty.
+    /// Creates an `ArrayDeque` holding the elements of `vec`, in order.
+    ///
+    /// Return `Err(CapacityError { *vec* })`, handing the vector back
+    /// untouched, if it is longer than the capacity, whatever the behavior.
+    /// A shorter vector is fine, unlike with `from_iter_exact`.
+    ///
+    /// This is not a `TryFrom` impl, as `From<Vec>` already provides one.
     ///
     /// # Examples
     ///
     /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
-    /// assert_eq!(buf.front(), None);
+    /// use arraydeque::{ArrayDeque, CapacityError, Wrapping};
     ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let buf = ArrayDeque::<[_; 3]>::try_from_vec(vec![1, 2]).unwrap();
+    /// assert_eq!(buf, vec![1, 2].into());
     ///
-    /// assert_eq!(buf.front(), Some(&1));
+    /// let long = ArrayDeque::<[_; 3], Wrapping>::try_from_vec(vec![1, 2, 3, 4]);
+    /// assert_eq!(long.unwrap_err(), CapacityError { element: vec![1, 2, 3, 4] });
     /// ```
-    pub fn front(&self) -> Option<&A::Item> {
-        if !self.is_empty() {
-            Some(&self[0])
-        } else {
-            None
+    #[cfg(feature = "std")]
+    pub fn try_from_vec(vec: Vec<A::Item>) -> Result<Self, CapacityError<Vec<A::Item>>> {
+        if vec.len() > A::capacity() {
+            return Err(CapacityError { element: vec });
         }
+        let mut deque = Self::new();
+        for element in vec {
+            unsafe { deque.push_back_unchecked(element) }
+        }
+        Ok(deque)
     }
 
-    /// Provides a mutable reference to the front element, or `None` if the
-    /// sequence is empty.
+    /// Creates an `ArrayDeque` holding the elements of `chunks`, in order.
+    ///
+    /// Return `Err(CapacityError)` if the chunks hold more elements than the
+    /// capacity of the `ArrayDeque`.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
-    /// assert_eq!(buf.front_mut(), None);
-    ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let buf: ArrayDeque<[_; 4]> = ArrayDeque::from_chunks(&[[1, 2], [3, 4]]).unwrap();
     ///
-    /// assert_eq!(buf.front_mut(), Some(&mut 1));
+    /// assert_eq!(buf, vec![1, 2, 3, 4].into());
+    /// assert!(ArrayDeque::<[_; 4]>::from_chunks(&[[1, 2, 3], [4, 5, 6]]).is_err());
     /// ```
-    pub fn front_mut(&mut self) -> Option<&mut A::Item> {
-        if !self.is_empty() {
-            Some(&mut self[0])
-        } else {
-            None
+    #[cfg(has_const_generics)]
+    pub fn from_chunks<const K: usize>(chunks: &[[A::Item; K]]) -> Result<Self, CapacityError>
+    where
+        A::Item: Clone,
+    {
+        match chunks.len().checked_mul(K) {
+            Some(len) if len <= A::capacity() => {}
+            _ => return Err(CapacityError { element: () }),
+        }
+        let mut deque = Self::new();
+        for element in chunks.iter().flat_map(|chunk| chunk.iter()) {
+            unsafe {
+                deque.push_back_unchecked(element.clone());
+            }
         }
+        Ok(deque)
     }
 
-    /// Provides a reference to the back element, or `None` if the sequence is
-    /// empty.
+    /// Creates an `ArrayDeque` holding the elements of `a` followed by the
+    /// elements of `b`, moved with bulk copies.
+    ///
+    /// Return `Err(CapacityError { *(a, b)* })`, handing both inputs back
+    /// untouched, if they hold more elements than the capacity of the new
+    /// `ArrayDeque`.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    /// let header: ArrayDeque<[_; 2]> = vec![1, 2].into();
+    /// let body: ArrayDeque<[_; 4]> = vec![3, 4, 5].into();
     ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let message: ArrayDeque<[_; 5]> = ArrayDeque::concat(header, body).unwrap();
     ///
-    /// assert_eq!(buf.back(), Some(&2));
+    /// assert_eq!(message, vec![1, 2, 3, 4, 5].into());
     /// ```
-    pub fn back(&self) -> Option<&A::Item> {
-        if !self.is_empty() {
-            Some(&self[self.len() - 1])
-        } else {
-            None
+    #[allow(clippy::type_complexity)]
+    pub fn concat<A1, B1, A2, B2>(
+        mut a: ArrayDeque<A1, B1>,
+        mut b: ArrayDeque<A2, B2>,
+    ) -> Result<Self, CapacityError<(ArrayDeque<A1, B1>, ArrayDeque<A2, B2>)>>
+    where
+        A1: Array<Item = A::Item>,
+        B1: Behavior,
+        A2: Array<Item = A::Item>,
+        B2: Behavior,
+    {
+        if a.len() + b.len() > A::capacity() {
+            return Err(CapacityError { element: (a, b) });
+        }
+        let mut deque = Self::new();
+        unsafe {
+            for src in [a.as_slices(), b.as_slices()].iter() {
+                deque.copy_slice_to_back(src.0);
+                deque.copy_slice_to_back(src.1);
+            }
+            a.set_len(0);
+            b.set_len(0);
         }
+        Ok(deque)
     }
 
-    /// Provides a mutable reference to the back element, or `None` if the
-    /// sequence is empty.
+    /// Consumes the deque, splitting its elements in order into two deques:
+    /// the ones for which `f` returns `true`, and the ones for which it
+    /// returns `false`.
+    ///
+    /// Return `Err(CapacityError { *element* })` with the first element that
+    /// did not fit into its deque. The other elements are dropped.
     ///
     /// # Examples
     ///
     /// ```
-    /// use arraydeque::ArrayDeque;
+    /// use arraydeque::{ArrayDeque, CapacityError};
     ///
-    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    /// let buf: ArrayDeque<[_; 6]> = (0..6).collect();
     ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let (even, odd): (ArrayDeque<[_; 3]>, ArrayDeque<[_; 3]>) =
+    ///     buf.clone().partition_deques(|&x| x % 2 == 0).unwrap();
+    /// assert_eq!(even, vec![0, 2, 4].into());
+    /// assert_eq!(odd, vec![1, 3, 5].into());
     ///
-    /// assert_eq!(buf.back_mut(), Some(&mut 2));
+    /// let small: Result<(ArrayDeque<[_; 2]>, ArrayDeque<[_; 4]>), _> =
+    ///     buf.partition_deques(|&x| x < 3);
+    /// assert_eq!(small, Err(CapacityError { element: 2 }));
     /// ```
-    pub fn back_mut(&mut self) -> Option<&mut A::Item> {
-        let len = self.len();
-        if !self.is_empty() {
-            Some(&mut self[len - 1])
-        } else {
-            None
+    #[allow(clippy::type_complexity)]
+    pub fn partition_deques<A1, B1, A2, B2, F>(
+        self,
+        mut f: F,
+    ) -> Result<(ArrayDeque<A1, B1>, ArrayDeque<A2, B2>), CapacityError<A::Item>>
+    where
+        A1: Array<Item = A::Item>,
+        B1: Behavior,
+        A2: Array<Item = A::Item>,
+        B2: Behavior,
+        F: FnMut(&A::Item) -> bool,
+    {
+        let mut left = ArrayDeque::new();
+        let mut right = ArrayDeque::new();
+        for element in self {
+            if f(&element) {
+                if left.is_full() {
+                    return Err(CapacityError { element });
+                }
+                unsafe { left.push_back_unchecked(element) }
+            } else {
+                if right.is_full() {
+                    return Err(CapacityError { element });
+                }
+                unsafe { right.push_back_unchecked(element) }
+            }
         }
+        Ok((left, right))
     }
 
-    /// Retrieves an element in the `ArrayDeque` by index.
+    /// Creates an `ArrayDeque` holding the elements of the sorted deques `a`
+    /// and `b`, merged in sorted order.
     ///
-    /// Element at index 0 is the front of the queue.
+    /// The merge is stable: equal elements keep their order, and elements of
+    /// `a` come before equal elements of `b`. Runs of elements coming from the
+    /// same input are moved with bulk copies.
+    ///
+    /// Return `Err(CapacityError { *(a, b)* })`, handing both inputs back
+    /// untouched, if they hold more elements than the capacity of the new
+    /// `ArrayDeque`.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// let a: ArrayDeque<[_; 4]> = vec![1, 4, 5, 8].into();
+    /// let b: ArrayDeque<[_; 4]> = vec![2, 3, 9].into();
     ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let merged: ArrayDeque<[_; 8]> = ArrayDeque::merge_sorted(a, b).unwrap();
     ///
-    /// assert_eq!(buf.get(1), Some(&1));
+    /// assert_eq!(merged, vec![1, 2, 3, 4, 5, 8, 9].into());
     /// ```
-    #[inline]
-    pub fn get(&self, index: usize) -> Option<&A::Item> {
-        if index < self.len() {
-            let idx = Self::wrap_add(self.tail(), index);
-            unsafe { Some(&*self.ptr().offset(idx as isize)) }
-        } else {
-            None
+    #[allow(clippy::type_complexity)]
+    pub fn merge_sorted<A1, B1, A2, B2>(
+        mut a: ArrayDeque<A1, B1>,
+        mut b: ArrayDeque<A2, B2>,
+    ) -> Result<Self, CapacityError<(ArrayDeque<A1, B1>, ArrayDeque<A2, B2>)>>
+    where
+        A::Item: Ord,
+        A1: Array<Item = A::Item>,
+        B1: Behavior,
+        A2: Array<Item = A::Item>,
+        B2: Behavior,
+    {
+        if a.len() + b.len() > A::capacity() {
+            return Err(CapacityError { element: (a, b) });
         }
+        let mut deque = Self::new();
+        deque.merge_into_back(&mut a, &mut b);
+        Ok(deque)
     }
 
-    /// Retrieves an element in the `ArrayDeque` mutably by index.
+    /// Merges the sorted deque `other` into this sorted deque, leaving `other`
+    /// empty.
     ///
-    /// Element at index 0 is the front of the queue.
+    /// The merge is stable: equal elements keep their order, and elements of
+    /// `self` come before equal elements of `other`.
+    ///
+    /// Return `Ok(())` if the merge succeeds, or return
+    /// `Err(CapacityError { () })`, leaving both deques unchanged, if they
+    /// hold more elements than the capacity.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// let mut buf: ArrayDeque<[_; 6]> = vec![1, 3, 5].into();
+    /// let mut other: ArrayDeque<[_; 3]> = vec![2, 3, 6].into();
     ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// buf.merge_from(&mut other).unwrap();
     ///
-    /// assert_eq!(buf.get_mut(1), Some(&mut 1));
+    /// assert_eq!(buf, vec![1, 2, 3, 3, 5, 6].into());
+    /// assert!(other.is_empty());
     /// ```
-    #[inline]
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut A::Item> {
-        if index < self.len() {
-            let idx = Self::wrap_add(self.tail(), index);
-            unsafe { Some(&mut *self.ptr_mut().offset(idx as isize)) }
-        } else {
-            None
+    pub fn merge_from<A2, B2>(
+        &mut self,
+        other: &mut ArrayDeque<A2, B2>,
+    ) -> Result<(), CapacityError>
+    where
+        A::Item: Ord,
+        A2: Array<Item = A::Item>,
+        B2: Behavior,
+    {
+        if self.len() + other.len() > A::capacity() {
+            return Err(CapacityError { element: () });
         }
+        let mut mine: ArrayDeque<A, B> = Self::new();
+        self.swap_contents(&mut mine);
+        self.merge_into_back(&mut mine, other);
+        Ok(())
     }
 
-    /// Returns a front-to-back iterator.
+    /// Moves the first `n` elements to the back of `dst`, keeping their order,
+    /// with bulk copies.
+    ///
+    /// Return `Ok(())` if the transfer succeeds, or return
+    /// `Err(CapacityError { () })`, leaving both deques unchanged, if `dst`
+    /// does not have room for `n` more elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the length of the deque.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let mut stage1: ArrayDeque<[_; 8]> = (0..6).collect();
+    /// let mut stage2: ArrayDeque<[_; 4]> = ArrayDeque::new();
     ///
-    /// let expected = vec![0, 1, 2];
+    /// stage1.transfer_front(&mut stage2, 3).unwrap();
+    /// assert!(stage1.transfer_front(&mut stage2, 2).is_err());
     ///
-    /// assert!(buf.iter().eq(expected.iter()));
+    /// assert_eq!(stage1, vec![3, 4, 5].into());
+    /// assert_eq!(stage2, vec![0, 1, 2].into());
     /// ```
-    #[inline]
-    pub fn iter(&self) -> Iter<A::Item> {
-        Iter {
-            tail: self.tail(),
-            len: self.len(),
-            ring: self.xs.as_slice(),
+    pub fn transfer_front<A2, B2>(
+        &mut self,
+        dst: &mut ArrayDeque<A2, B2>,
+        n: usize,
+    ) -> Result<(), CapacityError>
+    where
+        A2: Array<Item = A::Item>,
+        B2: Behavior,
+    {
+        assert!(n <= self.len(), "n out of bounds");
+        if dst.capacity() - dst.len() < n {
+            return Err(CapacityError { element: () });
         }
+        unsafe { self.move_front_into(dst, n) }
+        Ok(())
     }
 
-    /// Returns a front-to-back iterator that returns mutable references.
+    /// Appends the sorted elements of `a` and `b` in sorted order, leaving
+    /// both empty.
+    ///
+    /// Panics if there is not enough room for all of them.
+    fn merge_into_back<A1, B1, A2, B2>(
+        &mut self,
+        a: &mut ArrayDeque<A1, B1>,
+        b: &mut ArrayDeque<A2, B2>,
+    ) where
+        A::Item: Ord,
+        A1: Array<Item = A::Item>,
+        B1: Behavior,
+        A2: Array<Item = A::Item>,
+        B2: Behavior,
+    {
+        assert!(a.len() + b.len() <= A::capacity() - self.len());
+        while !a.is_empty() || !b.is_empty() {
+            let run = match b.front() {
+                Some(y) => a.iter().take_while(|x| *x <= y).count(),
+                None => a.len(),
+            };
+            unsafe { a.move_front_into(self, run) };
+            let run = match a.front() {
+                Some(x) => b.iter().take_while(|y| *y < x).count(),
+                None => b.len(),
+            };
+            unsafe { b.move_front_into(self, run) };
+        }
+    }
+
+    /// Return the capacity of the `ArrayDeque`.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[usize; 3]> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
-    ///
-    /// let mut expected = vec![0, 1, 2];
+    /// let buf: ArrayDeque<[usize; 2]> = ArrayDeque::new();
     ///
-    /// assert!(buf.iter_mut().eq(expected.iter_mut()));
+    /// assert_eq!(buf.capacity(), 2);
     /// ```
     #[inline]
-    pub fn iter_mut(&mut self) -> IterMut<A::Item> {
-        IterMut {
-            tail: self.tail(),
-            len: self.len(),
-            ring: self.xs.as_mut_slice(),
-        }
+    pub fn capacity(&self) -> usize {
+        A::capacity()
     }
 
-    /// Removes the first element and returns it, or `None` if the sequence is
-    /// empty.
+    /// Returns the number of free slots, that is how many more elements can
+    /// be pushed before the deque is full.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    /// let mut buf: ArrayDeque<[usize; 4]> = ArrayDeque::new();
     ///
     /// buf.push_back(1);
-    /// buf.push_back(2);
     ///
-    /// assert_eq!(buf.pop_front(), Some(1));
-    /// assert_eq!(buf.pop_front(), Some(2));
-    /// assert_eq!(buf.pop_front(), None);
+    /// assert_eq!(buf.spare_slots(), 3);
     /// ```
-    pub fn pop_front(&mut self) -> Option<A::Item> {
-        if self.is_empty() {
-            return None;
-        }
-        unsafe {
-            let tail = self.tail();
-            self.set_tail_forward();
-            Some(self.buffer_read(tail))
-        }
+    #[inline]
+    pub fn spare_slots(&self) -> usize {
+        A::capacity() - self.len()
     }
 
-    /// Removes the last element from a buffer and returns it, or `None` if
-    /// it is empty.
+    /// Returns the size of one element slot in bytes, including the padding
+    /// that rounds the element up to its alignment.
+    ///
+    /// Requires Rust 1.63+.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
-    /// assert_eq!(buf.pop_back(), None);
-    ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
-    ///
-    /// assert_eq!(buf.pop_back(), Some(2));
-    /// assert_eq!(buf.pop_back(), Some(1));
+    /// // 5 bytes of data, padded to the alignment of `u32`
+    /// assert_eq!(ArrayDeque::<[(u32, u8); 4]>::slot_size(), 8);
     /// ```
-    pub fn pop_back(&mut self) -> Option<A::Item> {
-        if self.is_empty() {
-            return None;
-        }
-        unsafe {
-            self.set_head_backward();
-            let head = self.head();
-            Some(self.buffer_read(head))
-        }
+    #[cfg(has_const_generics)]
+    #[inline]
+    pub const fn slot_size() -> usize {
+        mem::size_of::<A::Item>()
     }
 
-    /// Clears the buffer, removing all values.
+    /// Returns the size of the `ArrayDeque` type in bytes.
+    ///
+    /// This is a `const fn`, so RAM budgets can be checked at compile time.
+    ///
+    /// Requires Rust 1.63+.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
-    ///
-    /// buf.push_back(1);
-    /// buf.clear();
+    /// type Queue = ArrayDeque<[u32; 64]>;
     ///
-    /// assert!(buf.is_empty());
+    /// const _: () = assert!(Queue::footprint() <= 260);
     /// ```
+    #[cfg(has_const_generics)]
     #[inline]
-    pub fn clear(&mut self) {
-        self.drain(..);
+    pub const fn footprint() -> usize {
+        mem::size_of::<Self>()
     }
 
-    /// Create a draining iterator that removes the specified range in the
-    /// `ArrayDeque` and yields the removed items.
+    /// Returns the memory layout of the `ArrayDeque` type.
     ///
-    /// Note 1: The element range is removed even if the iterator is not
-    /// consumed until the end.
-    ///
-    /// Note 2: It is unspecified how many elements are removed from the deque,
-    /// if the `Drain` value is not dropped, but the borrow it holds expires
-    /// (eg. due to mem::forget).
-    ///
-    /// # Panics
+    /// This is a `const fn`, so RAM budgets can be checked at compile time.
     ///
-    /// Panics if the starting point is greater than the end point or if
-    /// the end point is greater than the length of the deque.
+    /// Requires Rust 1.63+.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
-    ///
-    /// {
-    ///     let drain = buf.drain(2..);
-    ///     assert!(vec![2].into_iter().eq(drain));
-    /// }
-    ///
-    /// {
-    ///     let iter = buf.iter();
-    ///     assert!(vec![0, 1].iter().eq(iter));
-    /// }
+    /// const LAYOUT: arraydeque::DequeLayout = ArrayDeque::<[u16; 8]>::layout();
     ///
-    /// // A full range clears all contents
-    /// buf.drain(..);
-    /// assert!(buf.is_empty());
+    /// assert_eq!(LAYOUT.capacity, 8);
+    /// assert_eq!(LAYOUT.element_size, 2);
+    /// assert_eq!(LAYOUT.size, LAYOUT.capacity * LAYOUT.element_size + LAYOUT.overhead);
     /// ```
-    pub fn drain<R>(&mut self, range: R) -> Drain<A, B>
+    #[cfg(has_const_generics)]
+    #[inline]
+    pub const fn layout() -> DequeLayout
     where
-        R: RangeArgument<usize>,
+        A: CapacityStorage,
     {
-        let len = self.len();
-        let start = range.start().unwrap_or(0);
-        let end = range.end().unwrap_or(len);
-        assert!(start <= end, "drain lower bound was too large");
-        assert!(end <= len, "drain upper bound was too large");
-
-        let drain_tail = Self::wrap_add(self.tail(), start);
-        let drain_head = Self::wrap_add(self.tail(), end);
-        let drain_len = end - start;
-
-        unsafe { self.set_len(start) }
-
-        Drain {
-            deque: self as *mut _,
-            after_tail: drain_head,
-            after_len: len - end,
-            iter: Iter {
-                tail: drain_tail,
-                len: drain_len,
-                ring: self.xs.as_mut_slice(),
-            },
+        let size = mem::size_of::<Self>();
+        let element_size = mem::size_of::<A::Item>();
+        DequeLayout {
+            size,
+            align: mem::align_of::<Self>(),
+            capacity: A::CAPACITY,
+            element_size,
+            overhead: size - A::CAPACITY * element_size,
         }
     }
 
-    /// Swaps elements at indices `i` and `j`.
-    ///
-    /// `i` and `j` may be equal.
-    ///
-    /// Fails if there is no element with either index.
-    ///
-    /// Element at index 0 is the front of the queue.
+    /// Returns the number of elements in the `ArrayDeque`.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
     ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// assert_eq!(buf.len(), 0);
     ///
-    /// buf.swap(0, 2);
+    /// buf.push_back(1);
     ///
-    /// assert_eq!(buf, vec![2, 1, 0].into());
+    /// assert_eq!(buf.len(), 1);
     /// ```
     #[inline]
-    pub fn swap(&mut self, i: usize, j: usize) {
-        assert!(i < self.len());
-        assert!(j < self.len());
-        let ri = Self::wrap_add(self.tail(), i);
-        let rj = Self::wrap_add(self.tail(), j);
-        unsafe {
-            ptr::swap(
-                self.ptr_mut().offset(ri as isize),
-                self.ptr_mut().offset(rj as isize),
-            )
-        }
+    pub fn len(&self) -> usize {
+        self.len.to_usize()
     }
 
-    /// Removes an element from anywhere in the `ArrayDeque` and returns it, replacing it with the
-    /// last element.
-    ///
-    /// This does not preserve ordering, but is O(1).
-    ///
-    /// Returns `None` if `index` is out of bounds.
-    ///
-    /// Element at index 0 is the front of the queue.
+    /// Returns true if the buffer contains no elements
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
-    /// assert_eq!(buf.swap_remove_back(0), None);
+    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
+    ///
+    /// assert!(buf.is_empty());
     ///
-    /// buf.push_back(0);
     /// buf.push_back(1);
-    /// buf.push_back(2);
     ///
-    /// assert_eq!(buf.swap_remove_back(0), Some(0));
-    /// assert_eq!(buf, vec![2, 1].into());
+    /// assert!(!buf.is_empty());
     /// ```
-    pub fn swap_remove_back(&mut self, index: usize) -> Option<A::Item> {
-        let length = self.len();
-        if length > 0 && index < length - 1 {
-            self.swap(index, length - 1);
-        } else if index >= length {
-            return None;
-        }
-        self.pop_back()
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
-    /// Removes an element from anywhere in the `ArrayDeque` and returns it,
-    /// replacing it with the first element.
-    ///
-    /// This does not preserve ordering, but is O(1).
-    ///
-    /// Returns `None` if `index` is out of bounds.
-    ///
-    /// Element at index 0 is the front of the queue.
+    /// Returns true if the buffer is full.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
-    /// assert_eq!(buf.swap_remove_back(0), None);
+    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
+    ///
+    /// assert!(!buf.is_full());
     ///
-    /// buf.push_back(0);
     /// buf.push_back(1);
-    /// buf.push_back(2);
     ///
-    /// assert_eq!(buf.swap_remove_front(2), Some(2));
-    /// assert_eq!(buf, vec![1, 0].into());
+    /// assert!(buf.is_full());
     /// ```
-    pub fn swap_remove_front(&mut self, index: usize) -> Option<A::Item> {
-        let length = self.len();
-        if length > 0 && index < length && index != 0 {
-            self.swap(index, 0);
-        } else if index >= length {
-            return None;
-        }
-        self.pop_front()
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
     }
 
-    /// Removes and returns the element at `index` from the `ArrayDeque`.
-    /// Whichever end is closer to the removal point will be moved to make
-    /// room, and all the affected elements will be moved to new positions.
-    /// Returns `None` if `index` is out of bounds.
-    ///
-    /// Element at index 0 is the front of the queue.
+    /// Returns `true` if the `ArrayDeque` contains an element equal to the
+    /// given value.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
     ///
-    /// buf.push_back(0);
     /// buf.push_back(1);
     /// buf.push_back(2);
     ///
-    /// assert_eq!(buf.remove(1), Some(1));
-    /// assert_eq!(buf, vec![0, 2].into());
+    /// assert_eq!(buf.contains(&1), true);
+    /// assert_eq!(buf.contains(&3), false);
     /// ```
-    pub fn remove(&mut self, index: usize) -> Option<A::Item> {
-        if self.is_empty() || self.len() <= index {
-            return None;
-        }
-
-        // There are three main cases:
-        //  Elements are contiguous
-        //  Elements are discontiguous and the removal is in the tail section
-        //  Elements are discontiguous and the removal is in the head section
-        //      - special case when elements are technically contiguous,
-        //        but self.head = 0
-        //
-        // For each of those there are two more cases:
-        //  Insert is closer to tail
-        //  Insert is closer to head
-        //
-        // Key: H - self.head
-        //      T - self.tail
-        //      o - Valid element
-        //      x - Element marked for removal
-        //      R - Indicates element that is being removed
-        //      M - Indicates element was moved
-
-        let idx = Self::wrap_add(self.tail(), index);
-
-        let elem = unsafe { Some(self.buffer_read(idx)) };
-
-        let distance_to_tail = index;
-        let distance_to_head = self.len() - index;
+    pub fn contains(&self, x: &A::Item) -> bool
+    where
+        A::Item: PartialEq<A::Item>,
+    {
+        let (a, b) = self.as_slices();
+        a.contains(x) || b.contains(x)
+    }
 
-        let contiguous = self.is_contiguous();
+    /// Returns `true` if `needle` is a prefix of the `ArrayDeque`.
+    ///
+    /// Each region is compared as a slice, even if the prefix straddles the
+    /// wrap point of the internal buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![1, 2, 3]);
+    ///
+    /// assert!(buf.starts_with(&[1, 2]));
+    /// assert!(buf.starts_with(&[]));
+    /// assert!(!buf.starts_with(&[2]));
+    /// assert!(!buf.starts_with(&[1, 2, 3, 4]));
+    /// ```
+    pub fn starts_with(&self, needle: &[A::Item]) -> bool
+    where
+        A::Item: PartialEq,
+    {
+        if needle.len() > self.len() {
+            return false;
+        }
+        let (a, b) = self.as_slices();
+        let split = cmp::min(needle.len(), a.len());
+        a[..split] == needle[..split] && b[..needle.len() - split] == needle[split..]
+    }
 
-        match (
-            contiguous,
-            distance_to_tail <= distance_to_head,
-            idx >= self.tail(),
-        ) {
-            (true, true, _) => {
-                unsafe {
-                    // contiguous, remove closer to tail:
-                    //
-                    //             T   R         H
-                    //      [. . . o o x o o o o . . . . . .]
-                    //
-                    //               T           H
-                    //      [. . . . o o o o o o . . . . . .]
-                    //               M M
+    /// Returns `true` if `needle` is a suffix of the `ArrayDeque`.
+    ///
+    /// Each region is compared as a slice, even if the suffix straddles the
+    /// wrap point of the internal buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![1, 2, 3]);
+    ///
+    /// assert!(buf.ends_with(&[2, 3]));
+    /// assert!(buf.ends_with(&[]));
+    /// assert!(!buf.ends_with(&[2]));
+    /// assert!(!buf.ends_with(&[0, 1, 2, 3]));
+    /// ```
+    pub fn ends_with(&self, needle: &[A::Item]) -> bool
+    where
+        A::Item: PartialEq,
+    {
+        if needle.len() > self.len() {
+            return false;
+        }
+        let (a, b) = self.as_slices();
+        if needle.len() <= b.len() {
+            return b[b.len() - needle.len()..] == *needle;
+        }
+        let split = needle.len() - b.len();
+        a[a.len() - split..] == needle[..split] && b == &needle[split..]
+    }
 
-                    let tail = self.tail();
-                    self.copy(tail + 1, tail, index);
-                    self.set_tail_forward();
-                }
-            }
-            (true, false, _) => {
-                unsafe {
-                    // contiguous, remove closer to head:
-                    //
-                    //             T       R     H
-                    //      [. . . o o o o x o o . . . . . .]
-                    //
-                    //             T           H
-                    //      [. . . o o o o o o . . . . . . .]
-                    //                     M M
+    /// Returns the index of the first occurrence of `needle` as a contiguous
+    /// run of elements, or `None` if it does not occur.
+    ///
+    /// Occurrences straddling the wrap point of the internal buffer are found
+    /// as well, without making the deque contiguous first. Candidates are
+    /// located by scanning for the first element of `needle`, and each one is
+    /// checked with slice comparisons, which compile to `memcmp` for bytes.
+    /// This is no substring search algorithm, so the worst case, such as a
+    /// needle of repeated elements in a deque full of them, takes
+    /// `len() * needle.len()` comparisons.
+    ///
+    /// An empty `needle` is found at index 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 5]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(3);
+    /// buf.push_back(4);
+    /// buf.push_front(2);
+    /// buf.push_front(1);
+    ///
+    /// assert_eq!(buf.find_subsequence(&[2, 3]), Some(1));
+    /// assert_eq!(buf.find_subsequence(&[3, 2]), None);
+    /// ```
+    pub fn find_subsequence(&self, needle: &[A::Item]) -> Option<usize>
+    where
+        A::Item: PartialEq,
+    {
+        let (first, rest) = match needle.split_first() {
+            Some(split) => split,
+            None => return Some(0),
+        };
+        if needle.len() > self.len() {
+            return None;
+        }
 
-                    let head = self.head();
-                    self.copy(idx, idx + 1, head - idx - 1);
-                    self.set_head_backward();
-                }
+        let (a, b) = self.as_slices();
+        let last = self.len() - needle.len();
+        let mut start = 0;
+        while start <= last {
+            let candidate = if start < a.len() {
+                a[start..]
+                    .iter()
+                    .position(|x| x == first)
+                    .map(|i| start + i)
+                    .or_else(|| b.iter().position(|x| x == first).map(|i| a.len() + i))
+            } else {
+                b[start - a.len()..]
+                    .iter()
+                    .position(|x| x == first)
+                    .map(|i| start + i)
+            };
+            let i = match candidate {
+                Some(i) if i <= last => i,
+                _ => return None,
+            };
+            let j = i + 1;
+            let found = if j >= a.len() {
+                b[j - a.len()..][..rest.len()] == *rest
+            } else if j + rest.len() <= a.len() {
+                a[j..j + rest.len()] == *rest
+            } else {
+                let (head, tail) = rest.split_at(a.len() - j);
+                a[j..] == *head && b[..tail.len()] == *tail
+            };
+            if found {
+                return Some(i);
             }
-            (false, true, true) => {
-                unsafe {
-                    // discontiguous, remove closer to tail, tail section:
-                    //
-                    //                   H         T   R
-                    //      [o o o o o o . . . . . o o x o o]
-                    //
-                    //                   H           T
-                    //      [o o o o o o . . . . . . o o o o]
-                    //                               M M
+            start = i + 1;
+        }
+        None
+    }
 
-                    let tail = self.tail();
-                    self.copy(tail + 1, tail, index);
-                    self.set_tail_forward();
-                }
+    /// Returns the first element comparing as `ordering` against every
+    /// element before it, along with its index.
+    fn extremum(&self, ordering: Ordering) -> Option<(usize, &A::Item)>
+    where
+        A::Item: Ord,
+    {
+        let (a, b) = self.as_slices();
+        let offset = a.len();
+        let mut best: Option<(usize, &A::Item)> = None;
+        for (i, x) in a
+            .iter()
+            .enumerate()
+            .chain(b.iter().enumerate().map(|(i, x)| (offset + i, x)))
+        {
+            match best {
+                Some((_, y)) if x.cmp(y) != ordering => {}
+                _ => best = Some((i, x)),
             }
-            (false, false, false) => {
-                unsafe {
-                    // discontiguous, remove closer to head, head section:
-                    //
-                    //               R     H           T
-                    //      [o o o o x o o . . . . . . o o o]
-                    //
-                    //                   H             T
-                    //      [o o o o o o . . . . . . . o o o]
-                    //               M M
+        }
+        best
+    }
 
-                    let head = self.head();
-                    self.copy(idx, idx + 1, head - idx - 1);
-                    self.set_head_backward();
-                }
-            }
-            (false, false, true) => {
-                unsafe {
-                    // discontiguous, remove closer to head, tail section:
-                    //
-                    //             H           T         R
-                    //      [o o o . . . . . . o o o o o x o]
-                    //
-                    //           H             T
-                    //      [o o . . . . . . . o o o o o o o]
-                    //       M M                         M M
-                    //
-                    // or quasi-discontiguous, remove next to head, tail section:
-                    //
-                    //       H                 T         R
-                    //      [. . . . . . . . . o o o o o x o]
-                    //
-                    //                         T           H
-                    //      [. . . . . . . . . o o o o o o .]
-                    //                                   M
+    /// Returns a reference to the minimum element, or `None` if the deque is
+    /// empty.
+    ///
+    /// If several elements are equally minimum, the first one is returned.
+    /// Named so as not to be shadowed by `Ord::min`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// assert_eq!(buf.min_element(), None);
+    ///
+    /// buf.extend_back(vec![3, 1, 2]);
+    ///
+    /// assert_eq!(buf.min_element(), Some(&1));
+    /// ```
+    pub fn min_element(&self) -> Option<&A::Item>
+    where
+        A::Item: Ord,
+    {
+        self.extremum(Ordering::Less).map(|(_, x)| x)
+    }
 
-                    // draw in elements in the tail section
-                    self.copy(idx, idx + 1, A::capacity() - idx - 1);
+    /// Returns a reference to the maximum element, or `None` if the deque is
+    /// empty.
+    ///
+    /// If several elements are equally maximum, the first one is returned.
+    /// Named so as not to be shadowed by `Ord::max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// assert_eq!(buf.max_element(), None);
+    ///
+    /// buf.extend_back(vec![3, 1, 2]);
+    ///
+    /// assert_eq!(buf.max_element(), Some(&3));
+    /// ```
+    pub fn max_element(&self) -> Option<&A::Item>
+    where
+        A::Item: Ord,
+    {
+        self.extremum(Ordering::Greater).map(|(_, x)| x)
+    }
 
-                    // Prevents underflow.
-                    if self.head() != 0 {
-                        // copy first element into empty spot
-                        self.copy(A::capacity() - 1, 0, 1);
+    /// Returns the index of the minimum element, or `None` if the deque is
+    /// empty.
+    ///
+    /// If several elements are equally minimum, the index of the first one is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    /// assert_eq!(buf.position_min(), None);
+    ///
+    /// buf.extend_back(vec![3, 1, 2, 1]);
+    ///
+    /// assert_eq!(buf.position_min(), Some(1));
+    /// ```
+    pub fn position_min(&self) -> Option<usize>
+    where
+        A::Item: Ord,
+    {
+        self.extremum(Ordering::Less).map(|(i, _)| i)
+    }
+
+    /// Returns the index of the maximum element, or `None` if the deque is
+    /// empty.
+    ///
+    /// If several elements are equally maximum, the index of the first one is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    /// assert_eq!(buf.position_max(), None);
+    ///
+    /// buf.extend_back(vec![1, 3, 2, 3]);
+    ///
+    /// assert_eq!(buf.position_max(), Some(1));
+    /// ```
+    pub fn position_max(&self) -> Option<usize>
+    where
+        A::Item: Ord,
+    {
+        self.extremum(Ordering::Greater).map(|(i, _)| i)
+    }
+
+    /// Provides a reference to the front element, or `None` if the sequence is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    /// assert_eq!(buf.front(), None);
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.front(), Some(&1));
+    /// ```
+    pub fn front(&self) -> Option<&A::Item> {
+        if !self.is_empty() {
+            Some(&self[0])
+        } else {
+            None
+        }
+    }
+
+    /// Provides a mutable reference to the front element, or `None` if the
+    /// sequence is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    /// assert_eq!(buf.front_mut(), None);
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.front_mut(), Some(&mut 1));
+    /// ```
+    pub fn front_mut(&mut self) -> Option<&mut A::Item> {
+        if !self.is_empty() {
+            Some(&mut self[0])
+        } else {
+            None
+        }
+    }
+
+    /// Provides a reference to the back element, or `None` if the sequence is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.back(), Some(&2));
+    /// ```
+    pub fn back(&self) -> Option<&A::Item> {
+        if !self.is_empty() {
+            Some(&self[self.len() - 1])
+        } else {
+            None
+        }
+    }
+
+    /// Provides a mutable reference to the back element, or `None` if the
+    /// sequence is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.back_mut(), Some(&mut 2));
+    /// ```
+    pub fn back_mut(&mut self) -> Option<&mut A::Item> {
+        let len = self.len();
+        if !self.is_empty() {
+            Some(&mut self[len - 1])
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves an element in the `ArrayDeque` by index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.get(1), Some(&1));
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&A::Item> {
+        if index < self.len() {
+            let idx = Self::wrap_add(self.tail(), index);
+            unsafe { Some(&*self.ptr().add(idx)) }
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves an element in the `ArrayDeque` mutably by index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.get_mut(1), Some(&mut 1));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut A::Item> {
+        if index < self.len() {
+            let idx = Self::wrap_add(self.tail(), index);
+            unsafe { Some(&mut *self.ptr_mut().add(idx)) }
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the pair of slices which contain, in order, the elements in
+    /// a range.
+    ///
+    /// Returns `None` if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_front(2);
+    ///
+    /// assert_eq!(buf.get_range(..2), Some((&[2][..], &[0][..])));
+    /// assert_eq!(buf.get_range(1..), Some((&[0, 1][..], &[][..])));
+    /// assert_eq!(buf.get_range(..=0), Some((&[2][..], &[][..])));
+    /// assert_eq!(buf.get_range(2..4), None);
+    /// ```
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn get_range<R>(&self, range: R) -> Option<(&[A::Item], &[A::Item])>
+    where
+        R: RangeArgument<usize>,
+    {
+        index::get_range(self.as_slices(), range)
+    }
+
+    /// Retrieves the pair of mutable slices which contain, in order, the
+    /// elements in a range.
+    ///
+    /// Returns `None` if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// if let Some((front, back)) = buf.get_range_mut(1..=2) {
+    ///     for x in front.iter_mut().chain(back) {
+    ///         *x *= 10;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(buf, vec![0, 10, 20].into());
+    /// ```
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn get_range_mut<R>(&mut self, range: R) -> Option<(&mut [A::Item], &mut [A::Item])>
+    where
+        R: RangeArgument<usize>,
+    {
+        index::get_range_mut(self.as_mut_slices(), range)
+    }
+
+    /// Retrieves an element at `index` modulo the length of the deque, or
+    /// `None` if the deque is empty.
+    ///
+    /// This never goes out of bounds, which suits circular lookup tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// assert_eq!(buf.get_mod(0), None);
+    ///
+    /// buf.extend_back(vec![0, 1, 2]);
+    ///
+    /// assert_eq!(buf.get_mod(1), Some(&1));
+    /// assert_eq!(buf.get_mod(5), Some(&2));
+    /// ```
+    #[inline]
+    pub fn get_mod(&self, index: usize) -> Option<&A::Item> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(&self[index % self.len()])
+        }
+    }
+
+    /// Retrieves a mutable element at `index` modulo the length of the deque,
+    /// or `None` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// assert_eq!(buf.get_mod_mut(0), None);
+    ///
+    /// buf.extend_back(vec![0, 1, 2]);
+    ///
+    /// if let Some(x) = buf.get_mod_mut(4) {
+    ///     *x = 7;
+    /// }
+    ///
+    /// assert_eq!(buf, vec![0, 7, 2].into());
+    /// ```
+    #[inline]
+    pub fn get_mod_mut(&mut self, index: usize) -> Option<&mut A::Item> {
+        if self.is_empty() {
+            None
+        } else {
+            let len = self.len();
+            Some(&mut self[index % len])
+        }
+    }
+
+    /// Returns a front-to-back iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// let expected = vec![0, 1, 2];
+    ///
+    /// assert!(buf.iter().eq(expected.iter()));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, A::Item> {
+        let (a, b) = self.as_slices();
+        Iter { a, b }
+    }
+
+    /// Returns a front-to-back iterator that returns mutable references.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[usize; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// let mut expected = vec![0, 1, 2];
+    ///
+    /// assert!(buf.iter_mut().eq(expected.iter_mut()));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, A::Item> {
+        let (a, b) = self.as_mut_slices();
+        IterMut { a, b }
+    }
+
+    /// Returns a front-to-back iterator over the elements in the given range,
+    /// without removing them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut history: ArrayDeque<[_; 8]> = ArrayDeque::new();
+    ///
+    /// history.extend_back(0..6);
+    ///
+    /// // the last 2 elements
+    /// let k = 2;
+    /// assert!(history.range(history.len() - k..).eq(&[4, 5]));
+    /// assert_eq!(history.range(1..3).len(), 2);
+    /// ```
+    #[inline]
+    pub fn range<R>(&self, range: R) -> Iter<'_, A::Item>
+    where
+        R: RangeArgument<usize>,
+    {
+        let (tail, len) = self.range_bounds(range);
+        let (a, b) = unsafe { self.slots(tail, len) };
+        Iter { a, b }
+    }
+
+    /// Returns a front-to-back iterator over mutable references to the
+    /// elements in the given range, without removing them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![1, 2, 3, 4]);
+    ///
+    /// for x in buf.range_mut(2..) {
+    ///     *x *= 10;
+    /// }
+    /// assert_eq!(buf, vec![1, 2, 30, 40].into());
+    /// ```
+    #[inline]
+    pub fn range_mut<R>(&mut self, range: R) -> IterMut<'_, A::Item>
+    where
+        R: RangeArgument<usize>,
+    {
+        let (tail, len) = self.range_bounds(range);
+        let (a, b) = unsafe { self.slots_mut(tail, len) };
+        IterMut { a, b }
+    }
+
+    /// Returns a back-to-front iterator over the elements in the given range,
+    /// without removing them.
+    ///
+    /// This is `range(range).rev()`. It walks at most two slices, and folds
+    /// them from the back slice-wise, so newest-first scans over a window
+    /// are as cheap as front-to-back ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut history: ArrayDeque<[_; 8]> = ArrayDeque::new();
+    ///
+    /// history.extend_back(0..6);
+    ///
+    /// // the newest element below 3, ignoring the last one
+    /// assert_eq!(history.range_back(..5).find(|&&x| x < 3), Some(&2));
+    /// assert!(history.range_back(1..4).eq(&[3, 2, 1]));
+    /// ```
+    #[inline]
+    pub fn range_back<R>(&self, range: R) -> Rev<Iter<'_, A::Item>>
+    where
+        R: RangeArgument<usize>,
+    {
+        self.range(range).rev()
+    }
+
+    /// Returns the buffer index of the start of `range` and its length.
+    #[inline]
+    fn range_bounds<R>(&self, range: R) -> (usize, usize)
+    where
+        R: RangeArgument<usize>,
+    {
+        let len = self.len();
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(len);
+        assert!(start <= end, "range lower bound was too large");
+        assert!(end <= len, "range upper bound was too large");
+        (Self::wrap_add(self.tail(), start), end - start)
+    }
+
+    /// Returns a back-to-front iterator, starting from the newest element
+    /// pushed to the back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// let expected = vec![2, 1, 0];
+    ///
+    /// assert!(buf.iter_back().eq(expected.iter()));
+    /// assert_eq!(buf.iter_back().position(|&x| x == 0), Some(2));
+    /// ```
+    #[inline]
+    pub fn iter_back(&self) -> Rev<Iter<'_, A::Item>> {
+        self.iter().rev()
+    }
+
+    /// Returns an iterator over the elements in the order they are stored in
+    /// the buffer, which only matches their order in the deque if it is
+    /// normalized. Otherwise, the order is unspecified.
+    ///
+    /// The iterator walks plain slices, with no wrap-around arithmetic, which
+    /// makes it the fastest way to visit every element when the order does not
+    /// matter, as in a sum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(2);
+    /// buf.push_front(1);
+    ///
+    /// assert_eq!(buf.iter_unordered().sum::<i32>(), 3);
+    /// ```
+    #[inline]
+    pub fn iter_unordered(&self) -> Chain<slice::Iter<'_, A::Item>, slice::Iter<'_, A::Item>> {
+        let (front, back) = self.as_slices();
+        back.iter().chain(front.iter())
+    }
+
+    /// Returns a front-to-back iterator over the contiguous slices of the
+    /// deque: the front slice of `as_slices`, followed by the back slice if
+    /// it is not empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{IoSlice, Write};
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(b"cd".iter().cloned());
+    /// buf.push_front(b'b');
+    ///
+    /// let slices: Vec<_> = buf.slices_iter().map(IoSlice::new).collect();
+    /// assert_eq!(slices.len(), 2);
+    ///
+    /// let mut out = Vec::new();
+    /// out.write_vectored(&slices).unwrap();
+    /// assert_eq!(out, b"bcd");
+    /// ```
+    #[inline]
+    pub fn slices_iter(&self) -> Slices<'_, A::Item> {
+        let (front, back) = self.as_slices();
+        let back = if back.is_empty() { None } else { Some(back) };
+        Some(front).into_iter().chain(back)
+    }
+
+    /// Returns a front-to-back iterator over the contiguous mutable slices of
+    /// the deque: the front slice of `as_mut_slices`, followed by the back
+    /// slice if it is not empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![1, 2, 3]);
+    ///
+    /// for slice in buf.slices_iter_mut() {
+    ///     slice.reverse();
+    /// }
+    /// assert_eq!(buf, vec![3, 2, 1].into());
+    /// ```
+    #[inline]
+    pub fn slices_iter_mut(&mut self) -> SlicesMut<'_, A::Item> {
+        let (front, back) = self.as_mut_slices();
+        let back = if back.is_empty() { None } else { Some(back) };
+        Some(front).into_iter().chain(back)
+    }
+
+    /// Returns a front-to-back iterator over every `k`-th element, starting
+    /// with the front element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<[_; 8]> = (0..8).collect();
+    ///
+    /// assert!(buf.iter_step(3).eq(&[0, 3, 6]));
+    /// ```
+    #[inline]
+    pub fn iter_step(&self, k: usize) -> IterStep<'_, A::Item> {
+        IterStep::new(self.iter(), k)
+    }
+
+    /// Returns a front-to-back iterator over every `k`-th element, aligned so
+    /// that the back element is always the last one yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<[_; 8]> = (0..8).collect();
+    ///
+    /// assert!(buf.latest_every(3).eq(&[1, 4, 7]));
+    /// ```
+    #[inline]
+    pub fn latest_every(&self, k: usize) -> IterStep<'_, A::Item> {
+        let mut iter = self.iter();
+        if k != 0 && !self.is_empty() {
+            iter.advance((self.len() - 1) % k);
+        }
+        IterStep::new(iter, k)
+    }
+
+    /// Returns a front-to-back iterator over the elements of `a` and `b` in
+    /// pairs.
+    ///
+    /// Unlike `a.iter().zip(b.iter())`, the two deques are walked with a
+    /// single cursor over their contiguous slices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` and `b` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut a: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    /// a.push_back(2);
+    /// a.push_front(1);
+    /// let b: ArrayDeque<[_; 2]> = vec!['a', 'b'].into_iter().collect();
+    ///
+    /// let pairs: Vec<_> = ArrayDeque::zip(&a, &b).collect();
+    /// assert_eq!(pairs, vec![(&1, &'a'), (&2, &'b')]);
+    /// ```
+    #[inline]
+    pub fn zip<'a, A2, B2>(a: &'a Self, b: &'a ArrayDeque<A2, B2>) -> Zip<'a, A::Item, A2::Item>
+    where
+        A2: Array,
+        B2: Behavior,
+    {
+        Zip::new(a.as_slices(), b.as_slices())
+    }
+
+    /// Removes the first element and returns it, or `None` if the sequence is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.pop_front(), Some(1));
+    /// assert_eq!(buf.pop_front(), Some(2));
+    /// assert_eq!(buf.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<A::Item> {
+        let element = self.pop_front_untraced();
+        #[cfg(feature = "trace")]
+        {
+            if element.is_some() {
+                self.trace(trace::Op::PopFront);
+            }
+        }
+        element
+    }
+
+    /// Removes the last element from a buffer and returns it, or `None` if
+    /// it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    /// assert_eq!(buf.pop_back(), None);
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.pop_back(), Some(2));
+    /// assert_eq!(buf.pop_back(), Some(1));
+    /// ```
+    pub fn pop_back(&mut self) -> Option<A::Item> {
+        let element = self.pop_back_untraced();
+        #[cfg(feature = "trace")]
+        {
+            if element.is_some() {
+                self.trace(trace::Op::PopBack);
+            }
+        }
+        element
+    }
+
+    /// Add an element to the front of the deque, whatever the behavior.
+    ///
+    /// Return `None` if deque still has capacity, or `Some(existing)`
+    /// if the deque is full, where `existing` is the backmost element being kicked out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.push_front_overwrite(1), None);
+    /// assert_eq!(buf.push_front_overwrite(2), None);
+    /// assert_eq!(buf.push_front_overwrite(3), Some(1));
+    /// assert_eq!(buf, vec![3, 2].into());
+    /// ```
+    pub fn push_front_overwrite(&mut self, element: A::Item) -> Option<A::Item> {
+        if self.capacity() == 0 {
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Evict);
+            return Some(element);
+        }
+        let existing = if self.is_full() {
+            let existing = self.pop_back_untraced();
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Evict);
+            existing
+        } else {
+            None
+        };
+        unsafe { self.push_front_unchecked(element) }
+        #[cfg(feature = "trace")]
+        self.trace(trace::Op::PushFront);
+        existing
+    }
+
+    /// Appends an element to the back of the deque, whatever the behavior.
+    ///
+    /// Return `None` if deque still has capacity, or `Some(existing)`
+    /// if the deque is full, where `existing` is the frontmost element being kicked out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// // a sliding window over the last 3 samples
+    /// let mut window: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// let mut sum = 0;
+    ///
+    /// for sample in 1..6 {
+    ///     sum += sample;
+    ///     if let Some(displaced) = window.push_back_overwrite(sample) {
+    ///         sum -= displaced;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(window, vec![3, 4, 5].into());
+    /// assert_eq!(sum, 12);
+    /// ```
+    pub fn push_back_overwrite(&mut self, element: A::Item) -> Option<A::Item> {
+        if self.capacity() == 0 {
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Evict);
+            return Some(element);
+        }
+        let existing = if self.is_full() {
+            let existing = self.pop_front_untraced();
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Evict);
+            existing
+        } else {
+            None
+        };
+        unsafe { self.push_back_unchecked(element) }
+        #[cfg(feature = "trace")]
+        self.trace(trace::Op::PushBack);
+        existing
+    }
+
+    /// Appends the elements of an iterator to the back of the deque, whatever
+    /// the behavior, until it is full.
+    ///
+    /// Return `Ok(count)` with the number of elements appended if all of them
+    /// fit, or return `Err(CapacityError { *element* })` with the first
+    /// element that did not, leaving the rest of the iterator unconsumed.
+    /// Unlike `Extend`, an overflow is never silent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.collect_into(0..3), Ok(3));
+    ///
+    /// let mut rest = 3..6;
+    /// assert_eq!(buf.collect_into(&mut rest), Err(CapacityError { element: 4 }));
+    /// assert_eq!(buf, vec![0, 1, 2, 3].into());
+    /// assert_eq!(rest.next(), Some(5));
+    /// ```
+    pub fn collect_into<I>(&mut self, iter: I) -> Result<usize, CapacityError<A::Item>>
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        let mut count = 0;
+        for element in iter {
+            if self.is_full() {
+                #[cfg(feature = "trace")]
+                self.trace(trace::Op::Reject);
+                return Err(CapacityError { element });
+            }
+            unsafe { self.push_back_unchecked(element) }
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::PushBack);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Add an element to the front of the deque, whatever the behavior.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the deque is full, leaving it unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 2], Wrapping> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.try_push_front(1), Ok(()));
+    /// assert_eq!(buf.try_push_front(2), Ok(()));
+    /// assert_eq!(buf.try_push_front(3), Err(CapacityError { element: 3 }));
+    /// assert_eq!(buf, vec![2, 1].into());
+    /// ```
+    pub fn try_push_front(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        if self.is_full() {
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
+            return Err(CapacityError { element });
+        }
+        unsafe { self.push_front_unchecked(element) }
+        #[cfg(feature = "trace")]
+        self.trace(trace::Op::PushFront);
+        Ok(())
+    }
+
+    /// Appends an element to the back of the deque, whatever the behavior.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the deque is full, leaving it unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 2], Wrapping> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.try_push_back(1), Ok(()));
+    /// assert_eq!(buf.try_push_back(2), Ok(()));
+    /// assert_eq!(buf.try_push_back(3), Err(CapacityError { element: 3 }));
+    /// assert_eq!(buf, vec![1, 2].into());
+    /// ```
+    pub fn try_push_back(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        if self.is_full() {
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
+            return Err(CapacityError { element });
+        }
+        unsafe { self.push_back_unchecked(element) }
+        #[cfg(feature = "trace")]
+        self.trace(trace::Op::PushBack);
+        Ok(())
+    }
+
+    /// Inserts an element at `index` within the deque, whatever the behavior.
+    ///
+    /// Return `Ok(())` if the insertion succeeds, or return `Err(CapacityError { *element* })`
+    /// if the deque is full, leaving it unchanged.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than `ArrayDeque`'s length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 3], Wrapping> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(3);
+    ///
+    /// assert_eq!(buf.try_insert(1, 2), Ok(()));
+    /// assert_eq!(buf.try_insert(1, 4), Err(CapacityError { element: 4 }));
+    /// assert_eq!(buf, vec![1, 2, 3].into());
+    /// ```
+    pub fn try_insert(
+        &mut self,
+        index: usize,
+        element: A::Item,
+    ) -> Result<(), CapacityError<A::Item>> {
+        assert!(index <= self.len(), "index out of bounds");
+
+        if self.is_full() {
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
+            return Err(CapacityError { element });
+        }
+        unsafe { self.insert_unchecked(index, element) }
+        #[cfg(feature = "trace")]
+        self.trace(trace::Op::Insert(index));
+        Ok(())
+    }
+
+    /// Copies as many elements of `src` as fit to the back of the deque, with
+    /// at most two `memcpy`s, and returns their number.
+    fn copy_prefix_to_back(&mut self, src: &[A::Item]) -> usize
+    where
+        A::Item: Copy,
+    {
+        let n = cmp::min(src.len(), A::capacity() - self.len());
+        unsafe { self.copy_slice_to_back(&src[..n]) }
+        #[cfg(feature = "trace")]
+        self.trace_pushed_back(n);
+        n
+    }
+
+    /// Copies as many elements from the front of the deque as fit in `dst`,
+    /// with at most two `memcpy`s, and returns their number.
+    ///
+    /// The elements are not removed, see `discard_front`.
+    #[cfg(any(feature = "std", feature = "bytes"))]
+    fn copy_prefix_from_front(&self, dst: &mut [A::Item]) -> usize
+    where
+        A::Item: Copy,
+    {
+        let (a, b) = self.as_slices();
+        let first = cmp::min(dst.len(), a.len());
+        dst[..first].copy_from_slice(&a[..first]);
+        let second = cmp::min(dst.len() - first, b.len());
+        dst[first..first + second].copy_from_slice(&b[..second]);
+        first + second
+    }
+
+    /// Removes the first `n` elements, or every element if there are fewer,
+    /// without dropping them.
+    #[cfg(any(feature = "std", feature = "bytes"))]
+    fn discard_front(&mut self, n: usize)
+    where
+        A::Item: Copy,
+    {
+        let n = cmp::min(n, self.len());
+        let tail = Self::wrap_add(self.tail(), n);
+        let len = self.len() - n;
+        unsafe {
+            self.set_tail(tail);
+            self.set_len(len);
+        }
+    }
+
+    /// Clones all of `src` to the back of the deque, whatever the behavior.
+    ///
+    /// The elements are cloned straight into the free slots, rather than
+    /// pushed one at a time.
+    ///
+    /// Return `Ok(())` if every element fits, or return
+    /// `Err(CapacityError { *src* })` otherwise, leaving the deque unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.try_extend_from_slice(&[1, 2, 3]), Ok(()));
+    /// assert_eq!(
+    ///     buf.try_extend_from_slice(&[4, 5]),
+    ///     Err(CapacityError { element: &[4, 5][..] })
+    /// );
+    /// assert_eq!(buf, vec![1, 2, 3].into());
+    /// ```
+    pub fn try_extend_from_slice<'a>(
+        &mut self,
+        src: &'a [A::Item],
+    ) -> Result<(), CapacityError<&'a [A::Item]>>
+    where
+        A::Item: Clone,
+    {
+        if src.len() > A::capacity() - self.len() {
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
+            return Err(CapacityError { element: src });
+        }
+        unsafe { self.clone_slice_to_back(src) }
+        #[cfg(feature = "trace")]
+        self.trace_pushed_back(src.len());
+        Ok(())
+    }
+
+    /// Copies all of `src` to the back of the deque, whatever the behavior,
+    /// with at most two `memcpy`s.
+    ///
+    /// This is the fast path of `try_extend_from_slice` for `Copy` elements,
+    /// such as the bytes of a stream.
+    ///
+    /// Return `Ok(())` if every element fits, or return
+    /// `Err(CapacityError { *src* })` otherwise, leaving the deque unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.extend_from_copy_slice(b"abc"), Ok(()));
+    /// assert_eq!(
+    ///     buf.extend_from_copy_slice(b"de"),
+    ///     Err(CapacityError { element: &b"de"[..] })
+    /// );
+    /// assert_eq!(buf, b"abc".to_vec().into());
+    /// ```
+    pub fn extend_from_copy_slice<'a>(
+        &mut self,
+        src: &'a [A::Item],
+    ) -> Result<(), CapacityError<&'a [A::Item]>>
+    where
+        A::Item: Copy,
+    {
+        if src.len() > A::capacity() - self.len() {
+            #[cfg(feature = "trace")]
+            self.trace(trace::Op::Reject);
+            return Err(CapacityError { element: src });
+        }
+        self.copy_prefix_to_back(src);
+        Ok(())
+    }
+
+    /// Clears the deque and returns a writer that can push exactly `R`
+    /// elements, checked at compile time.
+    ///
+    /// **The elements already in the deque are dropped**, even if it is not
+    /// empty: the room for `R` elements is only known at compile time for an
+    /// empty deque. Fails to compile if `R` is larger than the capacity. See
+    /// `FixedWriter` for more.
+    ///
+    /// Requires Rust 1.63+.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut frame: ArrayDeque<[u8; 3]> = ArrayDeque::new();
+    /// frame.push_back(b'?').unwrap();
+    ///
+    /// frame.fixed_writer::<3>().push(b'O').push(b'K').push(b'\n');
+    /// assert_eq!(frame, b"OK\n".to_vec().into());
+    /// ```
+    #[cfg(has_const_generics)]
+    #[inline]
+    pub fn fixed_writer<const R: usize>(&mut self) -> FixedWriter<'_, A, B, R>
+    where
+        A: ConstCapacity,
+    {
+        FixedWriter::new(self)
+    }
+
+    /// Returns a front-to-back iterator over non-overlapping chunks of `K`
+    /// elements, copied into arrays.
+    ///
+    /// If fewer than `K` elements remain at the end, they are not yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<[_; 5]> = (0..5).collect();
+    ///
+    /// let chunks: Vec<[i32; 2]> = buf.iter_chunks::<2>().collect();
+    ///
+    /// assert_eq!(chunks, vec![[0, 1], [2, 3]]);
+    /// ```
+    #[cfg(has_const_generics)]
+    #[inline]
+    pub fn iter_chunks<const K: usize>(&self) -> Chunks<'_, A::Item, K>
+    where
+        A::Item: Copy,
+    {
+        Chunks::new(self.iter())
+    }
+
+    /// Returns a front-to-back iterator over non-overlapping chunks of `K`
+    /// elements, as arrays of references.
+    ///
+    /// If fewer than `K` elements remain at the end, they are not yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<[_; 4]> = vec![String::from("a"), String::from("b")].into();
+    ///
+    /// let [a, b] = buf.iter_chunk_refs::<2>().next().unwrap();
+    ///
+    /// assert_eq!((&a[..], &b[..]), ("a", "b"));
+    /// ```
+    #[cfg(has_const_generics)]
+    #[inline]
+    pub fn iter_chunk_refs<const K: usize>(&self) -> ChunkRefs<'_, A::Item, K> {
+        ChunkRefs::new(self.iter())
+    }
+
+    /// Returns an iterator that removes chunks of `K` elements from the front
+    /// and yields them as arrays, until fewer than `K` elements remain.
+    ///
+    /// The remaining elements, and any chunks not yet yielded when the
+    /// iterator is dropped, are left in the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 8]> = (0..7).collect();
+    ///
+    /// let batches: Vec<[i32; 3]> = buf.drain_chunks::<3>().collect();
+    ///
+    /// assert_eq!(batches, vec![[0, 1, 2], [3, 4, 5]]);
+    /// assert_eq!(buf, vec![6].into());
+    /// ```
+    #[cfg(has_const_generics)]
+    #[inline]
+    pub fn drain_chunks<const K: usize>(&mut self) -> DrainChunks<'_, A, B, K> {
+        DrainChunks::new(self)
+    }
+
+    /// Provides a guard over the front element, or `None` if the sequence is
+    /// empty.
+    ///
+    /// The element is only removed if `PeekGuard::commit` is called; dropping
+    /// the guard leaves it at the front. This makes "pop only after successful
+    /// handling" patterns panic-safe.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// if let Some(guard) = buf.peek_front_guard() {
+    ///     assert_eq!(*guard, 1);
+    ///     // handling failed, the element stays in place
+    /// }
+    /// assert_eq!(buf.front(), Some(&1));
+    ///
+    /// if let Some(guard) = buf.peek_front_guard() {
+    ///     assert_eq!(guard.commit(), 1);
+    /// }
+    /// assert_eq!(buf.front(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn peek_front_guard(&mut self) -> Option<PeekGuard<'_, A, B>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekGuard::new(self))
+        }
+    }
+
+    /// Clears the buffer, removing all values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 1]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.clear();
+    ///
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.drain(..);
+    }
+
+    /// Replaces the contents of the buffer with the elements of an iterator.
+    ///
+    /// Existing elements are overwritten in place, and only the surplus
+    /// ones are dropped, so the buffer keeps its slots instead of being
+    /// emptied and refilled.
+    ///
+    /// Does not extract more items than there is space for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![1, 2, 3]);
+    /// buf.clear_and_extend(vec![4, 5]);
+    ///
+    /// assert_eq!(buf, vec![4, 5].into());
+    /// ```
+    pub fn clear_and_extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        let mut iter = iter.into_iter();
+        let mut assigned = 0;
+        for slot in self.iter_mut() {
+            match iter.next() {
+                Some(element) => *slot = element,
+                None => break,
+            }
+            assigned += 1;
+        }
+        self.drain(assigned..);
+        let room = A::capacity() - self.len();
+        for element in iter.take(room) {
+            unsafe { self.push_back_unchecked(element) }
+        }
+    }
+
+    /// Replaces the contents of the buffer with clones of the elements of an
+    /// iterator.
+    ///
+    /// Existing elements are overwritten with `clone_from`, which lets them
+    /// reuse their own resources, such as the allocation of a `String`.
+    /// Only the surplus ones are dropped.
+    ///
+    /// Does not extract more items than there is space for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[String; 3]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![String::from("a")]);
+    /// buf.assign(&[String::from("b"), String::from("c")]);
+    ///
+    /// assert_eq!(buf, vec![String::from("b"), String::from("c")].into());
+    /// ```
+    pub fn assign<'a, I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a A::Item>,
+        A::Item: Clone + 'a,
+    {
+        let mut iter = iter.into_iter();
+        let mut assigned = 0;
+        for slot in self.iter_mut() {
+            match iter.next() {
+                Some(element) => slot.clone_from(element),
+                None => break,
+            }
+            assigned += 1;
+        }
+        self.drain(assigned..);
+        let room = A::capacity() - self.len();
+        for element in iter.take(room) {
+            unsafe { self.push_back_unchecked(element.clone()) }
+        }
+    }
+
+    /// Clears the buffer, then overwrites its whole backing storage with
+    /// zeroes using volatile writes, which the compiler cannot elide.
+    ///
+    /// This is meant for wiping sensitive data such as credentials. Only the
+    /// storage of the deque itself is wiped: copies of elements moved out
+    /// earlier, or heap memory owned by the elements, are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[u8; 8]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(b"hunter2".iter().cloned());
+    /// buf.clear_volatile();
+    ///
+    /// assert!(buf.is_empty());
+    /// ```
+    pub fn clear_volatile(&mut self) {
+        self.clear();
+        let bytes = self.ptr_mut() as *mut u8;
+        for i in 0..mem::size_of::<A>() {
+            unsafe { ptr::write_volatile(bytes.add(i), 0) };
+        }
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+        unsafe { self.set_tail(0) };
+    }
+
+    /// Create a draining iterator that removes the specified range in the
+    /// `ArrayDeque` and yields the removed items.
+    ///
+    /// The items are yielded front to back, in the order `iter` visits them,
+    /// however the range wraps around the buffer. `Drain` is double-ended:
+    /// yielding from the back visits them newest first, see `drain_back`.
+    ///
+    /// Note 1: The element range is removed even if the iterator is not
+    /// consumed until the end.
+    ///
+    /// Note 2: It is unspecified how many elements are removed from the deque,
+    /// if the `Drain` value is not dropped, but the borrow it holds expires
+    /// (eg. due to mem::forget).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// {
+    ///     let drain = buf.drain(2..);
+    ///     assert!(vec![2].into_iter().eq(drain));
+    /// }
+    ///
+    /// {
+    ///     let iter = buf.iter();
+    ///     assert!(vec![0, 1].iter().eq(iter));
+    /// }
+    ///
+    /// // A full range clears all contents
+    /// buf.drain(..);
+    /// assert!(buf.is_empty());
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, A, B>
+    where
+        R: RangeArgument<usize>,
+    {
+        let len = self.len();
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(len);
+        assert!(start <= end, "drain lower bound was too large");
+        assert!(end <= len, "drain upper bound was too large");
+
+        let drain_tail = Self::wrap_add(self.tail(), start);
+        let drain_head = Self::wrap_add(self.tail(), end);
+        let drain_len = end - start;
+
+        unsafe { self.set_len(start) }
+        #[cfg(feature = "trace")]
+        trace::emit(trace::Op::Drain(drain_len), len - drain_len, A::capacity());
+
+        Drain {
+            after_tail: drain_head,
+            after_len: len - end,
+            tail: drain_tail,
+            len: drain_len,
+            deque: self as *mut _,
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// Create a draining iterator that removes the specified range in the
+    /// `ArrayDeque` and yields the removed items back to front, newest first.
+    ///
+    /// This is `drain(range).rev()`, and the same notes apply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(0..4);
+    ///
+    /// assert!(buf.drain_back(1..).eq(vec![3, 2, 1]));
+    /// assert_eq!(buf, vec![0].into());
+    /// ```
+    #[inline]
+    pub fn drain_back<R>(&mut self, range: R) -> Rev<Drain<'_, A, B>>
+    where
+        R: RangeArgument<usize>,
+    {
+        self.drain(range).rev()
+    }
+
+    /// Returns a future that extends the back of the deque with the contents
+    /// of an iterator, `chunk` elements per poll, yielding to the executor in
+    /// between.
+    ///
+    /// The future resolves to the number of elements appended once the
+    /// iterator runs dry or the deque is full, whatever the behavior. See
+    /// `ExtendYielding` for more.
+    ///
+    /// Requires the `futures` feature and Rust 1.36+.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// # use std::future::Future;
+    /// # use std::pin::Pin;
+    /// # use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    /// # fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+    /// #     fn clone(_: *const ()) -> RawWaker { RawWaker::new(std::ptr::null(), &VTABLE) }
+    /// #     fn noop(_: *const ()) {}
+    /// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    /// #     let waker = unsafe { Waker::from_raw(clone(std::ptr::null())) };
+    /// #     let mut cx = Context::from_waker(&waker);
+    /// #     loop {
+    /// #         if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// let mut buf: ArrayDeque<[u32; 1 << 16]> = ArrayDeque::new();
+    ///
+    /// // from an async fn: `buf.extend_yielding(0..100_000, 4096).await`
+    /// assert_eq!(block_on(buf.extend_yielding(0..100_000, 4096)), 1 << 16);
+    /// assert!(buf.is_full());
+    /// ```
+    #[cfg(feature = "futures")]
+    #[inline]
+    pub fn extend_yielding<I>(
+        &mut self,
+        iter: I,
+        chunk: usize,
+    ) -> ExtendYielding<'_, A, B, I::IntoIter>
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        ExtendYielding::new(self, iter.into_iter(), chunk)
+    }
+
+    /// Returns a future that pops every element from the front of the deque
+    /// and hands it to `f`, `chunk` elements per poll, yielding to the
+    /// executor in between.
+    ///
+    /// The future resolves to the number of elements drained. See
+    /// `DrainYielding` for more.
+    ///
+    /// Requires the `futures` feature and Rust 1.36+.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// # use std::future::Future;
+    /// # use std::pin::Pin;
+    /// # use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    /// # fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+    /// #     fn clone(_: *const ()) -> RawWaker { RawWaker::new(std::ptr::null(), &VTABLE) }
+    /// #     fn noop(_: *const ()) {}
+    /// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    /// #     let waker = unsafe { Waker::from_raw(clone(std::ptr::null())) };
+    /// #     let mut cx = Context::from_waker(&waker);
+    /// #     loop {
+    /// #         if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// let mut buf: ArrayDeque<[u32; 1 << 16]> = ArrayDeque::new();
+    /// buf.extend_back(0..1000);
+    ///
+    /// let mut sum = 0;
+    /// assert_eq!(block_on(buf.drain_yielding(256, |x| sum += x)), 1000);
+    /// assert_eq!(sum, 499_500);
+    /// assert!(buf.is_empty());
+    /// ```
+    #[cfg(feature = "futures")]
+    #[inline]
+    pub fn drain_yielding<F>(&mut self, chunk: usize, f: F) -> DrainYielding<'_, A, B, F>
+    where
+        F: FnMut(A::Item),
+    {
+        DrainYielding::new(self, chunk, f)
+    }
+
+    /// Removes everything up to and including the first element equal to
+    /// `delimiter`, returning a draining iterator over the removed elements,
+    /// or `None` if no element equals `delimiter`.
+    ///
+    /// This is the primitive for line or record framing: the deque is left
+    /// untouched until a whole record has arrived.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[u8; 16]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(b"ab\ncd".iter().cloned());
+    ///
+    /// let line: ArrayDeque<[u8; 4]> = buf.pop_front_until(&b'\n').unwrap().collect();
+    ///
+    /// assert_eq!(line, b"ab\n".to_vec().into());
+    /// assert!(buf.pop_front_until(&b'\n').is_none());
+    /// assert_eq!(buf, b"cd".to_vec().into());
+    /// ```
+    pub fn pop_front_until(&mut self, delimiter: &A::Item) -> Option<Drain<'_, A, B>>
+    where
+        A::Item: PartialEq,
+    {
+        let index = self.iter().position(|x| x == delimiter)?;
+        Some(self.drain(..index + 1))
+    }
+
+    /// Swaps elements at indices `i` and `j`.
+    ///
+    /// `i` and `j` may be equal.
+    ///
+    /// Fails if there is no element with either index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// buf.swap(0, 2);
+    ///
+    /// assert_eq!(buf, vec![2, 1, 0].into());
+    /// ```
+    #[inline]
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len());
+        assert!(j < self.len());
+        let ri = Self::wrap_add(self.tail(), i);
+        let rj = Self::wrap_add(self.tail(), j);
+        let ptr = self.ptr_mut();
+        unsafe { ptr::swap(ptr.add(ri), ptr.add(rj)) }
+    }
+
+    /// Removes an element from anywhere in the `ArrayDeque` and returns it, replacing it with the
+    /// last element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// assert_eq!(buf.swap_remove_back(0), None);
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.swap_remove_back(0), Some(0));
+    /// assert_eq!(buf, vec![2, 1].into());
+    /// ```
+    pub fn swap_remove_back(&mut self, index: usize) -> Option<A::Item> {
+        let length = self.len();
+        if length > 0 && index < length - 1 {
+            self.swap(index, length - 1);
+        } else if index >= length {
+            return None;
+        }
+        self.pop_back()
+    }
+
+    /// Removes an element from anywhere in the `ArrayDeque` and returns it,
+    /// replacing it with the first element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// assert_eq!(buf.swap_remove_front(0), None);
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.swap_remove_front(2), Some(2));
+    /// assert_eq!(buf, vec![1, 0].into());
+    /// ```
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<A::Item> {
+        let length = self.len();
+        if length > 0 && index < length && index != 0 {
+            self.swap(index, 0);
+        } else if index >= length {
+            return None;
+        }
+        self.pop_front()
+    }
+
+    /// Removes and returns the element at `index` from the `ArrayDeque`.
+    /// Whichever end is closer to the removal point will be moved to make
+    /// room, and all the affected elements will be moved to new positions.
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.remove(1), Some(1));
+    /// assert_eq!(buf, vec![0, 2].into());
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Option<A::Item> {
+        if self.is_empty() || self.len() <= index {
+            return None;
+        }
+
+        // There are three main cases:
+        //  Elements are contiguous
+        //  Elements are discontiguous and the removal is in the tail section
+        //  Elements are discontiguous and the removal is in the head section
+        //      - special case when elements are technically contiguous,
+        //        but self.head = 0
+        //
+        // For each of those there are two more cases:
+        //  Insert is closer to tail
+        //  Insert is closer to head
+        //
+        // Key: H - self.head
+        //      T - self.tail
+        //      o - Valid element
+        //      x - Element marked for removal
+        //      R - Indicates element that is being removed
+        //      M - Indicates element was moved
+
+        let idx = Self::wrap_add(self.tail(), index);
+
+        let elem = unsafe { Some(self.buffer_read(idx)) };
+
+        let distance_to_tail = index;
+        let distance_to_head = self.len() - index;
+
+        let contiguous = self.is_contiguous();
+
+        match (
+            contiguous,
+            distance_to_tail <= distance_to_head,
+            idx >= self.tail(),
+        ) {
+            (true, true, _) => {
+                unsafe {
+                    // contiguous, remove closer to tail:
+                    //
+                    //             T   R         H
+                    //      [. . . o o x o o o o . . . . . .]
+                    //
+                    //               T           H
+                    //      [. . . . o o o o o o . . . . . .]
+                    //               M M
+
+                    let tail = self.tail();
+                    self.copy(tail + 1, tail, index);
+                    self.set_tail_forward();
+                }
+            }
+            (true, false, _) => {
+                unsafe {
+                    // contiguous, remove closer to head:
+                    //
+                    //             T       R     H
+                    //      [. . . o o o o x o o . . . . . .]
+                    //
+                    //             T           H
+                    //      [. . . o o o o o o . . . . . . .]
+                    //                     M M
+
+                    let head = self.head();
+                    self.copy(idx, idx + 1, head - idx - 1);
+                    self.set_head_backward();
+                }
+            }
+            (false, true, true) => {
+                unsafe {
+                    // discontiguous, remove closer to tail, tail section:
+                    //
+                    //                   H         T   R
+                    //      [o o o o o o . . . . . o o x o o]
+                    //
+                    //                   H           T
+                    //      [o o o o o o . . . . . . o o o o]
+                    //                               M M
+
+                    let tail = self.tail();
+                    self.copy(tail + 1, tail, index);
+                    self.set_tail_forward();
+                }
+            }
+            (false, false, false) => {
+                unsafe {
+                    // discontiguous, remove closer to head, head section:
+                    //
+                    //               R     H           T
+                    //      [o o o o x o o . . . . . . o o o]
+                    //
+                    //                   H             T
+                    //      [o o o o o o . . . . . . . o o o]
+                    //               M M
+
+                    let head = self.head();
+                    self.copy(idx, idx + 1, head - idx - 1);
+                    self.set_head_backward();
+                }
+            }
+            (false, false, true) => {
+                unsafe {
+                    // discontiguous, remove closer to head, tail section:
+                    //
+                    //             H           T         R
+                    //      [o o o . . . . . . o o o o o x o]
+                    //
+                    //           H             T
+                    //      [o o . . . . . . . o o o o o o o]
+                    //       M M                         M M
+                    //
+                    // or quasi-discontiguous, remove next to head, tail section:
+                    //
+                    //       H                 T         R
+                    //      [. . . . . . . . . o o o o o x o]
+                    //
+                    //                         T           H
+                    //      [. . . . . . . . . o o o o o o .]
+                    //                                   M
+
+                    // draw in elements in the tail section
+                    self.copy(idx, idx + 1, A::capacity() - idx - 1);
+
+                    // Prevents underflow.
+                    if self.head() != 0 {
+                        // copy first element into empty spot
+                        self.copy(A::capacity() - 1, 0, 1);
 
                         // move elements in the head section backwards
                         let head = self.head();
                         self.copy(0, 1, head - 1);
                     }
 
-                    self.set_head_backward();
+                    self.set_head_backward();
+                }
+            }
+            (false, true, false) => {
+                unsafe {
+                    // discontiguous, remove closer to tail, head section:
+                    //
+                    //           R               H     T
+                    //      [o o x o o o o o o o . . . o o o]
+                    //
+                    //                           H       T
+                    //      [o o o o o o o o o o . . . . o o]
+                    //       M M M                       M M
+
+                    let tail = self.tail();
+                    // draw in elements up to idx
+                    self.copy(1, 0, idx);
+
+                    // copy last element into empty spot
+                    self.copy(0, A::capacity() - 1, 1);
+
+                    // move elements from tail to end forward, excluding the last one
+                    self.copy(tail + 1, tail, A::capacity() - tail - 1);
+
+                    self.set_tail_forward();
+                }
+            }
+        }
+
+        elem
+    }
+
+    /// Splits the collection into two at the given index.
+    ///
+    /// Returns a newly allocated `Self`. `self` contains elements `[0, at)`,
+    /// and the returned `Self` contains elements `[at, len)`.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// // buf = [0], buf2 = [1, 2]
+    /// let buf2 = buf.split_off(1);
+    ///
+    /// assert_eq!(buf.len(), 1);
+    /// assert_eq!(buf2.len(), 2);
+    /// ```
+    #[inline]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(at <= len, "`at` out of bounds");
+
+        let other_len = len - at;
+        let mut other = Self::new();
+
+        unsafe {
+            let (first_half, second_half) = self.as_slices();
+
+            let first_len = first_half.len();
+            let second_len = second_half.len();
+            if at < first_len {
+                // `at` lies in the first half.
+                let amount_in_first = first_len - at;
+
+                ptr::copy_nonoverlapping(
+                    first_half.as_ptr().add(at),
+                    other.ptr_mut(),
+                    amount_in_first,
+                );
+
+                // just take all of the second half.
+                ptr::copy_nonoverlapping(
+                    second_half.as_ptr(),
+                    other.ptr_mut().add(amount_in_first),
+                    second_len,
+                );
+            } else {
+                // `at` lies in the second half, need to factor in the elements we skipped
+                // in the first half.
+                let offset = at - first_len;
+                let amount_in_second = second_len - offset;
+                ptr::copy_nonoverlapping(
+                    second_half.as_ptr().add(offset),
+                    other.ptr_mut(),
+                    amount_in_second,
+                );
+            }
+        }
+
+        // Cleanup where the ends of the buffers are
+        unsafe {
+            self.set_len(at);
+            other.set_len(other_len);
+        }
+
+        other
+    }
+
+    /// Returns a new deque holding clones of the elements in the given range.
+    ///
+    /// Each contiguous region of the range is cloned in bulk. The result has
+    /// the same capacity, so no element is ever lost.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![1, 2, 3, 4]);
+    ///
+    /// assert_eq!(buf.clone_range(1..3), vec![2, 3].into());
+    /// assert_eq!(buf.clone_range(..), buf);
+    /// ```
+    pub fn clone_range<R>(&self, range: R) -> Self
+    where
+        R: RangeArgument<usize>,
+        A::Item: Clone,
+    {
+        let len = self.len();
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(len);
+        assert!(start <= end, "clone_range lower bound was too large");
+        assert!(end <= len, "clone_range upper bound was too large");
+
+        let mut other = Self::new();
+        if let Some((a, b)) = self.get_range(start..end) {
+            for x in a.iter().chain(b) {
+                unsafe {
+                    other.push_back_unchecked(x.clone());
+                }
+            }
+        }
+        other
+    }
+
+    /// Returns a new deque holding clones of the first `n` elements, or of
+    /// all of them if there are fewer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(buf.clone_front(2), vec![1, 2].into());
+    /// assert_eq!(buf.clone_front(5), buf);
+    /// ```
+    #[inline]
+    pub fn clone_front(&self, n: usize) -> Self
+    where
+        A::Item: Clone,
+    {
+        self.clone_range(..cmp::min(n, self.len()))
+    }
+
+    /// Returns a new deque holding clones of the last `n` elements, or of
+    /// all of them if there are fewer.
+    ///
+    /// A snapshot of the most recent window of a ring, for example.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4], Wrapping> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(0..6);
+    ///
+    /// assert_eq!(buf.clone_back(2), vec![4, 5].into());
+    /// assert_eq!(buf.clone_back(0), ArrayDeque::new());
+    /// ```
+    #[inline]
+    pub fn clone_back(&self, n: usize) -> Self
+    where
+        A::Item: Clone,
+    {
+        self.clone_range(self.len().saturating_sub(n)..)
+    }
+
+    /// Resizes the deque in place so that its length is `new_len`, capped
+    /// at the capacity.
+    ///
+    /// If `new_len` is greater than the length, the deque is extended at the
+    /// back with clones of `value`, up to the capacity. Otherwise the deque is
+    /// truncated, dropping the elements at the back. Use `try_resize` to be
+    /// told when `new_len` does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(5);
+    /// buf.resize(3, 0);
+    /// assert_eq!(buf, vec![5, 0, 0].into());
+    ///
+    /// buf.resize(10, 1);
+    /// assert_eq!(buf, vec![5, 0, 0, 1].into());
+    ///
+    /// buf.resize(1, 1);
+    /// assert_eq!(buf, vec![5].into());
+    /// ```
+    #[inline]
+    pub fn resize(&mut self, new_len: usize, value: A::Item)
+    where
+        A::Item: Clone,
+    {
+        self.resize_with(new_len, || value.clone());
+    }
+
+    /// Resizes the deque in place so that its length is `new_len`, capped
+    /// at the capacity.
+    ///
+    /// If `new_len` is greater than the length, the deque is extended at the
+    /// back with values returned by `f`, up to the capacity. Otherwise the
+    /// deque is truncated, dropping the elements at the back. Use
+    /// `try_resize_with` to be told when `new_len` does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// let mut next = 0;
+    /// buf.resize_with(3, || { next += 1; next });
+    /// assert_eq!(buf, vec![1, 2, 3].into());
+    /// ```
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F)
+    where
+        F: FnMut() -> A::Item,
+    {
+        let new_len = cmp::min(new_len, self.capacity());
+        let len = self.len();
+        if new_len <= len {
+            self.drain(new_len..);
+        } else {
+            for _ in len..new_len {
+                unsafe {
+                    self.push_back_unchecked(f());
+                }
+            }
+        }
+    }
+
+    /// Modifies the deque in-place so that `len()` is equal to `new_len`,
+    /// either by removing excess elements from the back or by appending clones
+    /// of `value` to the back.
+    ///
+    /// Return `Ok(())` if the resize succeeds, or return
+    /// `Err(CapacityError { *value* })`, leaving the deque unchanged, if
+    /// `new_len` is greater than the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    ///
+    /// assert_eq!(buf.try_resize(3, 0), Ok(()));
+    /// assert_eq!(buf, vec![1, 0, 0].into());
+    ///
+    /// assert_eq!(buf.try_resize(5, 0), Err(CapacityError { element: 0 }));
+    /// assert_eq!(buf.len(), 3);
+    ///
+    /// assert_eq!(buf.try_resize(1, 0), Ok(()));
+    /// assert_eq!(buf, vec![1].into());
+    /// ```
+    pub fn try_resize(
+        &mut self,
+        new_len: usize,
+        value: A::Item,
+    ) -> Result<(), CapacityError<A::Item>>
+    where
+        A::Item: Clone,
+    {
+        if new_len > self.capacity() {
+            return Err(CapacityError { element: value });
+        }
+        let len = self.len();
+        if new_len <= len {
+            self.drain(new_len..);
+        } else {
+            for _ in len + 1..new_len {
+                unsafe {
+                    self.push_back_unchecked(value.clone());
+                }
+            }
+            unsafe {
+                self.push_back_unchecked(value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Modifies the deque in-place so that `len()` is equal to `new_len`,
+    /// either by removing excess elements from the back or by appending
+    /// elements generated by calling `f` to the back.
+    ///
+    /// Return `Ok(())` if the resize succeeds, or return
+    /// `Err(CapacityError { () })`, leaving the deque unchanged and without
+    /// calling `f`, if `new_len` is greater than the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    /// let mut next = 0;
+    ///
+    /// assert_eq!(buf.try_resize_with(3, || { next += 1; next }), Ok(()));
+    /// assert_eq!(buf, vec![1, 2, 3].into());
+    ///
+    /// assert_eq!(buf.try_resize_with(5, Default::default), Err(CapacityError { element: () }));
+    /// ```
+    pub fn try_resize_with<F>(&mut self, new_len: usize, mut f: F) -> Result<(), CapacityError>
+    where
+        F: FnMut() -> A::Item,
+    {
+        if new_len > self.capacity() {
+            return Err(CapacityError { element: () });
+        }
+        let len = self.len();
+        if new_len <= len {
+            self.drain(new_len..);
+        } else {
+            for _ in len..new_len {
+                unsafe {
+                    self.push_back_unchecked(f());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Exchanges the contents of two deques of the same capacity, without
+    /// dropping or cloning any element.
+    ///
+    /// The deques may have different behaviors. To take the contents out of a
+    /// deque, leaving an empty one in its place, use `mem::take`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::mem;
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut front: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    /// let mut back: ArrayDeque<[_; 3], Wrapping> = ArrayDeque::new();
+    ///
+    /// front.push_back(1);
+    /// back.push_back(2);
+    /// back.push_back(3);
+    ///
+    /// front.swap_contents(&mut back);
+    ///
+    /// assert_eq!(front, vec![2, 3].into());
+    /// assert_eq!(back, vec![1].into());
+    ///
+    /// let taken = mem::take(&mut front);
+    ///
+    /// assert!(front.is_empty());
+    /// assert_eq!(taken, vec![2, 3].into());
+    /// ```
+    #[inline]
+    pub fn swap_contents<B2: Behavior>(&mut self, other: &mut ArrayDeque<A, B2>) {
+        mem::swap(&mut self.xs, &mut other.xs);
+        mem::swap(&mut self.tail, &mut other.tail);
+        mem::swap(&mut self.len, &mut other.len);
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` such that `f(&e)` returns false.
+    /// This method operates in place and preserves the order of the retained
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(0..4);
+    /// buf.retain(|&x| x % 2 == 0);
+    ///
+    /// assert_eq!(buf, vec![0, 2].into());
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&A::Item) -> bool,
+    {
+        self.retain_with_index(|_, x| f(x));
+    }
+
+    /// Retains only the elements specified by the predicate, which may also
+    /// modify them.
+    ///
+    /// In other words, remove all elements `e` such that `f(&mut e)` returns
+    /// false. This method operates in place, visits every element exactly
+    /// once in order, and preserves the order of the retained elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut in_flight: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// in_flight.extend_back(vec![3, 1, 2, 1]);
+    ///
+    /// // count down the remaining ticks, dropping expired requests
+    /// in_flight.retain_mut(|ticks| {
+    ///     *ticks -= 1;
+    ///     *ticks > 0
+    /// });
+    ///
+    /// assert_eq!(in_flight, vec![2, 1].into());
+    /// ```
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut A::Item) -> bool,
+    {
+        let len = self.len();
+        let mut del = 0;
+        for i in 0..len {
+            if !f(&mut self[i]) {
+                del += 1;
+            } else if del > 0 {
+                self.swap(i - del, i);
+            }
+        }
+        if del > 0 {
+            for _ in (len - del)..self.len() {
+                self.pop_back();
+            }
+        }
+    }
+
+    /// Retains only the elements specified by the predicate, which is also
+    /// given the index of each element.
+    ///
+    /// In other words, remove all elements `e` at index `i` such that
+    /// `f(i, &e)` returns false. Indices are those before any removal. This
+    /// method operates in place and preserves the order of the retained
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 6]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(10..16);
+    ///
+    /// // downsample, keeping every third sample
+    /// buf.retain_with_index(|i, _| i % 3 == 0);
+    ///
+    /// assert_eq!(buf, vec![10, 13].into());
+    /// ```
+    pub fn retain_with_index<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &A::Item) -> bool,
+    {
+        let len = self.len();
+        let mut del = 0;
+        for i in 0..len {
+            if !f(i, &self[i]) {
+                del += 1;
+            } else if del > 0 {
+                self.swap(i - del, i);
+            }
+        }
+        if del > 0 {
+            for _ in (len - del)..self.len() {
+                self.pop_back();
+            }
+        }
+    }
+
+    /// Retains only the elements specified by the predicate, returning an
+    /// iterator over the removed elements.
+    ///
+    /// In other words, remove and yield all elements `e` such that `f(&e)`
+    /// returns false, in a single pass. The retained elements keep their
+    /// order. Elements are visited lazily: if the iterator is dropped before
+    /// being fully consumed, the elements not yet visited are retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 6]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(0..6);
+    ///
+    /// let expired: Vec<_> = buf.retain_drain(|&x| x % 3 != 0).collect();
+    ///
+    /// assert_eq!(expired, vec![0, 3]);
+    /// assert_eq!(buf, vec![1, 2, 4, 5].into());
+    /// ```
+    pub fn retain_drain<F>(&mut self, f: F) -> RetainDrain<'_, A, B, F>
+    where
+        F: FnMut(&A::Item) -> bool,
+    {
+        RetainDrain::new(self, f)
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// `ArrayDeque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 7]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[0, 1][..], &[][..]));
+    ///
+    /// buf.push_front(2);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[2][..], &[0, 1][..]));
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&[A::Item], &[A::Item]) {
+        unsafe { self.slots(self.tail(), self.len()) }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// `ArrayDeque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 7]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    ///
+    /// assert_eq!(buf.as_mut_slices(), (&mut [0, 1][..], &mut[][..]));
+    ///
+    /// buf.push_front(2);
+    ///
+    /// assert_eq!(buf.as_mut_slices(), (&mut[2][..], &mut[0, 1][..]));
+    /// ```
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [A::Item], &mut [A::Item]) {
+        let tail = self.tail();
+        let len = self.len();
+        unsafe { self.slots_mut(tail, len) }
+    }
+
+    /// Returns `true` if the front element is stored at the start of the
+    /// buffer, so that the contents form one slice starting at offset 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// assert!(buf.is_normalized());
+    ///
+    /// buf.push_front(0);
+    /// assert!(!buf.is_normalized());
+    /// ```
+    #[inline]
+    pub fn is_normalized(&self) -> bool {
+        self.tail() == 0 || self.is_empty()
+    }
+
+    /// Moves the contents so that the front element is stored at the start of
+    /// the buffer, keeping their order, and returns them as one slice.
+    ///
+    /// This is what code that assumes element 0 lives at offset 0 of the
+    /// buffer, such as a C API, needs. Does nothing if the deque is already
+    /// normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(2);
+    /// buf.push_back(3);
+    /// buf.push_front(1);
+    ///
+    /// assert_eq!(buf.normalize(), &[1, 2, 3]);
+    /// assert!(buf.is_normalized());
+    /// assert_eq!(buf.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn normalize(&mut self) -> &mut [A::Item] {
+        let tail = self.tail();
+        let len = self.len();
+        if tail != 0 {
+            if tail + len <= A::capacity() {
+                unsafe { self.copy(0, tail, len) };
+            } else {
+                // move the front part down next to the back part, then swap
+                // the two parts, which are now both in `0..len`
+                let head = self.head();
+                let front_len = A::capacity() - tail;
+                unsafe { self.copy(head, tail, front_len) };
+                unsafe { slice::from_raw_parts_mut(self.ptr_mut(), len) }.rotate_left(head);
+            }
+            unsafe { self.set_tail(0) };
+        }
+        unsafe { slice::from_raw_parts_mut(self.ptr_mut(), len) }
+    }
+
+    /// Rotates the deque `n` places to the left, so that the element at
+    /// index `n` becomes the front.
+    ///
+    /// This moves `min(n, len - n)` elements, and none at all if the deque
+    /// is full, where only the front index changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 8]> = (0..5).collect();
+    ///
+    /// buf.rotate_left(2);
+    /// assert_eq!(buf, vec![2, 3, 4, 0, 1].into());
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len(), "rotation is larger than the length");
+        let k = self.len() - n;
+        unsafe {
+            if n <= k {
+                self.rotate_left_inner(n);
+            } else {
+                self.rotate_right_inner(k);
+            }
+        }
+    }
+
+    /// Rotates the deque `n` places to the right, so that the element at
+    /// index `len - n` becomes the front.
+    ///
+    /// This moves `min(n, len - n)` elements, and none at all if the deque
+    /// is full, where only the front index changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 8]> = (0..5).collect();
+    ///
+    /// buf.rotate_right(2);
+    /// assert_eq!(buf, vec![3, 4, 0, 1, 2].into());
+    /// ```
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len(), "rotation is larger than the length");
+        let k = self.len() - n;
+        unsafe {
+            if n <= k {
+                self.rotate_right_inner(n);
+            } else {
+                self.rotate_left_inner(k);
+            }
+        }
+    }
+
+    /// Copies the elements into `dest` as if the deque were rotated left by
+    /// `start` first, that is element `start` and the ones after it followed
+    /// by the ones before it, leaving the deque untouched.
+    ///
+    /// Every contiguous region is copied with one `memcpy`, so a snapshot of
+    /// a ring can be exported at an arbitrary phase, for example for an FFT.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length, or if `dest` does not
+    /// have the same length as the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![1, 2, 3, 4]);
+    ///
+    /// let mut frame = [0; 4];
+    /// buf.copy_rotated_into(&mut frame, 1);
+    /// assert_eq!(frame, [2, 3, 4, 1]);
+    /// ```
+    pub fn copy_rotated_into(&self, dest: &mut [A::Item], start: usize)
+    where
+        A::Item: Copy,
+    {
+        let len = self.len();
+        assert!(start <= len, "rotation is larger than the length");
+        assert_eq!(dest.len(), len, "destination and deque lengths differ");
+
+        let (dest_back, dest_front) = dest.split_at_mut(len - start);
+        let (a, b) = self.get_range(start..).unwrap();
+        dest_back[..a.len()].copy_from_slice(a);
+        dest_back[a.len()..].copy_from_slice(b);
+        let (a, b) = self.get_range(..start).unwrap();
+        dest_front[..a.len()].copy_from_slice(a);
+        dest_front[a.len()..].copy_from_slice(b);
+    }
+
+    /// Moves the first `n` elements to the back, `n <= len / 2`.
+    unsafe fn rotate_left_inner(&mut self, n: usize) {
+        debug_assert!(n * 2 <= self.len());
+        let head = self.head();
+        let tail = self.tail();
+        self.wrap_copy(head, tail, n);
+        self.set_tail(Self::wrap_add(tail, n));
+    }
+
+    /// Moves the last `n` elements to the front, `n <= len / 2`.
+    unsafe fn rotate_right_inner(&mut self, n: usize) {
+        debug_assert!(n * 2 <= self.len());
+        let head = self.head();
+        let tail = Self::wrap_sub(self.tail(), n);
+        self.set_tail(tail);
+        self.wrap_copy(tail, Self::wrap_sub(head, n), n);
+    }
+
+    /// Moves the contents so that they are stored contiguously, keeping their
+    /// order, and returns them as one slice.
+    ///
+    /// Unlike `normalize`, this does nothing if the contents do not wrap
+    /// around the end of the buffer, wherever they start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(2);
+    /// buf.push_back(1);
+    /// buf.push_front(3);
+    ///
+    /// buf.make_contiguous().sort();
+    /// assert_eq!(buf.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [A::Item] {
+        if self.tail() + self.len() <= A::capacity() {
+            self.as_mut_slices().0
+        } else {
+            self.normalize()
+        }
+    }
+
+    /// Returns a read-only `RingView` of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, RingView};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(2);
+    /// buf.push_front(1);
+    ///
+    /// let (a, b) = buf.as_slices();
+    /// assert_eq!(buf.view(), RingView::new(a, b));
+    /// assert_eq!(buf.view().binary_search(&2), Ok(1));
+    /// ```
+    #[inline]
+    pub fn view(&self) -> RingView<'_, A::Item> {
+        let (front, back) = self.as_slices();
+        RingView::new(front, back)
+    }
+
+    /// Binary searches the sorted deque for `x`, across both of its slices.
+    ///
+    /// Return `Ok(index)` of a matching element, or `Err(index)` where `x`
+    /// could be inserted while keeping the order. If several elements match,
+    /// any of them may be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 5]> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(vec![3, 5, 8]);
+    /// buf.push_front(1);
+    ///
+    /// assert_eq!(buf.binary_search(&5), Ok(2));
+    /// assert_eq!(buf.binary_search(&4), Err(2));
+    /// assert_eq!(buf.binary_search(&9), Err(4));
+    /// ```
+    #[inline]
+    pub fn binary_search(&self, x: &A::Item) -> Result<usize, usize>
+    where
+        A::Item: Ord,
+    {
+        self.view().binary_search(x)
+    }
+
+    /// Binary searches the sorted deque with a comparator function.
+    ///
+    /// The comparator returns the order of its argument relative to the
+    /// target. Return `Ok(index)` of a matching element, or `Err(index)`
+    /// where the target could be inserted while keeping the order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<[_; 4]> = vec![1, 2, 4, 8].into();
+    ///
+    /// assert_eq!(buf.binary_search_by(|x| x.cmp(&4)), Ok(2));
+    /// ```
+    #[inline]
+    pub fn binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&A::Item) -> Ordering,
+    {
+        self.view().binary_search_by(f)
+    }
+
+    /// Binary searches the deque, sorted by the key extracted by `f`, for
+    /// `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let events: ArrayDeque<[_; 4]> = vec![(10, 'a'), (20, 'b'), (30, 'c')].into();
+    ///
+    /// assert_eq!(events.binary_search_by_key(&20, |&(time, _)| time), Ok(1));
+    /// assert_eq!(events.binary_search_by_key(&25, |&(time, _)| time), Err(2));
+    /// ```
+    #[inline]
+    pub fn binary_search_by_key<K, F>(&self, key: &K, f: F) -> Result<usize, usize>
+    where
+        K: Ord,
+        F: FnMut(&A::Item) -> K,
+    {
+        self.view().binary_search_by_key(key, f)
+    }
+
+    /// Returns the index of the first element for which `pred` is false,
+    /// assuming that the deque is partitioned by `pred`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let timestamps: ArrayDeque<[_; 6]> = vec![100, 105, 110, 118, 120].into();
+    ///
+    /// // the samples older than 112
+    /// assert_eq!(timestamps.partition_point(|&t| t < 112), 3);
+    /// ```
+    #[inline]
+    pub fn partition_point<P>(&self, pred: P) -> usize
+    where
+        P: FnMut(&A::Item) -> bool,
+    {
+        self.view().partition_point(pred)
+    }
+
+    /// Returns a guard over the contiguous free slots following the back
+    /// element, for filling them in place, for example by DMA.
+    ///
+    /// The slots are reset to `Default::default()`. Call
+    /// `SpareRegion::commit_back` with the number of slots written to append
+    /// them to the deque. If the deque is empty, it is first rewound so that
+    /// the whole buffer is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    ///
+    /// let mut spare = buf.spare_capacity_mut();
+    /// assert_eq!(spare.len(), 4);
+    /// spare[..3].copy_from_slice(b"abc");
+    /// spare.commit_back(3).unwrap();
+    ///
+    /// let spare = buf.spare_capacity_mut();
+    /// assert_eq!(spare.commit_back(2), Err(CapacityError { element: 2 }));
+    ///
+    /// assert_eq!(buf, b"abc".to_vec().into());
+    /// ```
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> SpareRegion<'_, A, B>
+    where
+        A::Item: Copy + Default,
+    {
+        SpareRegion::new(self)
+    }
+
+    /// Returns a guard over the `n` free slots following the back element,
+    /// like `spare_capacity_mut`, or `Err(CapacityError { *n* })` if fewer
+    /// than `n` free slots are contiguous there.
+    ///
+    /// Reserving first guarantees the room for a trailer, for example,
+    /// before the rest of a message is buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    ///
+    /// let mut trailer = buf.reserve_back(2).unwrap();
+    /// trailer.copy_from_slice(b"\r\n");
+    /// trailer.commit_back(2).unwrap();
+    ///
+    /// assert_eq!(buf.reserve_back(3).unwrap_err(), CapacityError { element: 3 });
+    /// assert_eq!(buf, b"\r\n".to_vec().into());
+    /// ```
+    #[inline]
+    pub fn reserve_back(&mut self, n: usize) -> Result<SpareRegion<'_, A, B>, CapacityError<usize>>
+    where
+        A::Item: Copy + Default,
+    {
+        SpareRegion::reserve(self, n)
+    }
+
+    /// Returns a guard over the `n` free slots preceding the front element,
+    /// or `Err(CapacityError { *n* })` if fewer than `n` free slots are
+    /// contiguous there.
+    ///
+    /// The slots are reset to `Default::default()`. Call
+    /// `SpareFrontRegion::commit_front` with the number of slots written, at
+    /// the end of the region, to prepend them to the deque. If the deque is
+    /// empty, it is first rewound so that the whole buffer is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[u8; 6]> = ArrayDeque::new();
+    /// buf.extend_back(b"data".iter().cloned());
+    ///
+    /// let mut header = buf.reserve_front(2).unwrap();
+    /// header.copy_from_slice(b"h:");
+    /// header.commit_front(2).unwrap();
+    ///
+    /// assert_eq!(buf, b"h:data".to_vec().into());
+    /// ```
+    #[inline]
+    pub fn reserve_front(
+        &mut self,
+        n: usize,
+    ) -> Result<SpareFrontRegion<'_, A, B>, CapacityError<usize>>
+    where
+        A::Item: Copy + Default,
+    {
+        SpareFrontRegion::reserve(self, n)
+    }
+
+    /// Returns a guard over the contiguous elements starting at the front,
+    /// that is the first slice of `as_slices`, for reading them out in place.
+    ///
+    /// Call `FilledRegion::commit_front` with the number of elements read to
+    /// remove them from the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = vec![1, 2, 3].into();
+    ///
+    /// let filled = buf.filled_region();
+    /// assert_eq!(&*filled, &[1, 2, 3]);
+    /// filled.commit_front(2).unwrap();
+    ///
+    /// let filled = buf.filled_region();
+    /// assert_eq!(filled.commit_front(2), Err(CapacityError { element: 2 }));
+    ///
+    /// assert_eq!(buf, vec![3].into());
+    /// ```
+    #[inline]
+    pub fn filled_region(&mut self) -> FilledRegion<'_, A, B> {
+        FilledRegion::new(self)
+    }
+
+    /// Converts the deque into a `VecDeque` with room for at least `extra`
+    /// more elements, for when the fixed capacity proves too small.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// let mut growable = buf.into_vecdeque_with_capacity(8);
+    /// growable.push_back(3);
+    ///
+    /// assert!(growable.capacity() >= 10);
+    /// assert_eq!(growable, vec![1, 2, 3]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn into_vecdeque_with_capacity(self, extra: usize) -> VecDeque<A::Item> {
+        self.move_into_vec(extra).into()
+    }
+
+    /// Moves the elements into a new `Vec` with room for `extra` more, with
+    /// at most two `memcpy`s.
+    #[cfg(feature = "std")]
+    fn move_into_vec(mut self, extra: usize) -> Vec<A::Item> {
+        let mut vec = Vec::with_capacity(self.len() + extra);
+        unsafe {
+            {
+                let (a, b) = self.as_slices();
+                ptr::copy_nonoverlapping(a.as_ptr(), vec.as_mut_ptr(), a.len());
+                ptr::copy_nonoverlapping(b.as_ptr(), vec.as_mut_ptr().add(a.len()), b.len());
+            }
+            vec.set_len(self.len());
+            // the elements now belong to `vec`
+            self.set_len(0);
+        }
+        vec
+    }
+
+    /// Moves every element to the back of `other`, leaving the deque empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::VecDeque;
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 2]> = ArrayDeque::new();
+    /// let mut overflow = VecDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    /// if let Err(err) = buf.push_back(3) {
+    ///     buf.spill_into(&mut overflow);
+    ///     overflow.push_back(err.element);
+    /// }
+    ///
+    /// assert!(buf.is_empty());
+    /// assert_eq!(overflow, vec![1, 2, 3]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn spill_into(&mut self, other: &mut VecDeque<A::Item>) {
+        other.extend(self.drain(..));
+    }
+}
+
+impl<A: Array + CapacityStorage, B: Behavior> CapacityStorage for ArrayDeque<A, B> {
+    const CAPACITY: usize = A::CAPACITY;
+}
+
+impl<A: Array, H: Hooks<A::Item>> From<ArrayDeque<A, Wrapping<H>>>
+    for ArrayDeque<A, Saturating<H>>
+{
+    fn from(buf: ArrayDeque<A, Wrapping<H>>) -> Self {
+        buf.into_iter().collect()
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> From<ArrayDeque<A, Saturating<H>>>
+    for ArrayDeque<A, Wrapping<H>>
+{
+    fn from(buf: ArrayDeque<A, Saturating<H>>) -> Self {
+        buf.into_iter().collect()
+    }
+}
+
+#[cfg(has_const_generics)]
+impl<A: Array, B: Behavior> From<A> for ArrayDeque<A, B> {
+    /// Creates a full deque holding the elements of `xs`, in order, moving
+    /// the whole array in at once.
+    #[inline]
+    fn from(xs: A) -> Self {
+        ArrayDeque {
+            xs: MaybeUninit::from_array(xs),
+            tail: <A::Index as ArrayIndex>::ZERO,
+            len: <A::Index as ArrayIndex>::from(A::capacity()),
+            marker: marker::PhantomData,
+        }
+    }
+}
+
+/// Creates a deque holding clones of the elements of `slice`.
+///
+/// Fails with `CapacityError { *slice* }` if the slice is longer than the
+/// capacity, whatever the behavior.
+///
+/// Requires Rust 1.34+.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use arraydeque::{ArrayDeque, CapacityError, Wrapping};
+///
+/// let buf = ArrayDeque::<[_; 3]>::try_from(&[1, 2][..]).unwrap();
+/// assert_eq!(buf, vec![1, 2].into());
+///
+/// let long = ArrayDeque::<[_; 3], Wrapping>::try_from(&[1, 2, 3, 4][..]);
+/// assert_eq!(long.unwrap_err(), CapacityError { element: &[1, 2, 3, 4][..] });
+/// ```
+#[cfg(has_try_from)]
+impl<'a, A: Array, B: Behavior> TryFrom<&'a [A::Item]> for ArrayDeque<A, B>
+where
+    A::Item: Clone,
+{
+    type Error = CapacityError<&'a [A::Item]>;
+
+    fn try_from(slice: &'a [A::Item]) -> Result<Self, Self::Error> {
+        let mut deque = ArrayDeque::new();
+        deque.try_extend_from_slice(slice)?;
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: Array, B: Behavior> From<Vec<A::Item>> for ArrayDeque<A, B>
+where
+    Self: FromIterator<A::Item>,
+{
+    /// Creates a deque holding the elements of `vec`, collected with the
+    /// overflow rules of the behavior: a `Saturating` deque keeps the first
+    /// elements and a `Wrapping` one the last.
+    ///
+    /// Use `try_from_vec` to fail instead when `vec` is longer than the
+    /// capacity. There is no `TryFrom<Vec>` impl, since this `From` impl
+    /// already implies an infallible one.
+    fn from(vec: Vec<A::Item>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+#[cfg(all(feature = "std", has_from_foreign))]
+impl<A: Array, B: Behavior> From<ArrayDeque<A, B>> for Vec<A::Item> {
+    /// Moves the elements into a new `Vec`, front first, with at most two
+    /// `memcpy`s.
+    #[inline]
+    fn from(deque: ArrayDeque<A, B>) -> Self {
+        deque.move_into_vec(0)
+    }
+}
+
+#[cfg(all(feature = "std", has_from_foreign))]
+impl<A: Array, B: Behavior> From<ArrayDeque<A, B>> for VecDeque<A::Item> {
+    /// Moves the elements into a new `VecDeque`, front first, with at most
+    /// two `memcpy`s.
+    #[inline]
+    fn from(deque: ArrayDeque<A, B>) -> Self {
+        deque.move_into_vec(0).into()
+    }
+}
+
+// before Rust 1.41, a foreign type cannot implement `From` for a local one
+#[cfg(all(feature = "std", not(has_from_foreign)))]
+#[allow(clippy::from_over_into)]
+impl<A: Array, B: Behavior> Into<Vec<A::Item>> for ArrayDeque<A, B> {
+    #[inline]
+    fn into(self) -> Vec<A::Item> {
+        self.move_into_vec(0)
+    }
+}
+
+#[cfg(all(feature = "std", not(has_from_foreign)))]
+#[allow(clippy::from_over_into)]
+impl<A: Array, B: Behavior> Into<VecDeque<A::Item>> for ArrayDeque<A, B> {
+    #[inline]
+    fn into(self) -> VecDeque<A::Item> {
+        self.move_into_vec(0).into()
+    }
+}
+
+impl<A: Array, B: Behavior> Drop for ArrayDeque<A, B> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<A: Array, B: Behavior> Default for ArrayDeque<A, B> {
+    #[inline]
+    fn default() -> Self {
+        ArrayDeque::new()
+    }
+}
+
+impl<A: Array, B: Behavior> PartialEq for ArrayDeque<A, B>
+where
+    A::Item: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let (sa, sb) = self.as_slices();
+        let (oa, ob) = other.as_slices();
+        if sa.len() == oa.len() {
+            sa == oa && sb == ob
+        } else if sa.len() < oa.len() {
+            // Always divisible in three sections, for example:
+            // self:  [a b c|d e f]
+            // other: [0 1 2 3|4 5]
+            // front = 3, mid = 1,
+            // [a b c] == [0 1 2] && [d] == [3] && [e f] == [4 5]
+            let front = sa.len();
+            let mid = oa.len() - front;
+
+            let (oa_front, oa_mid) = oa.split_at(front);
+            let (sb_mid, sb_back) = sb.split_at(mid);
+            debug_assert_eq!(sa.len(), oa_front.len());
+            debug_assert_eq!(sb_mid.len(), oa_mid.len());
+            debug_assert_eq!(sb_back.len(), ob.len());
+            sa == oa_front && sb_mid == oa_mid && sb_back == ob
+        } else {
+            let front = oa.len();
+            let mid = sa.len() - front;
+
+            let (sa_front, sa_mid) = sa.split_at(front);
+            let (ob_mid, ob_back) = ob.split_at(mid);
+            debug_assert_eq!(sa_front.len(), oa.len());
+            debug_assert_eq!(sa_mid.len(), ob_mid.len());
+            debug_assert_eq!(sb.len(), ob_back.len());
+            sa_front == oa && sa_mid == ob_mid && sb == ob_back
+        }
+    }
+}
+
+impl<A: Array, B: Behavior> Eq for ArrayDeque<A, B> where A::Item: Eq {}
+
+impl<A: Array, B: Behavior> PartialOrd for ArrayDeque<A, B>
+where
+    A::Item: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<A: Array, B: Behavior> Ord for ArrayDeque<A, B>
+where
+    A::Item: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<A: Array, B: Behavior> Hash for ArrayDeque<A, B>
+where
+    A::Item: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        // `hash_slice` may write a slice at once, and not every hasher
+        // treats two writes like their concatenation
+        for element in self {
+            element.hash(state);
+        }
+    }
+}
+
+/// Stops on an out of bounds index.
+///
+/// With the `index_trap` feature, this aborts the process, so no formatting
+/// code is linked in. `core` has no stable abort, so without `std` this
+/// panics with a fixed message instead, and the `#[panic_handler]` of the
+/// application decides whether that halts, resets or unwinds.
+#[cold]
+#[inline(never)]
+pub(crate) fn index_out_of_bounds(len: usize, index: usize) -> ! {
+    #[cfg(all(feature = "index_trap", feature = "std"))]
+    {
+        let _ = (len, index);
+        std::process::abort()
+    }
+    #[cfg(all(feature = "index_trap", not(feature = "std")))]
+    {
+        let _ = (len, index);
+        panic!()
+    }
+    #[cfg(not(feature = "index_trap"))]
+    panic!(
+        "index out of bounds: the len is {} but the index is {}",
+        len, index
+    )
+}
+
+impl<A: Array, B: Behavior> Index<usize> for ArrayDeque<A, B> {
+    type Output = A::Item;
+
+    #[inline]
+    fn index(&self, index: usize) -> &A::Item {
+        let len = self.len();
+        match self.get(index) {
+            Some(element) => element,
+            None => index_out_of_bounds(len, index),
+        }
+    }
+}
+
+impl<A: Array, B: Behavior> IndexMut<usize> for ArrayDeque<A, B> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut A::Item {
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => index_out_of_bounds(len, index),
+        }
+    }
+}
+
+impl<A: Array, B: Behavior> IntoIterator for ArrayDeque<A, B> {
+    type Item = A::Item;
+    type IntoIter = IntoIter<A, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { inner: self }
+    }
+}
+
+impl<'a, A: Array, B: Behavior> IntoIterator for &'a ArrayDeque<A, B> {
+    type Item = &'a A::Item;
+    type IntoIter = Iter<'a, A::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, A: Array, B: Behavior> IntoIterator for &'a mut ArrayDeque<A, B> {
+    type Item = &'a mut A::Item;
+    type IntoIter = IterMut<'a, A::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<A: Array, B: Behavior> fmt::Debug for ArrayDeque<A, B>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+#[inline]
+fn wrap_add(index: usize, addend: usize, capacity: usize) -> usize {
+    debug_assert!(addend <= capacity);
+    // a buffer without slots only has index 0, and no modulus
+    if capacity == 0 {
+        return 0;
+    }
+    (index + addend) % capacity
+}
+
+#[inline]
+fn wrap_sub(index: usize, subtrahend: usize, capacity: usize) -> usize {
+    debug_assert!(subtrahend <= capacity);
+    if capacity == 0 {
+        return 0;
+    }
+    (index + capacity - subtrahend) % capacity
+}
+
+/// Iterator over the contiguous slices of an `ArrayDeque`.
+///
+/// This type is returned by the `slices_iter` method on `ArrayDeque`.
+pub type Slices<'a, T> = Chain<option::IntoIter<&'a [T]>, option::IntoIter<&'a [T]>>;
+
+/// Iterator over the contiguous mutable slices of an `ArrayDeque`.
+///
+/// This type is returned by the `slices_iter_mut` method on `ArrayDeque`.
+pub type SlicesMut<'a, T> = Chain<option::IntoIter<&'a mut [T]>, option::IntoIter<&'a mut [T]>>;
+
+/// `ArrayDeque` iterator
+///
+/// This `struct` is created by the `iter` method on `ArrayDeque`. Like the
+/// other iterator types of this crate, it can be named in signatures:
+///
+/// ```
+/// use arraydeque::{ArrayDeque, Iter};
+///
+/// fn evens(buf: &ArrayDeque<[u32; 4]>) -> std::iter::StepBy<Iter<'_, u32>> {
+///     buf.iter().step_by(2)
+/// }
+///
+/// let buf: ArrayDeque<[_; 4]> = (0..4).collect();
+///
+/// assert!(evens(&buf).eq(&[0, 2]));
+/// ```
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Iter<'a, T: 'a> {
+    // the remaining elements, in order
+    a: &'a [T],
+    b: &'a [T],
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Skips the next `n` elements, or every remaining element if fewer.
+    #[inline]
+    fn advance(&mut self, n: usize) {
+        if n < self.a.len() {
+            self.a = &self.a[n..];
+        } else {
+            let n = cmp::min(n - self.a.len(), self.b.len());
+            self.a = &self.b[n..];
+            self.b = &[];
+        }
+    }
+
+    /// Returns the remaining elements as a pair of slices, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(2);
+    /// buf.push_back(3);
+    /// buf.push_front(1);
+    /// buf.push_front(0);
+    ///
+    /// let mut iter = buf.iter();
+    /// iter.next();
+    ///
+    /// let (a, b) = iter.as_slices();
+    /// assert_eq!([a, b].concat(), vec![1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        (self.a, self.b)
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.a.is_empty() {
+            self.a = self.b;
+            self.b = &[];
+        }
+        match self.a.split_first() {
+            Some((first, rest)) => {
+                self.a = rest;
+                Some(first)
+            }
+            None => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.len() + self.b.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.a.len() + self.b.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<&'a T> {
+        self.advance(n);
+        self.next()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<&'a T> {
+        self.next_back()
+    }
+
+    fn fold<Acc, F>(self, accum: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let (a, b) = self.as_slices();
+        let accum = a.iter().fold(accum, &mut f);
+        b.iter().fold(accum, &mut f)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.b.is_empty() {
+            self.b = self.a;
+            self.a = &[];
+        }
+        match self.b.split_last() {
+            Some((last, rest)) => {
+                self.b = rest;
+                Some(last)
+            }
+            None => None,
+        }
+    }
+
+    fn rfold<Acc, F>(self, accum: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let (a, b) = self.as_slices();
+        let accum = b.iter().rfold(accum, &mut f);
+        a.iter().rfold(accum, &mut f)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Iter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, b) = self.as_slices();
+        f.debug_tuple("Iter").field(&a).field(&b).finish()
+    }
+}
+
+/// `ArrayDeque` mutable iterator
+///
+/// This `struct` is created by the `iter_mut` method on `ArrayDeque`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct IterMut<'a, T: 'a> {
+    // the remaining elements, in order
+    a: &'a mut [T],
+    b: &'a mut [T],
+}
+
+impl<'a, T> IterMut<'a, T> {
+    /// Returns the remaining elements as a pair of mutable slices, in order,
+    /// without consuming the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    /// buf.push_back(3);
+    ///
+    /// let mut iter = buf.iter_mut();
+    /// iter.next();
+    /// iter.as_mut_slices().0[0] = 20;
+    ///
+    /// assert_eq!(iter.next(), Some(&mut 20));
+    /// ```
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        (&mut *self.a, &mut *self.b)
+    }
+
+    /// Converts the iterator into the remaining elements as a pair of mutable
+    /// slices, in order.
+    ///
+    /// This allows switching from element-wise to slice-wise processing once
+    /// the region of interest is found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
+    ///
+    /// buf.push_back(2);
+    /// buf.push_back(3);
+    /// buf.push_front(1);
+    /// buf.push_front(0);
+    ///
+    /// let mut iter = buf.iter_mut();
+    /// while let Some(x) = iter.next() {
+    ///     if *x == 1 {
+    ///         break;
+    ///     }
+    /// }
+    ///
+    /// let (a, b) = iter.into_slices();
+    /// for x in a.iter_mut().chain(b) {
+    ///     *x *= 10;
+    /// }
+    ///
+    /// assert_eq!(buf, vec![0, 1, 20, 30].into());
+    /// ```
+    #[inline]
+    pub fn into_slices(self) -> (&'a mut [T], &'a mut [T]) {
+        (self.a, self.b)
+    }
+}
+
+// `mem::take` needs Rust 1.40
+#[allow(clippy::mem_replace_with_default)]
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.a.is_empty() {
+            self.a = mem::replace(&mut self.b, &mut []);
+        }
+        match mem::replace(&mut self.a, &mut []).split_first_mut() {
+            Some((first, rest)) => {
+                self.a = rest;
+                Some(first)
+            }
+            None => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.len() + self.b.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.a.len() + self.b.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<&'a mut T> {
+        if n < self.a.len() {
+            let a = mem::replace(&mut self.a, &mut []);
+            self.a = &mut a[n..];
+        } else {
+            let n = cmp::min(n - self.a.len(), self.b.len());
+            let b = mem::replace(&mut self.b, &mut []);
+            self.a = &mut b[n..];
+        }
+        self.next()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<&'a mut T> {
+        self.next_back()
+    }
+
+    fn fold<Acc, F>(self, accum: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let (a, b) = self.into_slices();
+        let accum = a.iter_mut().fold(accum, &mut f);
+        b.iter_mut().fold(accum, &mut f)
+    }
+}
+
+// `mem::take` needs Rust 1.40
+#[allow(clippy::mem_replace_with_default)]
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.b.is_empty() {
+            self.b = mem::replace(&mut self.a, &mut []);
+        }
+        match mem::replace(&mut self.b, &mut []).split_last_mut() {
+            Some((last, rest)) => {
+                self.b = rest;
+                Some(last)
+            }
+            None => None,
+        }
+    }
+
+    fn rfold<Acc, F>(self, accum: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let (a, b) = self.into_slices();
+        let accum = b.iter_mut().rfold(accum, &mut f);
+        a.iter_mut().rfold(accum, &mut f)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
+
+impl<'a, T: fmt::Debug> fmt::Debug for IterMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IterMut")
+            .field(&self.a)
+            .field(&self.b)
+            .finish()
+    }
+}
+
+/// By-value `ArrayDeque` iterator
+///
+/// This `struct` is created by the `into_iter` method on `ArrayDeque`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct IntoIter<A: Array, B: Behavior> {
+    inner: ArrayDeque<A, B>,
+}
+
+impl<A: Array, B: Behavior> Iterator for IntoIter<A, B> {
+    type Item = A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<A::Item> {
+        self.inner.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len();
+        (len, Some(len))
+    }
+}
+
+impl<A: Array, B: Behavior> DoubleEndedIterator for IntoIter<A, B> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A::Item> {
+        self.inner.pop_back()
+    }
+}
+
+impl<A: Array, B: Behavior> ExactSizeIterator for IntoIter<A, B> {}
+
+impl<A: Array, B: Behavior> FusedIterator for IntoIter<A, B> {}
+
+impl<A: Array, B: Behavior> fmt::Debug for IntoIter<A, B>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.inner).finish()
+    }
+}
+
+/// Draining `ArrayDeque` iterator
+///
+/// Yields the drained elements front to back, or back to front through
+/// `next_back`.
+///
+/// This `struct` is created by the `drain` method on `ArrayDeque`.
+pub struct Drain<'a, A, B>
+where
+    A: Array,
+    A::Item: 'a,
+    B: Behavior,
+{
+    after_tail: usize,
+    after_len: usize,
+    // the drained elements not yielded yet, read through `deque` so that no
+    // reference to the ring outlives a write to it
+    tail: usize,
+    len: usize,
+    deque: *mut ArrayDeque<A, B>,
+    marker: marker::PhantomData<&'a mut A::Item>,
+}
+
+impl<'a, A, B> Drop for Drain<'a, A, B>
+where
+    A: Array,
+    A::Item: 'a,
+    B: Behavior,
+{
+    fn drop(&mut self) {
+        // drops the rest and closes the gap even if a destructor panics
+        struct DropGuard<'r, 'a, A, B>(&'r mut Drain<'a, A, B>)
+        where
+            A: Array + 'r,
+            A::Item: 'a,
+            B: Behavior + 'r,
+            'a: 'r;
+
+        impl<'r, 'a, A, B> Drop for DropGuard<'r, 'a, A, B>
+        where
+            A: Array,
+            A::Item: 'a,
+            B: Behavior,
+        {
+            fn drop(&mut self) {
+                for _ in self.0.by_ref() {}
+                self.0.close_gap();
+            }
+        }
+
+        while let Some(element) = self.next() {
+            let guard = DropGuard(self);
+            drop(element);
+            mem::forget(guard);
+        }
+        self.close_gap();
+    }
+}
+
+impl<'a, A, B> Drain<'a, A, B>
+where
+    A: Array,
+    A::Item: 'a,
+    B: Behavior,
+{
+    /// Moves the elements after the drained range back against the ones
+    /// before it.
+    fn close_gap(&mut self) {
+        let source_deque = unsafe { &mut *self.deque };
+
+        let tail_len = source_deque.len();
+        let head_len = self.after_len;
+
+        let orig_tail = source_deque.tail();
+        let drain_tail = wrap_add(orig_tail, tail_len, A::capacity());
+        let drain_head = self.after_tail;
+        let orig_head = wrap_add(drain_head, head_len, A::capacity());
+        let orig_len = wrap_sub(orig_head, orig_tail, A::capacity());
+
+        // Restore the original len value
+        unsafe { source_deque.set_len(orig_len) }
+        match (tail_len, head_len) {
+            (0, 0) => unsafe {
+                source_deque.set_tail(0);
+                source_deque.set_len(0);
+            },
+            (0, _) => unsafe {
+                source_deque.set_tail(drain_head);
+                source_deque.set_len(head_len);
+            },
+            (_, 0) => unsafe { source_deque.set_len(tail_len) },
+            _ => unsafe {
+                if tail_len <= head_len {
+                    let new_tail = wrap_sub(drain_head, tail_len, A::capacity());
+                    source_deque.set_tail(new_tail);
+                    source_deque.set_len(tail_len + head_len);
+                    source_deque.wrap_copy(new_tail, orig_tail, tail_len);
+                } else {
+                    source_deque.set_len(tail_len + head_len);
+                    source_deque.wrap_copy(drain_tail, drain_head, head_len);
+                }
+            },
+        }
+    }
+}
+
+impl<'a, A, B> Iterator for Drain<'a, A, B>
+where
+    A: Array,
+    A::Item: 'a,
+    B: Behavior,
+{
+    type Item = A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<A::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let tail = self.tail;
+        self.tail = wrap_add(tail, 1, A::capacity());
+        self.len -= 1;
+        unsafe { Some(ptr::read((*self.deque).ptr().add(tail))) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, A, B> DoubleEndedIterator for Drain<'a, A, B>
+where
+    A: Array,
+    A::Item: 'a,
+    B: Behavior,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<A::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let head = wrap_add(self.tail, self.len, A::capacity());
+        unsafe { Some(ptr::read((*self.deque).ptr().add(head))) }
+    }
+}
+
+impl<'a, A, B> ExactSizeIterator for Drain<'a, A, B>
+where
+    A: Array,
+    A::Item: 'a,
+    B: Behavior,
+{
+}
+
+impl<'a, A, B> FusedIterator for Drain<'a, A, B>
+where
+    A: Array,
+    A::Item: 'a,
+    B: Behavior,
+{}
+
+impl<'a, A, B> fmt::Debug for Drain<'a, A, B>
+where
+    A: Array,
+    A::Item: 'a + fmt::Debug,
+    B: Behavior,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, b) = unsafe { (*self.deque).slots(self.tail, self.len) };
+        let iter = Iter { a, b };
+        f.debug_tuple("Drain").field(&iter).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_must_use)]
+    use super::*;
+
+    #[test]
+    fn test_simple() {
+        macro_rules! test {
+            ($behavior:ident) => {{
+                let mut tester: ArrayDeque<[_; 7], $behavior> = ArrayDeque::new();
+                assert_eq!(tester.capacity(), 7);
+                assert_eq!(tester.len(), 0);
+
+                tester.push_back(1);
+                tester.push_back(2);
+                tester.push_back(3);
+                tester.push_back(4);
+                assert_eq!(tester.len(), 4);
+                assert_eq!(tester.pop_front(), Some(1));
+                assert_eq!(tester.pop_front(), Some(2));
+                assert_eq!(tester.len(), 2);
+                assert_eq!(tester.pop_front(), Some(3));
+                assert_eq!(tester.pop_front(), Some(4));
+                assert_eq!(tester.pop_front(), None);
+            }};
+        }
+
+        test!(Saturating);
+        test!(Wrapping);
+    }
+
+    #[test]
+    fn test_simple_reversely() {
+        macro_rules! test {
+            ($behavior:ident) => {{
+                let mut tester: ArrayDeque<[_; 7], $behavior> = ArrayDeque::new();
+                assert_eq!(tester.capacity(), 7);
+                assert_eq!(tester.len(), 0);
+
+                tester.push_front(1);
+                tester.push_front(2);
+                tester.push_front(3);
+                tester.push_front(4);
+                assert_eq!(tester.len(), 4);
+                assert_eq!(tester.pop_back(), Some(1));
+                assert_eq!(tester.pop_back(), Some(2));
+                assert_eq!(tester.len(), 2);
+                assert_eq!(tester.pop_back(), Some(3));
+                assert_eq!(tester.pop_back(), Some(4));
+                assert_eq!(tester.pop_back(), None);
+            }};
+        }
+
+        test!(Saturating);
+        test!(Wrapping);
+    }
+
+    #[test]
+    fn test_overflow_saturating() {
+        let mut tester: ArrayDeque<[_; 2], Saturating> = ArrayDeque::new();
+        assert_eq!(tester.push_back(1), Ok(()));
+        assert_eq!(tester.push_back(2), Ok(()));
+        assert_eq!(tester.push_back(3), Err(CapacityError { element: 3 }));
+
+        let mut tester: ArrayDeque<[_; 2], Saturating> = ArrayDeque::new();
+        assert_eq!(tester.insert(0, 1), Ok(()));
+        assert_eq!(tester.insert(1, 2), Ok(()));
+        assert_eq!(tester.insert(2, 3), Err(CapacityError { element: 3 }));
+    }
+
+    #[test]
+    fn test_overflow_wrapping() {
+        let mut tester: ArrayDeque<[_; 2], Wrapping> = ArrayDeque::new();
+        assert_eq!(tester.push_back(1), None);
+        assert_eq!(tester.push_back(2), None);
+        assert_eq!(tester.push_back(3), Some(1));
+        assert_eq!(tester.push_front(4), Some(3));
+        assert_eq!(tester, vec![4, 2].into());
+
+        let mut tester: ArrayDeque<[_; 3], Wrapping> = ArrayDeque::new();
+        assert_eq!(tester.extend_back_counting(1..6), 2);
+        assert_eq!(tester, vec![3, 4, 5].into());
+        assert_eq!(tester.extend_front_counting(6..8), 2);
+        assert_eq!(tester, vec![7, 6, 3].into());
+        // extending a full deque kicks out elements from the front
+        tester.extend(8..10);
+        assert_eq!(tester, vec![3, 8, 9].into());
+    }
+
+    #[test]
+    fn test_pop_empty() {
+        let mut tester: ArrayDeque<[_; 2]> = ArrayDeque::new();
+        assert_eq!(tester.push_back(1), Ok(()));
+        assert_eq!(tester.pop_front(), Some(1));
+        assert!(tester.is_empty());
+        assert_eq!(tester.len(), 0);
+        assert_eq!(tester.pop_front(), None);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut tester: ArrayDeque<[_; 3]> = ArrayDeque::new();
+        tester.push_back(1);
+        tester.push_back(2);
+        tester.push_back(3);
+        assert_eq!(tester[0], 1);
+        // pop_front 1 <- [2, 3]
+        assert_eq!(tester.pop_front(), Some(1));
+        assert_eq!(tester[0], 2);
+        assert_eq!(tester.len(), 2);
+        // push_front 0 -> [0, 2, 3]
+        tester.push_front(0);
+        assert_eq!(tester[0], 0);
+        // [0, 2] -> 3 pop_back
+        assert_eq!(tester.pop_back(), Some(3));
+        assert_eq!(tester[1], 2);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(not(feature = "index_trap"))]
+    fn test_index_overflow() {
+        let mut tester: ArrayDeque<[_; 3]> = ArrayDeque::new();
+        tester.push_back(1);
+        tester.push_back(2);
+        let _ = tester[2];
+    }
+
+    #[test]
+    fn test_get_range() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let expected: Vec<_> = (0..len).collect();
+
+                for start in 0..len + 2 {
+                    for end in start..len + 2 {
+                        let got = tester
+                            .get_range(start..end)
+                            .map(|(a, b)| a.iter().chain(b).cloned().collect::<Vec<_>>());
+                        assert_eq!(got, expected.get(start..end).map(|s| s.to_vec()));
+
+                        if let Some((a, b)) = tester.get_range_mut(start..end) {
+                            assert_eq!(a.len() + b.len(), end - start);
+                            assert!(b.is_empty() || !a.is_empty());
+                        }
+                    }
+                }
+                for end in 0..len + 1 {
+                    let got = tester
+                        .get_range(..=end)
+                        .map(|(a, b)| a.iter().chain(b).cloned().collect::<Vec<_>>());
+                    assert_eq!(got, expected.get(..=end).map(|s| s.to_vec()));
+                    assert_eq!(tester.get_range(0..=end), tester.get_range(..=end));
+                }
+                assert_eq!(
+                    tester.get_range(..).map(|(a, b)| a.len() + b.len()),
+                    Some(len)
+                );
+                assert_eq!(tester.get_range(..=usize::MAX), None);
+                assert_eq!(tester.get(len), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut tester: ArrayDeque<[_; 2]> = ArrayDeque::new();
+        tester.push_back(1);
+        tester.push_back(2);
+        {
+            let mut iter = tester.iter();
+            assert_eq!(iter.size_hint(), (2, Some(2)));
+            assert_eq!(iter.next(), Some(&1));
+            assert_eq!(iter.next(), Some(&2));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+        tester.pop_front();
+        tester.push_back(3);
+        {
+            let mut iter = (&tester).into_iter();
+            assert_eq!(iter.next(), Some(&2));
+
+            // test clone
+            let mut iter2 = iter.clone();
+            assert_eq!(iter.next(), Some(&3));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter2.next(), Some(&3));
+            assert_eq!(iter2.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut tester: ArrayDeque<[_; 2]> = ArrayDeque::new();
+        tester.push_back(1);
+        tester.push_back(2);
+        {
+            let mut iter = tester.iter_mut();
+            assert_eq!(iter.size_hint(), (2, Some(2)));
+            assert_eq!(iter.next(), Some(&mut 1));
+            assert_eq!(iter.next(), Some(&mut 2));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+        tester.pop_front();
+        tester.push_back(3);
+        {
+            let mut iter = (&mut tester).into_iter();
+            assert_eq!(iter.next(), Some(&mut 2));
+            assert_eq!(iter.next(), Some(&mut 3));
+            assert_eq!(iter.next(), None);
+        }
+        {
+            // mutation
+            let mut iter = tester.iter_mut();
+            if let Some(n) = iter.next() {
+                *n += 1;
+            }
+            if let Some(n) = iter.next() {
+                *n += 2;
+            }
+        }
+        assert_eq!(tester[0], 3);
+        assert_eq!(tester[1], 5);
+    }
+
+    #[test]
+    fn test_iter_fold() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
                 }
+                tester.extend_back(0..len);
+                let expected: Vec<_> = (0..len).collect();
+                let expected_rev: Vec<_> = (0..len).rev().collect();
+
+                let folded = tester.iter().fold(vec![], |mut v, &x| {
+                    v.push(x);
+                    v
+                });
+                assert_eq!(folded, expected);
+                let rfolded = tester.iter().rfold(vec![], |mut v, &x| {
+                    v.push(x);
+                    v
+                });
+                assert_eq!(rfolded, expected_rev);
+                assert_eq!(tester.iter_back().cloned().collect::<Vec<_>>(), expected_rev);
+
+                // partially consumed iterators only fold the remainder
+                let mut iter = tester.iter();
+                iter.next();
+                iter.next_back();
+                assert_eq!(iter.fold(0, |n, _| n + 1), len.saturating_sub(2));
+
+                tester.iter_mut().rfold((), |_, x| *x *= 2);
+                tester.iter_mut().fold((), |_, x| *x += 1);
+                let doubled: Vec<_> = (0..len).map(|x| x * 2 + 1).collect();
+                assert_eq!(tester, doubled.into());
             }
-            (false, true, false) => {
+        }
+    }
+
+    #[cfg(has_const_generics)]
+    #[test]
+    fn test_chunks() {
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
                 unsafe {
-                    // discontiguous, remove closer to tail, head section:
-                    //
-                    //           R               H     T
-                    //      [o o x o o o o o o o . . . o o o]
-                    //
-                    //                           H       T
-                    //      [o o o o o o o o o o . . . . o o]
-                    //       M M M                       M M
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let expected: Vec<_> = (0..len).collect();
+
+                let chunks: Vec<[usize; 3]> = tester.iter_chunks().collect();
+                let expected_chunks: Vec<_> = expected.chunks(3).filter(|c| c.len() == 3).collect();
+                assert_eq!(chunks.len(), len / 3);
+                assert_eq!(tester.iter_chunks::<3>().len(), len / 3);
+                for (chunk, expected_chunk) in chunks.iter().zip(&expected_chunks) {
+                    assert_eq!(&chunk[..], *expected_chunk);
+                }
+                for (refs, chunk) in tester.iter_chunk_refs::<3>().zip(&chunks) {
+                    assert_eq!(refs.iter().map(|&&x| x).collect::<Vec<_>>(), &chunk[..]);
+                }
 
-                    let tail = self.tail();
-                    // draw in elements up to idx
-                    self.copy(1, 0, idx);
+                let rebuilt = ArrayDeque::<[_; CAP]>::from_chunks(&chunks).unwrap();
+                assert_eq!(rebuilt.len(), chunks.len() * 3);
+            }
+        }
+
+        assert_eq!(
+            ArrayDeque::<[usize; CAP]>::from_chunks(&[[0; 4], [0; 4]]),
+            Err(CapacityError { element: () })
+        );
+        assert_eq!(
+            ArrayDeque::<[(); CAP]>::from_chunks(&[[(); usize::MAX], [(); usize::MAX]]),
+            Err(CapacityError { element: () })
+        );
+    }
+
+    #[test]
+    fn test_into_iter() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct NoCopy<T>(T);
+
+        {
+            let mut tester: ArrayDeque<[NoCopy<u8>; 3]> = ArrayDeque::new();
+            tester.push_back(NoCopy(1));
+            tester.push_back(NoCopy(2));
+            let mut iter = tester.into_iter();
+            assert_eq!(iter.size_hint(), (2, Some(2)));
+            assert_eq!(iter.next(), Some(NoCopy(1)));
+            assert_eq!(iter.next(), Some(NoCopy(2)));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+        {
+            let mut tester: ArrayDeque<[NoCopy<u8>; 3]> = ArrayDeque::new();
+            tester.push_back(NoCopy(1));
+            tester.push_back(NoCopy(2));
+            tester.pop_front();
+            tester.push_back(NoCopy(3));
+            let mut iter = tester.into_iter();
+            assert_eq!(iter.next(), Some(NoCopy(2)));
+            assert_eq!(iter.next(), Some(NoCopy(3)));
+            assert_eq!(iter.next(), None);
+        }
+        {
+            let mut tester: ArrayDeque<[NoCopy<u8>; 3]> = ArrayDeque::new();
+            tester.push_back(NoCopy(1));
+            tester.push_back(NoCopy(2));
+            tester.pop_front();
+            tester.push_back(NoCopy(3));
+            tester.pop_front();
+            tester.push_back(NoCopy(4));
+            let mut iter = tester.into_iter();
+            assert_eq!(iter.next(), Some(NoCopy(3)));
+            assert_eq!(iter.next(), Some(NoCopy(4)));
+            assert_eq!(iter.next(), None);
+        }
+    }
 
-                    // copy last element into empty spot
-                    self.copy(0, A::capacity() - 1, 1);
+    #[test]
+    fn test_peek_front_guard() {
+        use std::panic;
 
-                    // move elements from tail to end forward, excluding the last one
-                    self.copy(tail + 1, tail, A::capacity() - tail - 1);
+        let mut tester: ArrayDeque<[_; 3]> = ArrayDeque::new();
+        assert!(tester.peek_front_guard().is_none());
 
-                    self.set_tail_forward();
-                }
-            }
+        tester.extend_back(vec![String::from("a"), String::from("b")]);
+        {
+            let mut guard = tester.peek_front_guard().unwrap();
+            guard.push('!');
         }
+        assert_eq!(tester.front().map(|s| &s[..]), Some("a!"));
 
-        return elem;
-    }
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let _guard = tester.peek_front_guard().unwrap();
+            panic!("handler failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(tester.len(), 2);
 
-    /// Splits the collection into two at the given index.
-    ///
-    /// Returns a newly allocated `Self`. `self` contains elements `[0, at)`,
-    /// and the returned `Self` contains elements `[at, len)`.
-    ///
-    /// Element at index 0 is the front of the queue.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `at > len`
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<[_; 3]> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
-    ///
-    /// // buf = [0], buf2 = [1, 2]
-    /// let buf2 = buf.split_off(1);
-    ///
-    /// assert_eq!(buf.len(), 1);
-    /// assert_eq!(buf2.len(), 2);
-    /// ```
-    #[inline]
-    pub fn split_off(&mut self, at: usize) -> Self {
-        let len = self.len();
-        assert!(at <= len, "`at` out of bounds");
+        assert_eq!(tester.peek_front_guard().unwrap().commit(), "a!");
+        assert_eq!(tester.peek_front_guard().unwrap().commit(), "b");
+        assert!(tester.is_empty());
+    }
 
-        let other_len = len - at;
-        let mut other = Self::new();
+    #[test]
+    fn test_transaction() {
+        let mut tester: ArrayDeque<[_; 4]> = ArrayDeque::new();
+        tester.push_back(0);
 
-        unsafe {
-            let (first_half, second_half) = self.as_slices();
+        {
+            let mut tx = tester.transaction();
+            tx.push_back(1).unwrap();
+            tx.push_front(-1).unwrap();
+            assert_eq!(tx.len(), 3);
+        }
+        assert_eq!(tester, vec![0].into());
 
-            let first_len = first_half.len();
-            let second_len = second_half.len();
-            if at < first_len {
-                // `at` lies in the first half.
-                let amount_in_first = first_len - at;
+        {
+            let mut tx = tester.transaction();
+            tx.push_front(-1).unwrap();
+            assert_eq!(tx.extend_back(1..4), Err(CapacityError { element: 3 }));
+        }
+        assert_eq!(tester, vec![0].into());
 
-                ptr::copy_nonoverlapping(
-                    first_half.as_ptr().offset(at as isize),
-                    other.ptr_mut(),
-                    amount_in_first,
-                );
+        {
+            let mut tx = tester.transaction();
+            tx.push_front(-1).unwrap();
+            tx.extend_back(1..3).unwrap();
+            tx.commit();
+        }
+        assert_eq!(tester, vec![-1, 0, 1, 2].into());
+    }
 
-                // just take all of the second half.
-                ptr::copy_nonoverlapping(
-                    second_half.as_ptr(),
-                    other.ptr_mut().offset(amount_in_first as isize),
-                    second_len,
-                );
-            } else {
-                // `at` lies in the second half, need to factor in the elements we skipped
-                // in the first half.
-                let offset = at - first_len;
-                let amount_in_second = second_len - offset;
-                ptr::copy_nonoverlapping(
-                    second_half.as_ptr().offset(offset as isize),
-                    other.ptr_mut(),
-                    amount_in_second,
-                );
+    #[test]
+    fn test_transaction_insert() {
+        const CAP: usize = 10;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
             }
+            tester.extend_back(vec![0, 10, 20]);
+
+            {
+                let mut tx = tester.transaction();
+                tx.insert(1, 5).unwrap();
+                tx.insert(2, 6).unwrap();
+                tx.insert(1, 4).unwrap();
+                tx.push_front(-1).unwrap();
+                tx.insert(0, -2).unwrap();
+                tx.insert(8, 30).unwrap();
+                tx.push_back(40).unwrap();
+                assert!(tx.iter().eq(&[-2, -1, 0, 4, 5, 6, 10, 20, 30, 40]));
+                assert!(tx.insert(1, 50).is_err());
+            }
+            assert_eq!(tester, vec![0, 10, 20].into());
+
+            {
+                let mut tx = tester.transaction();
+                tx.insert(3, 30).unwrap();
+                tx.insert(2, 15).unwrap();
+                tx.insert(3, 17).unwrap();
+                tx.commit();
+            }
+            assert_eq!(tester, vec![0, 10, 15, 17, 20, 30].into());
+            tester.clear();
         }
+    }
 
-        // Cleanup where the ends of the buffers are
-        unsafe {
-            self.set_len(at);
-            other.set_len(other_len);
-        }
+    #[test]
+    #[should_panic(expected = "a single run")]
+    fn test_transaction_insert_second_run() {
+        let mut tester: ArrayDeque<[_; 8]> = ArrayDeque::new();
+        tester.extend_back(0..4);
+        let mut tx = tester.transaction();
+        tx.insert(1, 10).unwrap();
+        tx.insert(3, 20).unwrap();
+    }
 
-        other
+    #[test]
+    fn test_builder() {
+        let tester: ArrayDeque<[_; 3], Wrapping> = ArrayDeque::builder()
+            .push(1)
+            .push_front(0)
+            .extend(vec![2])
+            .build()
+            .unwrap();
+        assert_eq!(tester, vec![0, 1, 2].into());
+
+        let tester = ArrayDeque::<[_; 3], Wrapping>::builder()
+            .extend(0..3)
+            .push_front(-1)
+            .push(3)
+            .build();
+        assert_eq!(tester, Err(CapacityError { element: -1 }));
+
+        let tester: Result<ArrayDeque<[i32; 2]>, _> = ArrayDeque::builder().build();
+        assert!(tester.unwrap().is_empty());
     }
 
-    /// Retains only the elements specified by the predicate.
-    ///
-    /// In other words, remove all elements `e` such that `f(&e)` returns false.
-    /// This method operates in place and preserves the order of the retained
-    /// elements.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<[_; 4]> = ArrayDeque::new();
-    ///
-    /// buf.extend_back(0..4);
-    /// buf.retain(|&x| x % 2 == 0);
-    ///
-    /// assert_eq!(buf, vec![0, 2].into());
-    /// ```
-    pub fn retain<F>(&mut self, mut f: F)
-    where
-        F: FnMut(&A::Item) -> bool,
-    {
-        let len = self.len();
-        let mut del = 0;
-        for i in 0..len {
-            if !f(&self[i]) {
-                del += 1;
-            } else if del > 0 {
-                self.swap(i - del, i);
-            }
+    #[cfg(feature = "unstable_placement")]
+    #[test]
+    fn test_new_in_place() {
+        let mut slot = mem::MaybeUninit::uninit();
+        {
+            let tester: &mut ArrayDeque<[_; 3], Wrapping> = ArrayDeque::new_in_place(&mut slot);
+            assert!(tester.is_empty());
+            tester.extend_back(0..4);
+            assert_eq!(*tester, vec![1, 2, 3].into());
         }
-        if del > 0 {
-            for _ in (len - del)..self.len() {
-                self.pop_back();
+        let tester = unsafe { slot.assume_init() };
+        assert_eq!(tester, vec![1, 2, 3].into());
+    }
+
+    #[test]
+    fn test_regions() {
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[u8; CAP]> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
             }
+            tester.push_back(0);
+            tester.push_back(1);
+
+            let mut written = 0;
+            while !tester.is_full() {
+                let spare = tester.spare_capacity_mut();
+                let n = spare.len();
+                assert!(n > 0);
+                assert_eq!(
+                    spare.commit_back(n + 1),
+                    Err(CapacityError { element: n + 1 })
+                );
+
+                let mut spare = tester.spare_capacity_mut();
+                assert!(spare.iter().all(|&x| x == 0));
+                for slot in spare.iter_mut() {
+                    written += 1;
+                    *slot = written + 1;
+                }
+                spare.commit_back(n).unwrap();
+            }
+            assert_eq!(tester, vec![0, 1, 2, 3, 4, 5].into());
+            assert!(tester.spare_capacity_mut().is_empty());
+
+            let mut read = Vec::new();
+            while !tester.is_empty() {
+                let filled = tester.filled_region();
+                let n = filled.len();
+                read.extend_from_slice(&filled);
+                filled.commit_front(n).unwrap();
+            }
+            assert_eq!(read, vec![0, 1, 2, 3, 4, 5]);
+            assert_eq!(tester.spare_capacity_mut().len(), CAP);
         }
     }
 
-    /// Returns a pair of slices which contain, in order, the contents of the
-    /// `ArrayDeque`.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<[_; 7]> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    ///
-    /// assert_eq!(buf.as_slices(), (&[0, 1][..], &[][..]));
-    ///
-    /// buf.push_front(2);
-    ///
-    /// assert_eq!(buf.as_slices(), (&[2][..], &[0, 1][..]));
-    /// ```
-    #[inline]
-    pub fn as_slices(&self) -> (&[A::Item], &[A::Item]) {
-        unsafe {
-            let (first, second) = (*(self as *const Self as *mut Self)).as_mut_slices();
-            (first, second)
+    #[test]
+    fn test_reserve() {
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[u8; CAP]> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            for len in 0..CAP + 1 {
+                for n in 0..CAP + 2 {
+                    unsafe {
+                        tester.set_tail(padding);
+                        tester.set_len(0);
+                    }
+                    tester.extend_back(10..10 + len as u8);
+                    let tail = if len == 0 { 0 } else { padding };
+                    let head = (tail + len) % CAP;
+
+                    let back_room = if len == CAP {
+                        0
+                    } else if head >= tail {
+                        CAP - head
+                    } else {
+                        tail - head
+                    };
+                    let mut expected: Vec<u8> = tester.iter().cloned().collect();
+                    match tester.reserve_back(n) {
+                        Ok(mut region) => {
+                            assert!(n <= back_room);
+                            assert_eq!(region.len(), n);
+                            assert!(region.iter().all(|&x| x == 0));
+                            for (i, slot) in region.iter_mut().enumerate() {
+                                *slot = i as u8;
+                            }
+                            assert_eq!(
+                                region.commit_back(n + 1),
+                                Err(CapacityError { element: n + 1 })
+                            );
+                        }
+                        Err(err) => {
+                            assert!(n > back_room);
+                            assert_eq!(err, CapacityError { element: n });
+                        }
+                    }
+                    if n <= back_room {
+                        let mut region = tester.reserve_back(n).unwrap();
+                        for (i, slot) in region.iter_mut().enumerate() {
+                            *slot = i as u8;
+                        }
+                        region.commit_back(n).unwrap();
+                        expected.extend(0..n as u8);
+                    }
+                    assert!(tester.iter().eq(&expected));
+
+                    unsafe {
+                        tester.set_tail(padding);
+                        tester.set_len(0);
+                    }
+                    tester.extend_back(10..10 + len as u8);
+                    let free = CAP - len;
+                    let front_room = if tail == 0 {
+                        free
+                    } else {
+                        cmp::min(free, tail)
+                    };
+                    let mut expected: Vec<u8> = tester.iter().cloned().collect();
+                    match tester.reserve_front(n) {
+                        Ok(mut region) => {
+                            assert!(n <= front_room);
+                            for (i, slot) in region.iter_mut().enumerate() {
+                                *slot = i as u8;
+                            }
+                            // only the two slots adjacent to the front
+                            let k = cmp::min(n, 2);
+                            region.commit_front(k).unwrap();
+                            let mut prefix: Vec<u8> = (n - k..n).map(|i| i as u8).collect();
+                            prefix.append(&mut expected);
+                            expected = prefix;
+                        }
+                        Err(err) => {
+                            assert!(n > front_room);
+                            assert_eq!(err, CapacityError { element: n });
+                        }
+                    }
+                    assert!(tester.iter().eq(&expected));
+                }
+            }
         }
     }
 
-    /// Returns a pair of slices which contain, in order, the contents of the
-    /// `ArrayDeque`.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<[_; 7]> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    ///
-    /// assert_eq!(buf.as_mut_slices(), (&mut [0, 1][..], &mut[][..]));
-    ///
-    /// buf.push_front(2);
-    ///
-    /// assert_eq!(buf.as_mut_slices(), (&mut[2][..], &mut[0, 1][..]));
-    /// ```
-    #[inline]
-    pub fn as_mut_slices(&mut self) -> (&mut [A::Item], &mut [A::Item]) {
-        let contiguous = self.is_contiguous();
-        let head = self.head();
-        let tail = self.tail();
-        let buf = self.xs.as_mut_slice();
+    #[test]
+    fn test_hooks() {
+        use std::cell::RefCell;
 
-        if contiguous {
-            let (empty, buf) = buf.split_at_mut(0);
-            (&mut buf[tail..head], empty)
-        } else {
-            let (mid, right) = buf.split_at_mut(tail);
-            let (left, _) = mid.split_at_mut(head);
+        thread_local! {
+            static EVENTS: RefCell<Vec<(&'static str, i32)>> = RefCell::new(Vec::new());
+        }
+
+        struct Record;
+
+        impl Hooks<i32> for Record {
+            fn on_full(element: &i32) {
+                EVENTS.with(|events| events.borrow_mut().push(("full", *element)));
+            }
 
-            (right, left)
+            fn on_evict(element: &i32) {
+                EVENTS.with(|events| events.borrow_mut().push(("evict", *element)));
+            }
         }
-    }
-}
 
-impl<A: Array> From<ArrayDeque<A, Wrapping>> for ArrayDeque<A, Saturating> {
-    fn from(buf: ArrayDeque<A, Wrapping>) -> Self {
-        buf.into_iter().collect()
-    }
-}
+        let mut saturating: ArrayDeque<[_; 2], Saturating<Record>> = ArrayDeque::new();
+        saturating.push_back(1).unwrap();
+        saturating.push_front(0).unwrap();
+        assert!(saturating.push_back(2).is_err());
+        assert!(saturating.push_front(3).is_err());
+        assert!(saturating.insert(1, 4).is_err());
 
-impl<A: Array> From<ArrayDeque<A, Saturating>> for ArrayDeque<A, Wrapping> {
-    fn from(buf: ArrayDeque<A, Saturating>) -> Self {
-        buf.into_iter().collect()
+        let mut wrapping: ArrayDeque<[_; 2], Wrapping<Record>> = ArrayDeque::new();
+        wrapping.extend_back(0..3);
+        wrapping.push_front(5);
+        wrapping.extend_from_slice(&[6]);
+        wrapping.extend_from_slice(&[7, 8, 9]);
+
+        let events = EVENTS.with(|events| events.borrow().clone());
+        assert_eq!(
+            events,
+            vec![
+                ("full", 2),
+                ("full", 3),
+                ("full", 4),
+                ("evict", 0),
+                ("evict", 2),
+                ("evict", 5),
+                ("evict", 1),
+                ("evict", 6),
+                ("evict", 7),
+            ]
+        );
+        assert_eq!(wrapping, vec![8, 9].into());
     }
-}
 
-#[cfg(feature = "std")]
-impl<A: Array, B: Behavior> From<Vec<A::Item>> for ArrayDeque<A, B>
-where
-    Self: FromIterator<A::Item>,
-{
-    fn from(vec: Vec<A::Item>) -> Self {
-        vec.into_iter().collect()
+    #[test]
+    fn test_behavior_values() {
+        // the behaviors without hooks can still be used as values
+        fn behavior_of<A: Array, B: Behavior>(_: &ArrayDeque<A, B>, behavior: B) -> B {
+            behavior
+        }
+
+        let saturating: ArrayDeque<[u8; 1]> = ArrayDeque::new();
+        let wrapping: ArrayDeque<[u8; 1], Wrapping> = ArrayDeque::new();
+        let decimating: ArrayDeque<[u8; 1], Decimating> = ArrayDeque::new();
+        let _: Saturating = behavior_of(&saturating, Saturating);
+        let _: Wrapping = behavior_of(&wrapping, Wrapping);
+        let _: Decimating = behavior_of(&decimating, Decimating);
     }
-}
 
-#[cfg(feature = "std")]
-impl<A: Array, B: Behavior> Into<Vec<A::Item>> for ArrayDeque<A, B>
-where
-    Self: FromIterator<A::Item>,
-{
-    fn into(self) -> Vec<A::Item> {
-        self.into_iter().collect()
+    #[test]
+    fn test_decimating() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP], Decimating> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.extend_back(0..5), 0);
+            assert!(tester.push_back(5));
+            assert_eq!(tester, vec![0, 2, 4, 5].into());
+            assert!(!tester.push_back(6));
+            assert!(tester.push_back(7));
+            assert_eq!(tester, vec![0, 4, 6, 7].into());
+        }
+
+        let mut tester: ArrayDeque<[_; 1], Decimating> = ArrayDeque::new();
+        assert!(!tester.push_back(0));
+        assert!(tester.push_back(1));
+        assert_eq!(tester, vec![1].into());
     }
-}
 
-impl<A: Array, B: Behavior> Drop for ArrayDeque<A, B> {
-    fn drop(&mut self) {
-        self.clear();
+    #[cfg(has_const_generics)]
+    #[test]
+    fn test_layout() {
+        let layout = ArrayDeque::<[u64; 3], Wrapping>::layout();
+        assert_eq!(
+            layout.size,
+            mem::size_of::<ArrayDeque<[u64; 3], Wrapping>>()
+        );
+        assert_eq!(layout.align, mem::align_of::<u64>());
+        assert_eq!(layout.capacity, 3);
+        assert_eq!(layout.element_size, 8);
+        assert_eq!(layout.overhead, layout.size - 24);
+        assert_eq!(ArrayDeque::<[u64; 3], Wrapping>::footprint(), layout.size);
+
+        let layout = ArrayDeque::<[(); 1024]>::layout();
+        assert_eq!(layout.capacity, 1024);
+        assert_eq!(layout.overhead, layout.size);
     }
-}
 
-impl<A: Array, B: Behavior> Default for ArrayDeque<A, B> {
-    #[inline]
-    fn default() -> Self {
-        ArrayDeque::new()
-    }
-}
+    #[cfg(feature = "strict")]
+    #[test]
+    fn test_soft_limit() {
+        use std::cell::Cell;
 
-impl<A: Array, B: Behavior> PartialEq for ArrayDeque<A, B>
-where
-    A::Item: PartialEq,
-{
-    fn eq(&self, other: &Self) -> bool {
-        if self.len() != other.len() {
-            return false;
+        thread_local! {
+            static REPORTED: Cell<usize> = Cell::new(0);
         }
-        let (sa, sb) = self.as_slices();
-        let (oa, ob) = other.as_slices();
-        if sa.len() == oa.len() {
-            sa == oa && sb == ob
-        } else if sa.len() < oa.len() {
-            // Always divisible in three sections, for example:
-            // self:  [a b c|d e f]
-            // other: [0 1 2 3|4 5]
-            // front = 3, mid = 1,
-            // [a b c] == [0 1 2] && [d] == [3] && [e f] == [4 5]
-            let front = sa.len();
-            let mid = oa.len() - front;
 
-            let (oa_front, oa_mid) = oa.split_at(front);
-            let (sb_mid, sb_back) = sb.split_at(mid);
-            debug_assert_eq!(sa.len(), oa_front.len());
-            debug_assert_eq!(sb_mid.len(), oa_mid.len());
-            debug_assert_eq!(sb_back.len(), ob.len());
-            sa == oa_front && sb_mid == oa_mid && sb_back == ob
-        } else {
-            let front = oa.len();
-            let mid = sa.len() - front;
+        struct Limit;
 
-            let (sa_front, sa_mid) = sa.split_at(front);
-            let (ob_mid, ob_back) = ob.split_at(mid);
-            debug_assert_eq!(sa_front.len(), oa.len());
-            debug_assert_eq!(sa_mid.len(), ob_mid.len());
-            debug_assert_eq!(sb.len(), ob_back.len());
-            sa_front == oa && sa_mid == ob_mid && sb == ob_back
+        impl<T> Hooks<T> for Limit {
+            const SOFT_LIMIT: usize = 2;
+
+            fn on_soft_limit(len: usize) {
+                REPORTED.with(|reported| reported.set(len));
+            }
         }
+
+        let mut tester: ArrayDeque<[_; 4], Wrapping<Limit>> = ArrayDeque::new();
+        tester.push_back(0);
+        tester.push_front(1);
+        assert_eq!(REPORTED.with(Cell::get), 0);
+        tester.push_back(2);
+        assert_eq!(REPORTED.with(Cell::get), 3);
+        tester.extend_back(3..6);
+        assert_eq!(REPORTED.with(Cell::get), 4);
     }
-}
 
-impl<A: Array, B: Behavior> Eq for ArrayDeque<A, B> where A::Item: Eq {}
+    #[cfg(feature = "strict")]
+    #[test]
+    #[should_panic]
+    fn test_soft_limit_panics_by_default() {
+        struct Limit;
 
-impl<A: Array, B: Behavior> PartialOrd for ArrayDeque<A, B>
-where
-    A::Item: PartialOrd,
-{
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.iter().partial_cmp(other.iter())
+        impl<T> Hooks<T> for Limit {
+            const SOFT_LIMIT: usize = 1;
+        }
+
+        let mut tester: ArrayDeque<[_; 4], Saturating<Limit>> = ArrayDeque::new();
+        tester.push_back(0).unwrap();
+        tester.insert(0, 1).unwrap();
     }
-}
 
-impl<A: Array, B: Behavior> Ord for ArrayDeque<A, B>
-where
-    A::Item: Ord,
-{
-    #[inline]
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.iter().cmp(other.iter())
+    #[test]
+    fn test_partition_deques() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..5);
+            let (small, large): (ArrayDeque<[_; 3]>, ArrayDeque<[_; 2], Wrapping>) =
+                tester.clone().partition_deques(|&x| x < 3).unwrap();
+            assert_eq!(small, vec![0, 1, 2].into());
+            assert_eq!(large, vec![3, 4].into());
+
+            let (all, none): (ArrayDeque<[_; 5]>, ArrayDeque<[_; 2]>) =
+                tester.clone().partition_deques(|_| true).unwrap();
+            assert_eq!(all, vec![0, 1, 2, 3, 4].into());
+            assert!(none.is_empty());
+
+            let overflow = tester
+                .clone()
+                .partition_deques::<[_; 3], Saturating, [_; 1], Saturating, _>(|&x| x % 2 == 0);
+            assert_eq!(overflow, Err(CapacityError { element: 3 }));
+        }
     }
-}
 
-impl<A: Array, B: Behavior> Hash for ArrayDeque<A, B>
-where
-    A::Item: Hash,
-{
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.len().hash(state);
-        let (a, b) = self.as_slices();
-        Hash::hash_slice(a, state);
-        Hash::hash_slice(b, state);
+    #[cfg(has_const_generics)]
+    #[test]
+    fn test_drain_chunks() {
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[String; CAP]> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back((0..CAP).map(|i| i.to_string()));
+            {
+                let mut chunks = tester.drain_chunks::<2>();
+                assert_eq!(chunks.len(), 3);
+                assert_eq!(chunks.next(), Some([String::from("0"), String::from("1")]));
+                assert_eq!(chunks.len(), 2);
+            }
+            assert_eq!(tester.len(), 5);
+            let rest: Vec<_> = tester.drain_chunks::<2>().collect();
+            assert_eq!(rest.len(), 2);
+            assert_eq!(rest[1], [String::from("4"), String::from("5")]);
+            assert_eq!(tester.front().map(|s| &s[..]), Some("6"));
+            assert_eq!(tester.len(), 1);
+            tester.clear();
+        }
     }
-}
 
-impl<A: Array, B: Behavior> Index<usize> for ArrayDeque<A, B> {
-    type Output = A::Item;
+    #[test]
+    fn test_normalize() {
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[Box<usize>; CAP]> = ArrayDeque::new();
 
-    #[inline]
-    fn index(&self, index: usize) -> &A::Item {
-        let len = self.len();
-        self.get(index)
-            .or_else(|| {
-                panic!(
-                    "index out of bounds: the len is {} but the index is {}",
-                    len, index
-                )
-            }).unwrap()
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_tail(padding);
+                    tester.set_len(0);
+                }
+                tester.extend_back((0..len).map(Box::new));
+                let expected: Vec<_> = (0..len).map(Box::new).collect();
+                assert_eq!(tester.normalize(), &expected[..]);
+                assert!(tester.is_normalized());
+                assert_eq!(tester.as_slices().0, &expected[..]);
+                assert!(tester.iter().eq(expected.iter()));
+                tester.clear();
+            }
+        }
     }
-}
 
-impl<A: Array, B: Behavior> IndexMut<usize> for ArrayDeque<A, B> {
-    #[inline]
-    fn index_mut(&mut self, index: usize) -> &mut A::Item {
-        let len = self.len();
-        self.get_mut(index)
-            .or_else(|| {
-                panic!(
-                    "index out of bounds: the len is {} but the index is {}",
-                    len, index
-                )
-            }).unwrap()
+    #[test]
+    fn test_iter_unordered() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..4);
+            // the element stored at buffer index `i` is `i - padding`
+            let physical = (0..CAP)
+                .map(|i| (i + CAP - padding) % CAP)
+                .filter(|&element| element < 4);
+            assert!(tester.iter_unordered().cloned().eq(physical));
+        }
     }
-}
 
-impl<A: Array, B: Behavior> IntoIterator for ArrayDeque<A, B> {
-    type Item = A::Item;
-    type IntoIter = IntoIter<A, B>;
+    #[test]
+    fn test_push_overwrite() {
+        const CAP: usize = 3;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            for i in 0..CAP {
+                assert_eq!(tester.push_back_overwrite(i), None);
+            }
+            assert_eq!(tester.push_back_overwrite(3), Some(0));
+            assert_eq!(tester.push_front_overwrite(4), Some(3));
+            assert_eq!(tester, vec![4, 1, 2].into());
+        }
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        IntoIter { inner: self }
+    #[test]
+    fn test_try_push() {
+        const CAP: usize = 3;
+        let mut tester: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.try_push_back(String::from("b")), Ok(()));
+            assert_eq!(tester.try_push_front(String::from("a")), Ok(()));
+            assert_eq!(tester.try_insert(2, String::from("c")), Ok(()));
+
+            // the rejected element is handed back, not dropped
+            let err = tester.try_insert(1, String::from("d")).unwrap_err();
+            assert_eq!(err.element, "d");
+            assert_eq!(
+                tester.try_push_back(String::from("e")).unwrap_err().element,
+                "e"
+            );
+            assert_eq!(
+                tester
+                    .try_push_front(String::from("f"))
+                    .unwrap_err()
+                    .element,
+                "f"
+            );
+            assert_eq!(
+                tester,
+                vec!["a", "b", "c"].into_iter().map(String::from).collect()
+            );
+        }
     }
-}
 
-impl<'a, A: Array, B: Behavior> IntoIterator for &'a ArrayDeque<A, B> {
-    type Item = &'a A::Item;
-    type IntoIter = Iter<'a, A::Item>;
+    #[test]
+    fn test_assign() {
+        use std::cell::Cell;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+        thread_local!(static CLONES: Cell<usize> = Cell::new(0));
+
+        #[derive(Debug, PartialEq)]
+        struct Heavy(usize);
+        impl Clone for Heavy {
+            fn clone(&self) -> Heavy {
+                CLONES.with(|c| c.set(c.get() + 1));
+                Heavy(self.0)
+            }
+            fn clone_from(&mut self, source: &Heavy) {
+                self.0 = source.0;
+            }
+        }
+
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back((0..3).map(Heavy));
+            CLONES.with(|c| c.set(0));
+
+            let source: Vec<_> = (10..15).map(Heavy).collect();
+            tester.assign(&source[..2]);
+            assert_eq!(CLONES.with(Cell::get), 0);
+            assert!(tester.iter().eq(&source[..2]));
+
+            tester.assign(&source);
+            assert_eq!(CLONES.with(Cell::get), 2);
+            assert!(tester.iter().eq(&source[..CAP]));
+
+            tester.clear_and_extend((20..23).map(Heavy));
+            assert!(tester.iter().map(|x| x.0).eq(20..23));
+            tester.clear_and_extend((30..40).map(Heavy));
+            assert!(tester.iter().map(|x| x.0).eq(30..34));
+            tester.clear_and_extend(None);
+            assert!(tester.is_empty());
+
+            // no item is pulled past the capacity
+            let mut source = (0..10).map(Heavy);
+            tester.clear_and_extend(&mut source);
+            assert!(tester.iter().map(|x| x.0).eq(0..CAP));
+            assert_eq!(source.next(), Some(Heavy(CAP)));
+
+            let source: Vec<_> = (10..20).map(Heavy).collect();
+            tester.clear_and_extend((0..1).map(Heavy));
+            let mut iter = source.iter();
+            tester.assign(&mut iter);
+            assert!(tester.iter().eq(&source[..CAP]));
+            assert_eq!(iter.next(), Some(&Heavy(10 + CAP)));
+        }
     }
-}
 
-impl<'a, A: Array, B: Behavior> IntoIterator for &'a mut ArrayDeque<A, B> {
-    type Item = &'a mut A::Item;
-    type IntoIter = IterMut<'a, A::Item>;
+    #[test]
+    fn test_from_iter_exact() {
+        let tester: ArrayDeque<[_; 3], Wrapping> =
+            ArrayDeque::from_iter_exact(vec![1, 2, 3]).unwrap();
+        assert!(tester.is_full());
+        assert_eq!(tester, vec![1, 2, 3].into());
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+        let short = ArrayDeque::<[String; 3]>::from_iter_exact(vec![String::from("a")]);
+        assert_eq!(short.unwrap_err(), LengthError::TooFew { len: 1 });
+        let empty = ArrayDeque::<[u8; 3]>::from_iter_exact(None);
+        assert_eq!(empty.unwrap_err(), LengthError::TooFew { len: 0 });
+        let long = ArrayDeque::<[_; 2]>::from_iter_exact(vec!["a", "b", "c", "d"]);
+        assert_eq!(long.unwrap_err(), LengthError::TooMany("c"));
+        assert_eq!(
+            LengthError::<u8>::TooFew { len: 1 }.to_string(),
+            "too few elements, only 1"
+        );
     }
-}
 
-impl<A: Array, B: Behavior> fmt::Debug for ArrayDeque<A, B>
-where
-    A::Item: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_list().entries(self).finish()
+    #[test]
+    fn test_try_from_vec() {
+        let short = ArrayDeque::<[_; 3]>::try_from_vec(vec![1, 2]).unwrap();
+        assert_eq!(short, vec![1, 2].into());
+        let exact = ArrayDeque::<[_; 3], Wrapping>::try_from_vec(vec![1, 2, 3]).unwrap();
+        assert!(exact.is_full());
+        assert!(exact.iter().eq(&[1, 2, 3]));
+        assert_eq!(
+            ArrayDeque::<[_; 3], Wrapping>::try_from_vec(vec![1, 2, 3, 4]),
+            Err(CapacityError {
+                element: vec![1, 2, 3, 4]
+            })
+        );
+        let empty = ArrayDeque::<[String; 0]>::try_from_vec(Vec::new()).unwrap();
+        assert!(empty.is_empty());
     }
-}
 
-#[inline]
-fn wrap_add(index: usize, addend: usize, capacity: usize) -> usize {
-    debug_assert!(addend <= capacity);
-    (index + addend) % capacity
-}
+    #[test]
+    fn test_retain_mut() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..CAP);
+            let mut visited = vec![];
+            tester.retain_mut(|x| {
+                visited.push(*x);
+                *x *= 10;
+                *x % 20 != 0
+            });
+            assert_eq!(visited, vec![0, 1, 2, 3, 4]);
+            assert_eq!(tester, vec![10, 30].into());
+        }
+    }
 
-#[inline]
-fn wrap_sub(index: usize, subtrahend: usize, capacity: usize) -> usize {
-    debug_assert!(subtrahend <= capacity);
-    (index + capacity - subtrahend) % capacity
-}
+    #[test]
+    fn test_make_contiguous() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_tail(padding);
+                    tester.set_len(0);
+                }
+                tester.extend_back(0..len);
+                let wrapped = padding + len > CAP;
+                assert!(tester.make_contiguous().iter().cloned().eq(0..len));
+                assert_eq!(tester.as_slices().1, &[]);
+                // contents which did not wrap stay in place
+                assert_eq!(tester.tail(), if wrapped { 0 } else { padding });
+            }
+        }
+    }
 
-/// `ArrayDeque` iterator
-#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-#[derive(Clone)]
-pub struct Iter<'a, T: 'a> {
-    ring: &'a [T],
-    tail: usize,
-    len: usize,
-}
+    #[cfg(has_const_generics)]
+    #[test]
+    fn test_capacity_for() {
+        assert_eq!(capacity_for(0), 0);
+        assert_eq!(capacity_for(33), 40);
+        assert_eq!(capacity_for(225), 256);
+        for shift in 0..16 {
+            assert_eq!(capacity_for(1 << shift), 1 << shift);
+        }
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
+        let tester: ArrayDeque<array_for!(u16; 300)> = ArrayDeque::new();
+        assert_eq!(tester.capacity(), 384);
+    }
 
-    #[inline]
-    fn next(&mut self) -> Option<&'a T> {
-        if self.len == 0 {
-            return None;
+    #[cfg(has_const_generics)]
+    #[test]
+    #[should_panic]
+    fn test_capacity_for_too_large() {
+        capacity_for((1 << 16) + 1);
+    }
+
+    #[cfg(has_const_generics)]
+    #[test]
+    fn test_const_deque() {
+        const CAP: usize = 257;
+        let mut tester: ConstDeque<usize, CAP, Wrapping> = ConstDeque::new();
+        for padding in (0..CAP).step_by(16) {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.extend_back_counting(0..CAP + 3), 3);
+            assert!(tester.iter().cloned().eq(3..CAP + 3));
+            assert_eq!(tester.pop_front(), Some(3));
+            tester.push_front(0);
+            assert_eq!(tester[0], 0);
+            assert_eq!(tester.len(), CAP);
         }
-        let tail = self.tail;
-        self.tail = wrap_add(self.tail, 1, self.ring.len());
-        self.len -= 1;
-        unsafe { Some(self.ring.get_unchecked(tail)) }
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+    #[test]
+    fn test_collect_into() {
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.collect_into(None), Ok(0));
+            assert_eq!(tester.collect_into(vec![0, 1]), Ok(2));
+            assert_eq!(tester.collect_into(vec![2, 3]), Ok(2));
+            let err = tester.collect_into(vec![4]).unwrap_err();
+            assert_eq!(err.element, 4);
+            assert!(tester.iter().cloned().eq(0..4));
+        }
     }
-}
 
-impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
-    #[inline]
-    fn next_back(&mut self) -> Option<&'a T> {
-        if self.len == 0 {
-            return None;
+    #[test]
+    fn test_transfer_front() {
+        const CAP: usize = 5;
+        let mut src: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        let mut dst: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for src_padding in 0..CAP {
+            for dst_padding in 0..CAP {
+                unsafe {
+                    src.set_tail(src_padding);
+                    src.set_len(0);
+                    dst.set_tail(dst_padding);
+                    dst.set_len(0);
+                }
+                src.extend_back(0..4);
+                dst.extend_back(vec![10, 11]);
+                src.transfer_front(&mut dst, 3).unwrap();
+                assert_eq!(
+                    src.transfer_front(&mut dst, 1),
+                    Err(CapacityError { element: () })
+                );
+                assert_eq!(src, vec![3].into());
+                assert_eq!(dst, vec![10, 11, 0, 1, 2].into());
+                src.transfer_front(&mut dst, 0).unwrap();
+            }
         }
-        self.len -= 1;
-        let head = wrap_add(self.tail, self.len, self.ring.len());
-        unsafe { Some(self.ring.get_unchecked(head)) }
     }
-}
-
-impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
 
-/// `ArrayDeque` mutable iterator
-#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct IterMut<'a, T: 'a> {
-    ring: &'a mut [T],
-    tail: usize,
-    len: usize,
-}
+    #[test]
+    fn test_full_capacity() {
+        let mut tester: ArrayDeque<[u8; 64]> = ArrayDeque::new();
+        tester.extend_back(0..100);
+        assert_eq!(tester.len(), 64);
+        assert_eq!(tester.capacity(), 64);
+        assert!(tester.iter().cloned().eq(0..64));
+
+        let mut empty: ArrayDeque<[String; 0]> = ArrayDeque::new();
+        assert_eq!(empty.capacity(), 0);
+        assert!(empty.is_full());
+        assert!(empty.push_back(String::from("a")).is_err());
+        assert!(empty.push_front(String::from("b")).is_err());
+        empty.extend_back(vec![String::from("c")]);
+        assert_eq!(empty.iter().count(), 0);
+        assert_eq!(empty.pop_front(), None);
+        empty.clear();
+
+        let mut wrapping: ArrayDeque<[u8; 0], Wrapping> = ArrayDeque::new();
+        assert_eq!(wrapping.push_back(1), Some(1));
+        assert_eq!(wrapping.extend_front_counting(0..3), 3);
+        assert_eq!(wrapping.drain(..).count(), 0);
+    }
 
-impl<'a, T> Iterator for IterMut<'a, T> {
-    type Item = &'a mut T;
+    #[test]
+    fn test_into_iter_refs() {
+        fn sum<'a, I: IntoIterator<Item = &'a usize>>(iter: I) -> usize {
+            iter.into_iter().sum()
+        }
 
-    #[inline]
-    fn next(&mut self) -> Option<&'a mut T> {
-        if self.len == 0 {
-            return None;
+        fn double<'a, I: IntoIterator<Item = &'a mut usize>>(iter: I) {
+            for x in iter {
+                *x *= 2;
+            }
         }
-        let tail = self.tail;
-        self.tail = wrap_add(self.tail, 1, self.ring.len());
-        self.len -= 1;
-        unsafe {
-            let elem = self.ring.get_unchecked_mut(tail);
-            Some(&mut *(elem as *mut _))
+
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..CAP + 1);
+            assert_eq!(sum(&tester), 1 + 2 + 3 + 4);
+            double(&mut tester);
+            assert_eq!(sum(&tester), 2 + 4 + 6 + 8);
+
+            let mut seen = vec![];
+            for x in &tester {
+                seen.push(*x);
+            }
+            assert_eq!(seen, [2, 4, 6, 8]);
         }
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
-    }
-}
+    #[test]
+    fn test_hash_ord_split() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::{BTreeSet, HashSet};
 
-impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
-    #[inline]
-    fn next_back(&mut self) -> Option<&'a mut T> {
-        if self.len == 0 {
-            return None;
+        /// Mixes each `write` as one unit, like most fast hashers.
+        #[derive(Default)]
+        struct ChunkHasher(u64);
+
+        impl Hasher for ChunkHasher {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                let chunk = bytes.iter().fold(0u64, |acc, &b| acc << 8 | u64::from(b));
+                self.0 = (self.0 ^ chunk).wrapping_mul(0x100_0000_01b3);
+            }
         }
-        self.len -= 1;
-        let head = wrap_add(self.tail, self.len, self.ring.len());
-        unsafe {
-            let elem = self.ring.get_unchecked_mut(head);
-            Some(&mut *(elem as *mut _))
+
+        fn hash<T: Hash, H: Hasher + Default>(value: &T) -> u64 {
+            let mut state = H::default();
+            value.hash(&mut state);
+            state.finish()
         }
+
+        const CAP: usize = 6;
+        let mut hashes = HashSet::new();
+        let mut chunk_hashes = HashSet::new();
+        let mut keys = BTreeSet::new();
+        for padding in 0..CAP {
+            let mut tester: ArrayDeque<[u8; CAP]> = ArrayDeque::new();
+            unsafe { tester.set_tail(padding) }
+            tester.extend_back(1..5);
+            hashes.insert(hash::<_, DefaultHasher>(&tester));
+            chunk_hashes.insert(hash::<_, ChunkHasher>(&tester));
+
+            let mut smaller = tester.clone();
+            *smaller.back_mut().unwrap() -= 1;
+            assert!(smaller < tester);
+            assert_eq!(tester.cmp(&tester.clone()), Ordering::Equal);
+            keys.insert(tester);
+        }
+        assert_eq!(hashes.len(), 1);
+        assert_eq!(chunk_hashes.len(), 1);
+        assert_eq!(keys.len(), 1);
     }
-}
 
-impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+    #[test]
+    fn test_push_back_pinned() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            for pinned in 0..CAP + 1 {
+                tester.clear();
+                tester.extend_back(0..CAP);
+                let mut expected: Vec<_> = (0..CAP).collect();
+                for element in CAP..3 * CAP {
+                    let existing = tester.push_back_pinned(pinned, element);
+                    if pinned < CAP {
+                        assert_eq!(existing, Some(expected.remove(pinned)));
+                        expected.push(element);
+                    } else {
+                        assert_eq!(existing, Some(element));
+                    }
+                    assert!(tester.iter().eq(&expected));
+                }
+            }
+        }
 
-/// By-value `ArrayDeque` iterator
-#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct IntoIter<A: Array, B: Behavior> {
-    inner: ArrayDeque<A, B>,
-}
+        let mut empty: ArrayDeque<[_; 0], Wrapping> = ArrayDeque::new();
+        assert_eq!(empty.push_back_pinned(0, 1), Some(1));
+    }
 
-impl<A: Array, B: Behavior> Iterator for IntoIter<A, B> {
-    type Item = A::Item;
+    #[test]
+    fn test_pinned_hooks() {
+        use std::cell::RefCell;
 
-    #[inline]
-    fn next(&mut self) -> Option<A::Item> {
-        self.inner.pop_front()
-    }
+        thread_local! {
+            static REJECTED: RefCell<Vec<usize>> = RefCell::new(Vec::new());
+        }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.inner.len();
-        (len, Some(len))
-    }
-}
+        struct Header;
 
-impl<A: Array, B: Behavior> DoubleEndedIterator for IntoIter<A, B> {
-    #[inline]
-    fn next_back(&mut self) -> Option<A::Item> {
-        self.inner.pop_back()
-    }
-}
+        impl Hooks<usize> for Header {
+            const PINNED: usize = 2;
 
-impl<A: Array, B: Behavior> ExactSizeIterator for IntoIter<A, B> {}
+            fn on_full(element: &usize) {
+                REJECTED.with(|rejected| rejected.borrow_mut().push(*element));
+            }
+        }
 
-/// Draining `ArrayDeque` iterator
-pub struct Drain<'a, A, B>
-where
-    A: Array,
-    A::Item: 'a,
-    B: Behavior,
-{
-    after_tail: usize,
-    after_len: usize,
-    iter: Iter<'a, A::Item>,
-    deque: *mut ArrayDeque<A, B>,
-}
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP], Wrapping<Header>> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..CAP);
+            assert_eq!(tester.push_back(5), Some(2));
+            tester.extend(6..8);
+            assert_eq!(tester, vec![0, 1, 5, 6, 7].into());
+            assert_eq!(tester.push_front(8), Some(7));
+            assert_eq!(tester, vec![0, 1, 8, 5, 6].into());
+            tester.extend_front(9..11);
+            assert_eq!(tester, vec![0, 1, 10, 9, 8].into());
+            assert_eq!(tester.push_back_pinned(0, 11), Some(10));
+            assert_eq!(tester, vec![0, 1, 9, 8, 11].into());
+            tester.extend_from_slice(&[12, 13]);
+            assert_eq!(tester, vec![0, 1, 11, 12, 13].into());
+            tester.clear();
+        }
 
-impl<'a, A, B> Drop for Drain<'a, A, B>
-where
-    A: Array,
-    A::Item: 'a,
-    B: Behavior,
-{
-    fn drop(&mut self) {
-        for _ in self.by_ref() {}
+        // fewer elements than pinned ones
+        tester.push_back(0);
+        tester.push_front(1);
+        assert_eq!(tester, vec![0, 1].into());
 
-        let source_deque = unsafe { &mut *self.deque };
+        let mut headers: ArrayDeque<[_; 2], Wrapping<Header>> = ArrayDeque::new();
+        headers.extend_back(0..2);
+        assert_eq!(headers.push_back(2), Some(2));
+        assert_eq!(headers.push_front(3), Some(3));
+        assert_eq!(headers, vec![0, 1].into());
+        assert_eq!(
+            REJECTED.with(|rejected| rejected.borrow().clone()),
+            vec![2, 3]
+        );
+    }
 
-        let tail_len = source_deque.len();
-        let head_len = self.after_len;
+    #[test]
+    fn test_extend_from_slice() {
+        const CAP: usize = 7;
+        let source: Vec<usize> = (0..CAP + 2).collect();
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            for len in 0..CAP + 1 {
+                for n in 0..source.len() {
+                    unsafe {
+                        tester.set_tail(padding);
+                        tester.set_len(0);
+                    }
+                    tester.extend_back(100..100 + len);
+                    let mut expected: Vec<_> = tester.iter().cloned().collect();
+
+                    // a clone starts at slot 0, so pad the copies like `tester`
+                    let padded = || {
+                        let mut copy: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+                        unsafe {
+                            copy.set_tail(padding);
+                            copy.set_len(0);
+                        }
+                        copy.extend_back(100..100 + len);
+                        copy
+                    };
+                    let mut copy = padded();
+                    let fits = n <= CAP - len;
+                    assert_eq!(copy.try_extend_from_slice(&source[..n]).is_ok(), fits);
+                    if !fits {
+                        assert_eq!(copy, tester);
+                    }
 
-        let orig_tail = source_deque.tail();
-        let drain_tail = wrap_add(orig_tail, tail_len, A::capacity());
-        let drain_head = self.after_tail;
-        let orig_head = wrap_add(drain_head, head_len, A::capacity());
-        let orig_len = wrap_sub(orig_head, orig_tail, A::capacity());
+                    let mut memcpy = padded();
+                    assert_eq!(memcpy.extend_from_copy_slice(&source[..n]).is_ok(), fits);
+                    assert_eq!(memcpy, copy);
+                    if fits {
+                        assert!(memcpy.iter().eq(expected.iter().chain(&source[..n])));
+                    }
+                    if fits && n > 0 && padding + len < CAP && padding + len + n > CAP {
+                        // copied in two segments, across the wrap point
+                        assert!(!memcpy.as_slices().1.is_empty());
+                    }
 
-        // Restore the original len value
-        unsafe { source_deque.set_len(orig_len) }
-        match (tail_len, head_len) {
-            (0, 0) => unsafe {
-                source_deque.set_tail(0);
-                source_deque.set_len(0);
-            },
-            (0, _) => unsafe {
-                source_deque.set_tail(drain_head);
-                source_deque.set_len(head_len);
-            },
-            (_, 0) => unsafe { source_deque.set_len(tail_len) },
-            _ => unsafe {
-                if tail_len <= head_len {
-                    let new_tail = wrap_sub(drain_head, tail_len, A::capacity());
-                    source_deque.set_tail(new_tail);
-                    source_deque.set_len(tail_len + head_len);
-                    source_deque.wrap_copy(new_tail, orig_tail, tail_len);
-                } else {
-                    source_deque.set_len(tail_len + head_len);
-                    source_deque.wrap_copy(drain_tail, drain_head, head_len);
+                    let mut wrapping: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+                    unsafe {
+                        wrapping.set_tail(padding);
+                        wrapping.set_len(0);
+                    }
+                    wrapping.extend_back(100..100 + len);
+                    wrapping.extend_from_slice(&source[..n]);
+                    expected.extend_from_slice(&source[..n]);
+                    let skip = expected.len().saturating_sub(CAP);
+                    assert!(wrapping.iter().eq(&expected[skip..]));
                 }
-            },
+            }
         }
+
+        let strings: Vec<String> = (0..3).map(|x| x.to_string()).collect();
+        let mut saturating: ArrayDeque<[String; 4]> = ArrayDeque::new();
+        saturating.try_extend_from_slice(&strings).unwrap();
+        assert!(saturating.try_extend_from_slice(&strings).is_err());
+        let mut wrapping: ArrayDeque<[String; 4], Wrapping> = ArrayDeque::new();
+        wrapping.extend_from_slice(&strings);
+        wrapping.extend_from_slice(&strings);
+        assert_eq!(saturating.iter().collect::<Vec<_>>(), vec!["0", "1", "2"]);
+        assert_eq!(
+            wrapping.iter().collect::<Vec<_>>(),
+            vec!["2", "0", "1", "2"]
+        );
     }
-}
 
-impl<'a, A, B> Iterator for Drain<'a, A, B>
-where
-    A: Array,
-    A::Item: 'a,
-    B: Behavior,
-{
-    type Item = A::Item;
+    #[test]
+    fn test_rotate() {
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            for len in 0..CAP + 1 {
+                for n in 0..len + 1 {
+                    unsafe {
+                        tester.set_tail(padding);
+                        tester.set_len(0);
+                    }
+                    tester.extend_back((0..len).map(|x| x.to_string()));
+                    let mut expected: Vec<_> = tester.iter().cloned().collect();
+
+                    tester.rotate_left(n);
+                    expected.rotate_left(n);
+                    assert!(tester.iter().eq(&expected));
+                    if len == CAP {
+                        assert_eq!(tester.tail(), (padding + n) % CAP);
+                    }
 
-    #[inline]
-    fn next(&mut self) -> Option<A::Item> {
-        self.iter.next().map(|elt| unsafe { ptr::read(elt) })
+                    tester.rotate_right(n);
+                    expected.rotate_right(n);
+                    assert!(tester.iter().eq(&expected));
+
+                    tester.rotate_right(n);
+                    expected.rotate_right(n);
+                    assert!(tester.iter().eq(&expected));
+                }
+            }
+        }
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+    #[test]
+    #[should_panic]
+    fn test_rotate_too_far() {
+        let mut tester: ArrayDeque<[_; 4]> = (0..2).collect();
+        tester.rotate_left(3);
     }
-}
 
-impl<'a, A, B> DoubleEndedIterator for Drain<'a, A, B>
-where
-    A: Array,
-    A::Item: 'a,
-    B: Behavior,
-{
-    #[inline]
-    fn next_back(&mut self) -> Option<A::Item> {
-        self.iter.next_back().map(|elt| unsafe { ptr::read(elt) })
+    #[test]
+    fn test_binary_search() {
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            for len in 0..CAP + 1 {
+                unsafe {
+                    tester.set_tail(padding);
+                    tester.set_len(0);
+                }
+                tester.extend_back((0..len).map(|x| 2 * x));
+                for x in 0..2 * CAP + 1 {
+                    let expected = if x % 2 == 0 && x / 2 < len {
+                        Ok(x / 2)
+                    } else {
+                        Err(cmp::min(x / 2 + x % 2, len))
+                    };
+                    assert_eq!(tester.binary_search(&x), expected);
+                    assert_eq!(tester.binary_search_by(|y| y.cmp(&x)), expected);
+                    assert_eq!(tester.binary_search_by_key(&(x * 3), |y| y * 3), expected);
+                    assert_eq!(
+                        tester.partition_point(|&y| y < x),
+                        expected.unwrap_or_else(|i| i)
+                    );
+                }
+            }
+        }
     }
-}
 
-impl<'a, A, B> ExactSizeIterator for Drain<'a, A, B>
-where
-    A: Array,
-    A::Item: 'a,
-    B: Behavior,
-{}
+    #[test]
+    fn test_slices_iter() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            for len in 0..CAP + 1 {
+                unsafe {
+                    tester.set_tail(padding);
+                    tester.set_len(0);
+                }
+                tester.extend_back(0..len);
+                let (front, back) = tester.as_slices();
+                let slices: Vec<_> = tester.slices_iter().collect();
+                assert_eq!(slices[0], front);
+                assert_eq!(slices.len(), if back.is_empty() { 1 } else { 2 });
+                assert!(slices.iter().flat_map(|s| s.iter()).eq(tester.iter()));
+
+                for slice in tester.slices_iter_mut() {
+                    for x in slice.iter_mut() {
+                        *x += 10;
+                    }
+                }
+                assert!(tester.iter().cloned().eq(10..10 + len));
+            }
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    #![allow(unused_must_use)]
-    use super::*;
+    #[test]
+    fn test_swap() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..CAP);
+            tester.swap(0, CAP - 1);
+            tester.swap(1, 1);
+            tester.swap(3, 1);
+            assert_eq!(tester, vec![4, 3, 2, 1, 0].into());
+        }
+    }
 
     #[test]
-    fn test_simple() {
-        macro_rules! test {
-            ($behavior:ident) => {{
-                let mut tester: ArrayDeque<[_; 7], $behavior> = ArrayDeque::new();
-                assert_eq!(tester.capacity(), 7);
-                assert_eq!(tester.len(), 0);
+    #[should_panic]
+    fn test_swap_out_of_bounds() {
+        let mut tester: ArrayDeque<[_; 3]> = ArrayDeque::new();
+        tester.extend_back(0..2);
+        tester.swap(0, 2);
+    }
 
-                tester.push_back(1);
-                tester.push_back(2);
-                tester.push_back(3);
-                tester.push_back(4);
-                assert_eq!(tester.len(), 4);
-                assert_eq!(tester.pop_front(), Some(1));
-                assert_eq!(tester.pop_front(), Some(2));
-                assert_eq!(tester.len(), 2);
-                assert_eq!(tester.pop_front(), Some(3));
-                assert_eq!(tester.pop_front(), Some(4));
-                assert_eq!(tester.pop_front(), None);
-            }};
-        }
+    #[test]
+    fn test_with_len() {
+        let tester: ArrayDeque<[String; 3], Wrapping> = ArrayDeque::with_len(3).unwrap();
+        assert!(tester.is_full());
+        assert!(tester.iter().all(String::is_empty));
 
-        test!(Saturating);
-        test!(Wrapping);
+        let empty: ArrayDeque<[u8; 0]> = ArrayDeque::with_len(0).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(
+            ArrayDeque::<[u8; 0]>::with_len(1).unwrap_err(),
+            CapacityError { element: 1 }
+        );
     }
 
     #[test]
-    fn test_simple_reversely() {
-        macro_rules! test {
-            ($behavior:ident) => {{
-                let mut tester: ArrayDeque<[_; 7], $behavior> = ArrayDeque::new();
-                assert_eq!(tester.capacity(), 7);
-                assert_eq!(tester.len(), 0);
+    fn test_resize() {
+        use std::rc::Rc;
 
-                tester.push_front(1);
-                tester.push_front(2);
-                tester.push_front(3);
-                tester.push_front(4);
-                assert_eq!(tester.len(), 4);
-                assert_eq!(tester.pop_back(), Some(1));
-                assert_eq!(tester.pop_back(), Some(2));
-                assert_eq!(tester.len(), 2);
-                assert_eq!(tester.pop_back(), Some(3));
-                assert_eq!(tester.pop_back(), Some(4));
-                assert_eq!(tester.pop_back(), None);
-            }};
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.resize(2, Rc::new(1));
+            tester.resize(4, Rc::new(2));
+            assert_eq!(
+                tester,
+                vec![Rc::new(1), Rc::new(1), Rc::new(2), Rc::new(2)].into()
+            );
+
+            let shared = Rc::new(3);
+            tester.resize(CAP + 1, shared.clone());
+            assert!(tester.is_full());
+            assert_eq!(Rc::strong_count(&shared), 2);
+            assert_eq!(
+                tester.try_resize(CAP + 1, shared.clone()),
+                Err(CapacityError {
+                    element: shared.clone()
+                })
+            );
+
+            // truncating drops the elements at the back
+            tester.resize_with(1, || unreachable!());
+            assert_eq!(Rc::strong_count(&shared), 1);
+            assert_eq!(tester, vec![Rc::new(1)].into());
+            assert_eq!(tester.try_resize_with(0, || unreachable!()), Ok(()));
+            assert!(tester.is_empty());
         }
-
-        test!(Saturating);
-        test!(Wrapping);
     }
 
     #[test]
-    fn test_overflow_saturating() {
-        let mut tester: ArrayDeque<[_; 2], Saturating> = ArrayDeque::new();
-        assert_eq!(tester.push_back(1), Ok(()));
-        assert_eq!(tester.push_back(2), Ok(()));
-        assert_eq!(tester.push_back(3), Err(CapacityError { element: 3 }));
+    fn test_drain_order() {
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..CAP);
+            assert!(tester.drain(1..5).eq(1..5));
+            assert_eq!(tester, vec![0, 5].into());
 
-        let mut tester: ArrayDeque<[_; 2], Saturating> = ArrayDeque::new();
-        assert_eq!(tester.insert(0, 1), Ok(()));
-        assert_eq!(tester.insert(1, 2), Ok(()));
-        assert_eq!(tester.insert(2, 3), Err(CapacityError { element: 3 }));
+            tester.clear();
+            tester.extend_back(0..CAP);
+            {
+                let mut drain = tester.drain_back(..4);
+                assert_eq!(drain.next(), Some(3));
+                assert_eq!(drain.next_back(), Some(0));
+                assert_eq!(drain.len(), 2);
+            }
+            assert_eq!(tester, vec![4, 5].into());
+        }
     }
 
+    #[cfg(has_const_generics)]
     #[test]
-    fn test_overflow_wrapping() {
-        let mut tester: ArrayDeque<[_; 2], Wrapping> = ArrayDeque::new();
-        assert_eq!(tester.push_back(1), None);
-        assert_eq!(tester.push_back(2), None);
-        assert_eq!(tester.push_back(3), Some(1));
+    fn test_from_array() {
+        let tester: ArrayDeque<[String; 2], Wrapping> = ["a".to_string(), "b".to_string()].into();
+        assert!(tester.is_full());
+        assert_eq!(tester, vec!["a".to_string(), "b".to_string()].into());
     }
 
+    #[cfg(has_try_from)]
     #[test]
-    fn test_pop_empty() {
-        let mut tester: ArrayDeque<[_; 2]> = ArrayDeque::new();
-        assert_eq!(tester.push_back(1), Ok(()));
-        assert_eq!(tester.pop_front(), Some(1));
-        assert_eq!(tester.is_empty(), true);
-        assert_eq!(tester.len(), 0);
-        assert_eq!(tester.pop_front(), None);
+    fn test_try_from_slice() {
+        let slice: &[u8] = b"abc";
+        let saturating = ArrayDeque::<[u8; 3]>::try_from(slice).unwrap();
+        assert_eq!(saturating, b"abc".to_vec().into());
+        let wrapping = ArrayDeque::<[u8; 2], Wrapping>::try_from(slice);
+        assert_eq!(wrapping.unwrap_err(), CapacityError { element: slice });
+
+        let strings = ["a".to_string()];
+        let cloned = ArrayDeque::<[String; 2]>::try_from(&strings[..]).unwrap();
+        assert_eq!(cloned.front(), Some(&strings[0]));
     }
 
     #[test]
-    fn test_index() {
-        let mut tester: ArrayDeque<[_; 3]> = ArrayDeque::new();
-        tester.push_back(1);
-        tester.push_back(2);
-        tester.push_back(3);
-        assert_eq!(tester[0], 1);
-        // pop_front 1 <- [2, 3]
-        assert_eq!(tester.pop_front(), Some(1));
-        assert_eq!(tester[0], 2);
-        assert_eq!(tester.len(), 2);
-        // push_front 0 -> [0, 2, 3]
-        tester.push_front(0);
-        assert_eq!(tester[0], 0);
-        // [0, 2] -> 3 pop_back
-        assert_eq!(tester.pop_back(), Some(3));
-        assert_eq!(tester[1], 2);
+    fn test_clone_front_back() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back((0..4).map(|i| i.to_string()));
+            for n in 0..CAP + 1 {
+                let front = tester.clone_front(n);
+                let back = tester.clone_back(n);
+                assert!(front.iter().eq(tester.iter().take(n)));
+                assert!(back.iter().eq(tester.iter().skip(4 - cmp::min(n, 4))));
+            }
+        }
     }
 
     #[test]
-    #[should_panic]
-    fn test_index_overflow() {
-        let mut tester: ArrayDeque<[_; 3]> = ArrayDeque::new();
-        tester.push_back(1);
-        tester.push_back(2);
-        tester[2];
+    fn test_into_vec() {
+        use std::rc::Rc;
+
+        const CAP: usize = 5;
+        let shared = Rc::new(0);
+        for padding in 0..CAP {
+            let mut tester: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back((0..4).map(|i| (i, shared.clone())));
+            let cloned = tester.clone();
+
+            let vec: Vec<_> = tester.into();
+            assert!(vec.iter().map(|x| x.0).eq(0..4));
+            let vecdeque: VecDeque<_> = cloned.into();
+            assert!(vecdeque.iter().eq(vec.iter()));
+            assert_eq!(Rc::strong_count(&shared), 9);
+        }
+        assert_eq!(Rc::strong_count(&shared), 1);
     }
 
     #[test]
-    fn test_iter() {
-        let mut tester: ArrayDeque<[_; 2]> = ArrayDeque::new();
-        tester.push_back(1);
-        tester.push_back(2);
-        {
-            let mut iter = tester.iter();
-            assert_eq!(iter.size_hint(), (2, Some(2)));
-            assert_eq!(iter.next(), Some(&1));
-            assert_eq!(iter.next(), Some(&2));
-            assert_eq!(iter.next(), None);
-            assert_eq!(iter.size_hint(), (0, Some(0)));
+    fn test_spare_slots() {
+        assert_ram_budget!(ArrayDeque<[(u16, u8); 8]>, 40);
+
+        let mut tester: ArrayDeque<[(u16, u8); 8], Wrapping> = ArrayDeque::new();
+        assert_eq!(ArrayDeque::<[(u16, u8); 8]>::slot_size(), 4);
+        for i in 0..10 {
+            assert_eq!(tester.spare_slots(), 8 - cmp::min(i, 8));
+            tester.push_back((i as u16, 0));
         }
-        tester.pop_front();
-        tester.push_back(3);
-        {
-            let mut iter = (&tester).into_iter();
-            assert_eq!(iter.next(), Some(&2));
+        assert_eq!(tester.spare_slots(), 0);
 
-            // test clone
-            let mut iter2 = iter.clone();
-            assert_eq!(iter.next(), Some(&3));
-            assert_eq!(iter.next(), None);
-            assert_eq!(iter2.next(), Some(&3));
-            assert_eq!(iter2.next(), None);
-        }
+        let empty: ArrayDeque<[u8; 0]> = ArrayDeque::new();
+        assert_eq!(empty.spare_slots(), 0);
     }
 
     #[test]
-    fn test_iter_mut() {
-        let mut tester: ArrayDeque<[_; 2]> = ArrayDeque::new();
-        tester.push_back(1);
-        tester.push_back(2);
-        {
-            let mut iter = tester.iter_mut();
-            assert_eq!(iter.size_hint(), (2, Some(2)));
-            assert_eq!(iter.next(), Some(&mut 1));
-            assert_eq!(iter.next(), Some(&mut 2));
-            assert_eq!(iter.next(), None);
-            assert_eq!(iter.size_hint(), (0, Some(0)));
-        }
-        tester.pop_front();
-        tester.push_back(3);
-        {
-            let mut iter = (&mut tester).into_iter();
-            assert_eq!(iter.next(), Some(&mut 2));
-            assert_eq!(iter.next(), Some(&mut 3));
-            assert_eq!(iter.next(), None);
-        }
-        {
-            // mutation
-            let mut iter = tester.iter_mut();
-            iter.next().map(|n| *n += 1);
-            iter.next().map(|n| *n += 2);
+    fn test_starts_ends_with() {
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        let expected: Vec<_> = (0..5).collect();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..5);
+            for n in 0..6 {
+                assert!(tester.starts_with(&expected[..n]));
+                assert!(tester.ends_with(&expected[5 - n..]));
+            }
+            assert!(!tester.starts_with(&[0, 1, 3]));
+            assert!(!tester.ends_with(&[1, 3, 4]));
+            assert!(!tester.starts_with(&[0, 1, 2, 3, 4, 5]));
+            assert!(!tester.ends_with(&[0, 0, 1, 2, 3, 4]));
         }
-        assert_eq!(tester[0], 3);
-        assert_eq!(tester[1], 5);
     }
 
     #[test]
-    fn test_into_iter() {
-        #[derive(Eq, PartialEq, Debug)]
-        struct NoCopy<T>(T);
+    fn test_iter_specializations() {
+        fn assert_traits<I: DoubleEndedIterator + ExactSizeIterator + FusedIterator>(_: &I) {}
 
-        {
-            let mut tester: ArrayDeque<[NoCopy<u8>; 3]> = ArrayDeque::new();
-            tester.push_back(NoCopy(1));
-            tester.push_back(NoCopy(2));
-            let mut iter = tester.into_iter();
-            assert_eq!(iter.size_hint(), (2, Some(2)));
-            assert_eq!(iter.next(), Some(NoCopy(1)));
-            assert_eq!(iter.next(), Some(NoCopy(2)));
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..4);
+            assert_traits(&tester.iter());
+            assert_traits(&tester.iter_mut());
+
+            assert_eq!(tester.iter().count(), 4);
+            assert_eq!(tester.iter().last(), Some(&3));
+            assert_eq!(tester.iter_mut().last(), Some(&mut 3));
+            for n in 0..6 {
+                let mut iter = tester.iter();
+                assert_eq!(iter.nth(n), (0..4).nth(n).as_ref());
+                assert_eq!(iter.len(), 3 - cmp::min(n, 3));
+                let mut iter_mut = tester.iter_mut();
+                assert_eq!(iter_mut.nth(n).cloned(), (0..4).nth(n));
+                assert_eq!(iter_mut.count(), 3 - cmp::min(n, 3));
+            }
+
+            assert_traits(&tester.drain(..0));
+            let mut iter = tester.clone().into_iter();
+            assert_traits(&iter);
+            assert!(iter.by_ref().eq(0..4));
             assert_eq!(iter.next(), None);
-            assert_eq!(iter.size_hint(), (0, Some(0)));
         }
-        {
-            let mut tester: ArrayDeque<[NoCopy<u8>; 3]> = ArrayDeque::new();
-            tester.push_back(NoCopy(1));
-            tester.push_back(NoCopy(2));
-            tester.pop_front();
-            tester.push_back(NoCopy(3));
-            let mut iter = tester.into_iter();
-            assert_eq!(iter.next(), Some(NoCopy(2)));
-            assert_eq!(iter.next(), Some(NoCopy(3)));
-            assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_copy_rotated_into() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..4);
+            for start in 0..5 {
+                let mut dest = [9; 4];
+                tester.copy_rotated_into(&mut dest, start);
+                let mut expected: Vec<_> = (0..4).collect();
+                expected.rotate_left(start % 4);
+                assert_eq!(&dest[..], &expected[..]);
+            }
+            assert_eq!(tester, vec![0, 1, 2, 3].into());
         }
-        {
-            let mut tester: ArrayDeque<[NoCopy<u8>; 3]> = ArrayDeque::new();
-            tester.push_back(NoCopy(1));
-            tester.push_back(NoCopy(2));
-            tester.pop_front();
-            tester.push_back(NoCopy(3));
-            tester.pop_front();
-            tester.push_back(NoCopy(4));
-            let mut iter = tester.into_iter();
-            assert_eq!(iter.next(), Some(NoCopy(3)));
-            assert_eq!(iter.next(), Some(NoCopy(4)));
-            assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_copy_rotated_into_wrong_len() {
+        let mut tester: ArrayDeque<[_; 3]> = ArrayDeque::new();
+        tester.extend_back(0..2);
+        tester.copy_rotated_into(&mut [0; 3], 0);
+    }
+
+    #[test]
+    fn test_range() {
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..5);
+            for start in 0..6 {
+                for end in start..6 {
+                    assert!(tester
+                        .range(start..end)
+                        .eq((start..end).collect::<Vec<_>>().iter()));
+                    assert!(tester
+                        .range_back(start..end)
+                        .cloned()
+                        .eq((start..end).rev()));
+                    let rfolded = tester.range_back(start..end).fold(vec![], |mut v, &x| {
+                        v.push(x);
+                        v
+                    });
+                    assert_eq!(rfolded, (start..end).rev().collect::<Vec<_>>());
+                }
+            }
+            assert_eq!(tester.range(..).len(), 5);
+
+            for x in tester.range_mut(1..4) {
+                *x += 10;
+            }
+            assert_eq!(tester, vec![0, 11, 12, 13, 4].into());
         }
     }
 
+    #[test]
+    #[should_panic]
+    fn test_range_too_long() {
+        let mut tester: ArrayDeque<[_; 3]> = ArrayDeque::new();
+        tester.extend_back(0..2);
+        tester.range(1..3);
+    }
+
     #[test]
     fn test_drain() {
         const CAP: usize = 8;
@@ -2602,6 +8397,143 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_drain_panicking_drop() {
+        use std::cell::Cell;
+        use std::panic;
+
+        thread_local!(static DROPS: Cell<usize> = Cell::new(0));
+
+        struct D(usize);
+        impl Drop for D {
+            fn drop(&mut self) {
+                DROPS.with(|d| d.set(d.get() + 1));
+                if self.0 == 2 {
+                    panic!("drop failed");
+                }
+            }
+        }
+
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back((0..CAP).map(D));
+            DROPS.with(|d| d.set(0));
+
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                tester.drain(1..4);
+            }));
+            assert!(result.is_err());
+            assert_eq!(DROPS.with(Cell::get), 3);
+            assert!(tester.iter().map(|d| d.0).eq(vec![0, 4, 5]));
+
+            tester.clear();
+        }
+    }
+
+    #[test]
+    fn test_pop_front_until() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            tester.extend_back(vec![1, 0, 2, 3, 0, 4]);
+            assert!(tester.pop_front_until(&0).unwrap().eq(vec![1, 0]));
+            assert!(tester.pop_front_until(&0).unwrap().eq(vec![2, 3, 0]));
+            assert!(tester.pop_front_until(&0).is_none());
+            assert_eq!(tester, vec![4].into());
+            {
+                let mut drain = tester.pop_front_until(&4).unwrap();
+                assert_eq!(drain.len(), 1);
+                drain.next();
+            }
+            assert!(tester.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_find_subsequence() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                let contents: Vec<_> = (0..len).map(|x| x % 3).collect();
+                tester.extend_back(contents.clone());
+
+                for start in 0..len {
+                    for end in start..len + 1 {
+                        let needle = &contents[start..end];
+                        let expected = if needle.is_empty() {
+                            Some(0)
+                        } else {
+                            contents.windows(needle.len()).position(|w| w == needle)
+                        };
+                        assert_eq!(tester.find_subsequence(needle), expected);
+                    }
+                }
+                assert_eq!(tester.find_subsequence(&[7]), None);
+
+                // every needle of three elements, most of them absent
+                for code in 0..27 {
+                    let needle = [code % 3, code / 3 % 3, code / 9];
+                    let expected = contents.windows(3).position(|w| w == needle);
+                    assert_eq!(tester.find_subsequence(&needle), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_concat() {
+        const CAP: usize = 6;
+        let mut a: ArrayDeque<[_; 4]> = ArrayDeque::new();
+        let mut b: ArrayDeque<[_; 4], Wrapping> = ArrayDeque::new();
+
+        for len_a in 0..5 {
+            for len_b in 0..5 {
+                for padding in 0..4 {
+                    unsafe {
+                        a.set_len(0);
+                        a.set_tail(padding);
+                        b.set_len(0);
+                        b.set_tail(3 - padding);
+                    }
+                    a.extend_back((0..len_a).map(|x| x.to_string()));
+                    b.extend_back((len_a..len_a + len_b).map(|x| x.to_string()));
+
+                    let result = ArrayDeque::<[_; CAP]>::concat(a, b);
+                    if len_a + len_b > CAP {
+                        let (a_back, b_back) = result.unwrap_err().element;
+                        assert_eq!(a_back.len(), len_a);
+                        assert_eq!(b_back.len(), len_b);
+                        a = a_back;
+                        b = b_back;
+                        a.clear();
+                        b.clear();
+                    } else {
+                        let expected: Vec<_> = (0..len_a + len_b).map(|x| x.to_string()).collect();
+                        assert_eq!(result.unwrap(), expected.into());
+                        a = ArrayDeque::new();
+                        b = ArrayDeque::new();
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_drop() {
         use std::cell::Cell;
@@ -2765,6 +8697,453 @@ mod tests {
         }
     }
 
+    #[cfg(has_const_generics)]
+    #[test]
+    fn test_from_array_const() {
+        const TABLE: ArrayDeque<[u8; 3], Wrapping> = ArrayDeque::from_array_const([1, 2, 3]);
+        let mut tester = TABLE;
+        assert_eq!(tester, vec![1, 2, 3].into());
+        tester.push_back(4);
+        assert_eq!(tester, vec![2, 3, 4].into());
+
+        let strings: ArrayDeque<[String; 2]> =
+            ArrayDeque::from_array_const(["a".into(), "b".into()]);
+        assert_eq!(strings.front().map(|s| &s[..]), Some("a"));
+        assert_eq!(strings.back().map(|s| &s[..]), Some("b"));
+    }
+
+    #[test]
+    fn test_downstream_array() {
+        // Implements only the items `Array` has always had.
+        struct Buffer([u16; 3]);
+
+        unsafe impl Array for Buffer {
+            type Item = u16;
+            type Index = u8;
+
+            fn as_ptr(&self) -> *const u16 {
+                self.0.as_ptr()
+            }
+
+            fn as_mut_ptr(&mut self) -> *mut u16 {
+                self.0.as_mut_ptr()
+            }
+
+            fn capacity() -> usize {
+                3
+            }
+        }
+
+        let mut tester: ArrayDeque<Buffer, Wrapping> = ArrayDeque::new();
+        tester.extend_back(0..5);
+        assert_eq!(tester.capacity(), 3);
+        assert_eq!(tester.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        const CAP: usize = 6;
+        let mut a: ArrayDeque<[(u8, char); CAP]> = ArrayDeque::new();
+        let mut b: ArrayDeque<[(u8, char); CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                a.set_tail(padding);
+                a.set_len(0);
+                b.set_tail(CAP - 1 - padding);
+                b.set_len(0);
+            }
+            a.extend_back(vec![(1, 'a'), (2, 'a'), (2, 'a'), (7, 'a')]);
+            b.extend_back(vec![(0, 'b'), (2, 'b'), (3, 'b'), (8, 'b'), (9, 'b')]);
+            let expected = vec![
+                (0, 'b'),
+                (1, 'a'),
+                (2, 'a'),
+                (2, 'a'),
+                (2, 'b'),
+                (3, 'b'),
+                (7, 'a'),
+                (8, 'b'),
+                (9, 'b'),
+            ];
+
+            let merged: ArrayDeque<[_; 9]> =
+                ArrayDeque::merge_sorted(a.clone(), b.clone()).unwrap();
+            assert_eq!(merged, expected.clone().into());
+
+            let (a2, b2) = ArrayDeque::<[_; 8]>::merge_sorted(a.clone(), b.clone())
+                .unwrap_err()
+                .element;
+            assert_eq!((&a2, &b2), (&a, &b));
+
+            let mut wide: ArrayDeque<[_; 9]> = a.clone().into_iter().collect();
+            let mut other = b.clone();
+            wide.merge_from(&mut other).unwrap();
+            assert_eq!(wide, expected.into());
+            assert!(other.is_empty());
+
+            let mut other = b.clone();
+            assert_eq!(a.merge_from(&mut other), Err(CapacityError { element: () }));
+            assert_eq!(other, b);
+        }
+    }
+
+    #[test]
+    fn test_zip() {
+        const CAP: usize = 5;
+        let mut a: ArrayDeque<[usize; CAP]> = ArrayDeque::new();
+        let mut b: ArrayDeque<[usize; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                a.set_tail(padding);
+                a.set_len(0);
+                b.set_tail(CAP - 1 - padding);
+                b.set_len(0);
+            }
+            a.extend_back(0..4);
+            b.extend_back(10..14);
+            let expected: Vec<_> = a.iter().zip(b.iter()).collect();
+
+            assert!(ArrayDeque::zip(&a, &b).eq(expected.clone()));
+            assert!(ArrayDeque::zip(&a, &b).rev().eq(expected.into_iter().rev()));
+
+            let mut iter = ArrayDeque::zip(&a, &b);
+            assert_eq!(iter.len(), 4);
+            assert_eq!(iter.next(), Some((&0, &10)));
+            assert_eq!(iter.next_back(), Some((&3, &13)));
+            assert_eq!(iter.len(), 2);
+            assert_eq!(iter.next(), Some((&1, &11)));
+            assert_eq!(iter.next_back(), Some((&2, &12)));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next_back(), None);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zip_unequal_len() {
+        let a: ArrayDeque<[_; 4]> = (0..3).collect();
+        let b: ArrayDeque<[_; 4]> = (0..2).collect();
+        ArrayDeque::zip(&a, &b);
+    }
+
+    #[test]
+    fn test_clone_range() {
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back((0..CAP).map(|i| i.to_string()));
+            for start in 0..CAP + 1 {
+                for end in start..CAP + 1 {
+                    let cloned = tester.clone_range(start..end);
+                    let expected = tester.iter().skip(start).take(end - start);
+                    assert!(cloned.iter().eq(expected));
+                }
+            }
+            assert_eq!(tester.clone_range(..), tester);
+            assert_eq!(tester.clone_range(4..).len(), 2);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_clone_range_out_of_bounds() {
+        let mut tester: ArrayDeque<[_; 4]> = ArrayDeque::new();
+        tester.extend_back(vec![1, 2]);
+        tester.clone_range(..3);
+    }
+
+    #[test]
+    fn test_get_mod() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.get_mod(3), None);
+            assert_eq!(tester.get_mod_mut(3), None);
+            tester.extend_back(0..3);
+            for i in 0..10 {
+                assert_eq!(tester.get_mod(i), Some(&(i % 3)));
+            }
+            for i in 0..10 {
+                *tester.get_mod_mut(i).unwrap() += 3;
+            }
+            assert_eq!(tester, vec![12, 10, 11].into());
+        }
+    }
+
+    #[test]
+    fn test_iter_debug() {
+        let mut tester: ArrayDeque<[_; 4]> = ArrayDeque::new();
+        tester.push_back(2);
+        tester.push_back(3);
+        tester.push_front(1);
+        assert_eq!(format!("{:?}", tester.iter()), "Iter([1], [2, 3])");
+        assert_eq!(format!("{:?}", tester.iter_mut()), "IterMut([1], [2, 3])");
+        {
+            let drain = tester.drain(1..);
+            assert_eq!(format!("{:?}", drain), "Drain(Iter([2, 3], []))");
+        }
+        assert_eq!(format!("{:?}", tester.into_iter()), "IntoIter([1])");
+    }
+
+    #[test]
+    fn test_clear_volatile() {
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[u32; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(vec![0xdead_beef; CAP]);
+            tester.clear_volatile();
+            assert!(tester.is_empty());
+            let bytes = unsafe {
+                ::std::slice::from_raw_parts(
+                    tester.ptr() as *const u8,
+                    mem::size_of::<[u32; CAP]>(),
+                )
+            };
+            assert!(bytes.iter().all(|&b| b == 0));
+            tester.push_back(1);
+            assert_eq!(tester, vec![1].into());
+        }
+    }
+
+    #[test]
+    fn test_try_resize() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(vec![1, 2]);
+            assert_eq!(
+                tester.try_resize(CAP + 1, 7),
+                Err(CapacityError { element: 7 })
+            );
+            assert_eq!(tester.try_resize(4, 7), Ok(()));
+            assert_eq!(tester, vec![1, 2, 7, 7].into());
+            assert_eq!(
+                tester.try_resize_with(CAP + 1, || unreachable!()),
+                Err(CapacityError { element: () })
+            );
+            assert_eq!(tester.try_resize_with(CAP, || 8), Ok(()));
+            assert_eq!(tester, vec![1, 2, 7, 7, 8].into());
+            assert_eq!(tester.try_resize(1, 7), Ok(()));
+            assert_eq!(tester, vec![1].into());
+            assert_eq!(tester.try_resize_with(0, || 8), Ok(()));
+            assert!(tester.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_swap_contents() {
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        let mut other: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+                other.set_tail(CAP - 1 - padding);
+                other.set_len(0);
+            }
+            tester.extend_back(vec![1, 2, 3]);
+            other.extend_back(vec![4, 5, 6, 7]);
+            tester.swap_contents(&mut other);
+            assert_eq!(tester, vec![4, 5, 6, 7].into());
+            assert_eq!(other, vec![1, 2, 3].into());
+            assert_eq!(tester.push_back(8), Err(CapacityError { element: 8 }));
+
+            let taken = mem::take(&mut tester);
+            assert!(tester.is_empty());
+            assert_eq!(taken, vec![4, 5, 6, 7].into());
+        }
+    }
+
+    #[test]
+    fn test_spill_into() {
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..CAP);
+            let mut overflow: VecDeque<_> = vec![9].into();
+            tester.spill_into(&mut overflow);
+            assert!(tester.is_empty());
+            assert_eq!(overflow, vec![9, 0, 1, 2, 3]);
+
+            tester.extend_back(0..CAP);
+            let growable = tester.clone().into_vecdeque_with_capacity(3);
+            assert!(growable.capacity() >= CAP + 3);
+            assert!(growable.iter().eq(tester.iter()));
+        }
+    }
+
+    #[test]
+    fn test_get_interpolated() {
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[f64; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.get_interpolated(0.0), None);
+            tester.extend_back(vec![0.0, 4.0, 8.0, 2.0]);
+            assert_eq!(tester.get_interpolated(-0.5), None);
+            assert_eq!(tester.get_interpolated(f32::NAN), None);
+            assert_eq!(tester.get_interpolated(0.0), Some(0.0));
+            assert_eq!(tester.get_interpolated(1.5), Some(6.0));
+            assert_eq!(tester.get_interpolated(2.5), Some(5.0));
+            assert_eq!(tester.get_interpolated(3.0), Some(2.0));
+            assert_eq!(tester.get_interpolated(3.01), None);
+        }
+    }
+
+    #[test]
+    fn test_iter_step() {
+        const CAP: usize = 7;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.iter_step(2).next(), None);
+            assert_eq!(tester.latest_every(2).next(), None);
+            tester.extend_back(0..CAP);
+            for k in 1..CAP + 2 {
+                let expected: Vec<_> = (0..CAP).step_by(k).collect();
+                assert_eq!(tester.iter_step(k).len(), expected.len());
+                assert!(tester.iter_step(k).eq(expected.iter()));
+
+                let mut expected: Vec<_> = (0..CAP).rev().step_by(k).collect();
+                expected.reverse();
+                assert_eq!(tester.latest_every(k).len(), expected.len());
+                assert!(tester.latest_every(k).eq(expected.iter()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_extremum() {
+        const CAP: usize = 6;
+        let mut tester: ArrayDeque<[i32; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            assert_eq!(tester.position_min(), None);
+            tester.extend_back(vec![4, 2, 7, 2, 7, 5]);
+            assert_eq!(tester.min_element(), Some(&2));
+            assert_eq!(tester.max_element(), Some(&7));
+            assert_eq!(tester.position_min(), Some(1));
+            assert_eq!(tester.position_max(), Some(2));
+        }
+    }
+
+    #[test]
+    fn test_push_back_unique() {
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            for &x in &[1, 1, 2, 2, 2, 1] {
+                tester.push_back_unique(x).unwrap();
+            }
+            assert_eq!(tester, vec![1, 2, 1].into());
+            assert_eq!(tester.push_back_absent(2), Ok(false));
+            assert_eq!(tester.push_back_absent(3), Ok(true));
+            assert_eq!(tester.push_back_unique(3), Ok(false));
+            assert_eq!(tester.push_back_unique(4), Err(CapacityError { element: 4 }));
+            assert_eq!(tester.push_back_absent(4), Err(CapacityError { element: 4 }));
+        }
+    }
+
+    #[test]
+    fn test_retain_with_index() {
+        const CAP: usize = 10;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back((0..CAP).rev());
+            let mut seen = vec![];
+            tester.retain_with_index(|i, &x| {
+                seen.push(i);
+                assert_eq!(x, CAP - 1 - i);
+                i % 4 != 1
+            });
+            assert_eq!(seen, (0..CAP).collect::<Vec<_>>());
+            assert_eq!(tester, vec![9, 7, 6, 5, 3, 2, 1].into());
+        }
+    }
+
+    #[test]
+    fn test_retain_drain() {
+        const CAP: usize = 10;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                for consumed in 0..len + 1 {
+                    unsafe {
+                        tester.set_tail(padding);
+                        tester.set_len(0);
+                    }
+                    tester.extend_back((0..len).map(|x| x.to_string()));
+
+                    let removed: Vec<_> = tester
+                        .retain_drain(|x| x.parse::<usize>().unwrap() % 3 != 0)
+                        .take(consumed)
+                        .collect();
+                    let expected_removed: Vec<_> = (0..len)
+                        .filter(|x| x % 3 == 0)
+                        .take(consumed)
+                        .map(|x| x.to_string())
+                        .collect();
+                    assert_eq!(removed, expected_removed);
+
+                    // elements after the last removed one that was yielded are
+                    // not visited when the iterator is dropped early
+                    let last_visited = if consumed == 0 {
+                        None
+                    } else {
+                        removed.last().map(|x| x.parse::<usize>().unwrap())
+                    };
+                    let expected: Vec<_> = (0..len)
+                        .filter(|&x| match last_visited {
+                            Some(last) if x <= last => x % 3 != 0,
+                            _ => !removed.contains(&x.to_string()),
+                        })
+                        .map(|x| x.to_string())
+                        .collect();
+                    assert_eq!(tester, expected.into());
+                    assert!(tester.tail() < CAP);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_split_off() {
         const CAP: usize = 16;
@@ -2829,7 +9208,7 @@ mod tests {
 
     #[test]
     fn test_clone() {
-        let tester: ArrayDeque<[_; 16]> = (0..16).into_iter().collect();
+        let tester: ArrayDeque<[_; 16]> = (0..16).collect();
         let cloned = tester.clone();
         assert_eq!(tester, cloned)
     }
@@ -2928,4 +9307,19 @@ mod tests {
         tester.extend_back(vec![4, 5]);
         assert_eq!(tester, vec![3, 4, 5].into());
     }
+
+    #[test]
+    fn test_extend_evicted_wrapping() {
+        let mut tester: ArrayDeque<[usize; 3], Wrapping> = ArrayDeque::new();
+        assert_eq!(tester.extend_back_counting(vec![1, 2]), 0);
+        assert_eq!(tester.extend_back_counting(vec![3, 4, 5]), 2);
+        assert_eq!(tester.extend_front_counting(vec![6]), 1);
+        assert_eq!(tester, vec![6, 3, 4].into());
+
+        let mut evicted = vec![];
+        tester.extend_front_with(vec![7, 8], |x| evicted.push(x));
+        tester.extend_back_with(vec![9], |x| evicted.push(x));
+        assert_eq!(evicted, vec![4, 3, 8]);
+        assert_eq!(tester, vec![7, 6, 9].into());
+    }
 }
@@ -16,6 +16,14 @@ impl<A: Array> MaybeUninit<A> {
     pub unsafe fn uninitialized() -> Self {
         MaybeUninit { empty: () }
     }
+
+    /// Create a new MaybeUninit wholly initialized with `value`
+    #[cfg(has_const_generics)]
+    pub const fn from_array(value: A) -> Self {
+        MaybeUninit {
+            value: ManuallyDrop::new(value),
+        }
+    }
 }
 
 impl<A: Array> Deref for MaybeUninit<A> {
@@ -0,0 +1,73 @@
+use array::Array;
+use std::mem;
+
+/// A wrapper around `core::mem::MaybeUninit`;
+/// this wraps a value that can be wholly or partially uninitialized;
+/// it also has no drop regardless of the type of Array.
+///
+/// Unlike the other implementations, it has no `Deref` to the array, only
+/// raw pointers to the slots, so no reference to the whole, possibly
+/// uninitialized, array is created. `ArrayDeque` builds slices over its
+/// initialized elements only.
+#[repr(transparent)]
+pub struct MaybeUninit<A: Array>(mem::MaybeUninit<A>);
+
+impl<A: Array> MaybeUninit<A> {
+    /// Create a new MaybeUninit with uninitialized interior
+    pub unsafe fn uninitialized() -> Self {
+        MaybeUninit(mem::MaybeUninit::uninit())
+    }
+
+    /// Create a new MaybeUninit wholly initialized with `value`
+    #[cfg(has_const_generics)]
+    pub const fn from_array(value: A) -> Self {
+        MaybeUninit(mem::MaybeUninit::new(value))
+    }
+
+    /// Returns a raw pointer to the first slot.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const A::Item {
+        self.0.as_ptr() as *const A::Item
+    }
+
+    /// Returns a raw mutable pointer to the first slot.
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut A::Item {
+        self.0.as_mut_ptr() as *mut A::Item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaybeUninit;
+    use std::cell::Cell;
+    use std::mem;
+    use std::ptr;
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(mem::size_of::<MaybeUninit<[u8; 3]>>(), 3);
+        assert_eq!(mem::align_of::<MaybeUninit<[u64; 2]>>(), 8);
+        // no niche is taken from the element type
+        assert!(mem::size_of::<Option<MaybeUninit<[&u8; 1]>>>() > mem::size_of::<&u8>());
+    }
+
+    #[test]
+    fn test_no_drop() {
+        struct Bump<'a>(&'a Cell<i32>);
+
+        impl<'a> Drop for Bump<'a> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let flag = Cell::new(0);
+        {
+            let mut slots: MaybeUninit<[Bump; 2]> = unsafe { MaybeUninit::uninitialized() };
+            unsafe { ptr::write(slots.as_mut_ptr(), Bump(&flag)) };
+            unsafe { ptr::drop_in_place(slots.as_mut_ptr()) };
+        }
+        assert_eq!(flag.get(), 1);
+    }
+}
@@ -1,9 +1,15 @@
-#[cfg(has_manually_drop_in_union)]
+#[cfg(has_core_maybe_uninit)]
+#[path = "maybe_uninit_core.rs"]
+#[allow(clippy::module_inception)]
 mod maybe_uninit;
-#[cfg(not(has_manually_drop_in_union))]
+#[cfg(all(has_manually_drop_in_union, not(has_core_maybe_uninit)))]
+#[allow(clippy::module_inception)]
+mod maybe_uninit;
+#[cfg(not(any(has_manually_drop_in_union, has_core_maybe_uninit)))]
 #[path = "maybe_uninit_nodrop.rs"]
+#[allow(clippy::module_inception)]
 mod maybe_uninit;
-#[cfg(not(has_manually_drop_in_union))]
+#[cfg(not(any(has_manually_drop_in_union, has_core_maybe_uninit)))]
 mod nodrop;
 
 pub use self::maybe_uninit::MaybeUninit;
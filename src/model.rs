@@ -0,0 +1,274 @@
+//! A reference model of `ArrayDeque`, for differential testing.
+//!
+//! `Model` implements the saturating and the wrapping deque on top of a
+//! `Vec`, in the most obvious way. Fuzz targets can decode their input into a
+//! list of `Op`s, apply each of them to both a `Model` and an `ArrayDeque`
+//! with `apply` or `apply_wrapping`, and compare the results and contents
+//! after every step.
+//!
+//! ```
+//! use arraydeque::ArrayDeque;
+//! use arraydeque::model::{apply, Model, Op};
+//!
+//! let ops = vec![Op::PushBack(1), Op::PushFront(0), Op::Insert(7, 2), Op::PopFront];
+//!
+//! let mut deque: ArrayDeque<[_; 2]> = ArrayDeque::new();
+//! let mut model = Model::new(deque.capacity());
+//!
+//! for op in ops {
+//!     assert_eq!(apply(&mut deque, op.clone()), model.apply(op));
+//!     assert!(deque.iter().eq(model.as_slice()));
+//! }
+//! ```
+
+use array::Array;
+use behavior::Hooks;
+use {ArrayDeque, Saturating, Wrapping};
+
+/// An operation on a deque.
+///
+/// Indices are taken modulo the number of valid positions, so that any
+/// operation can be applied to any deque without panicking.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Op<T> {
+    /// `push_back(element)`
+    PushBack(T),
+    /// `push_front(element)`
+    PushFront(T),
+    /// `pop_back()`
+    PopBack,
+    /// `pop_front()`
+    PopFront,
+    /// `insert(index, element)`
+    Insert(usize, T),
+    /// `remove(index)`
+    Remove(usize),
+    /// `swap(i, j)`
+    Swap(usize, usize),
+    /// `clear()`
+    Clear,
+}
+
+/// A saturating or wrapping deque of bounded capacity, stored in a `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Model<T> {
+    capacity: usize,
+    wrapping: bool,
+    items: Vec<T>,
+}
+
+impl<T> Model<T> {
+    /// Creates an empty saturating model which holds at most `capacity`
+    /// elements.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Model {
+            capacity,
+            wrapping: false,
+            items: Vec::new(),
+        }
+    }
+
+    /// Creates an empty wrapping model which holds at most `capacity`
+    /// elements, kicking out the element at the other end when pushing to a
+    /// full model.
+    #[inline]
+    pub fn wrapping(capacity: usize) -> Self {
+        Model {
+            capacity,
+            wrapping: true,
+            items: Vec::new(),
+        }
+    }
+
+    /// Returns the elements, front to back.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Applies `op`, returning the element it removed, rejected or kicked
+    /// out, if any.
+    pub fn apply(&mut self, op: Op<T>) -> Option<T> {
+        let len = self.items.len();
+        let full = len == self.capacity;
+        match op {
+            Op::PushBack(element) | Op::PushFront(element) if full && len == 0 => Some(element),
+            Op::PushBack(element) if full && self.wrapping => {
+                self.items.push(element);
+                Some(self.items.remove(0))
+            }
+            Op::PushFront(element) if full && self.wrapping => {
+                self.items.insert(0, element);
+                self.items.pop()
+            }
+            Op::PushBack(element) | Op::PushFront(element) | Op::Insert(_, element) if full => {
+                Some(element)
+            }
+            Op::PushBack(element) => {
+                self.items.push(element);
+                None
+            }
+            Op::PushFront(element) => {
+                self.items.insert(0, element);
+                None
+            }
+            Op::Insert(index, element) => {
+                self.items.insert(index % (len + 1), element);
+                None
+            }
+            Op::PopBack => self.items.pop(),
+            Op::PopFront if len == 0 => None,
+            Op::PopFront => Some(self.items.remove(0)),
+            Op::Remove(_) if len == 0 => None,
+            Op::Remove(index) => Some(self.items.remove(index % len)),
+            Op::Swap(..) if len == 0 => None,
+            Op::Swap(i, j) => {
+                self.items.swap(i % len, j % len);
+                None
+            }
+            Op::Clear => {
+                self.items.clear();
+                None
+            }
+        }
+    }
+}
+
+/// Applies `op` to a saturating `deque`, returning the element it removed or
+/// rejected, if any, like `Model::apply` on a model created by `Model::new`.
+pub fn apply<A, H>(deque: &mut ArrayDeque<A, Saturating<H>>, op: Op<A::Item>) -> Option<A::Item>
+where
+    A: Array,
+    H: Hooks<A::Item>,
+{
+    let len = deque.len();
+    match op {
+        Op::PushBack(element) => deque.push_back(element).err().map(|err| err.element),
+        Op::PushFront(element) => deque.push_front(element).err().map(|err| err.element),
+        Op::Insert(index, element) => deque
+            .insert(index % (len + 1), element)
+            .err()
+            .map(|err| err.element),
+        Op::PopBack => deque.pop_back(),
+        Op::PopFront => deque.pop_front(),
+        Op::Remove(_) if len == 0 => None,
+        Op::Remove(index) => deque.remove(index % len),
+        Op::Swap(..) if len == 0 => None,
+        Op::Swap(i, j) => {
+            deque.swap(i % len, j % len);
+            None
+        }
+        Op::Clear => {
+            deque.clear();
+            None
+        }
+    }
+}
+
+/// Applies `op` to a wrapping `deque`, returning the element it removed,
+/// rejected or kicked out, if any, like `Model::apply` on a model created by
+/// `Model::wrapping`.
+///
+/// `Op::Insert` uses `try_insert`, which rejects the element when the deque
+/// is full. The model does not pin elements, so the hooks of the deque must
+/// keep `Hooks::PINNED` at zero.
+pub fn apply_wrapping<A, H>(
+    deque: &mut ArrayDeque<A, Wrapping<H>>,
+    op: Op<A::Item>,
+) -> Option<A::Item>
+where
+    A: Array,
+    H: Hooks<A::Item>,
+{
+    let len = deque.len();
+    match op {
+        Op::PushBack(element) => deque.push_back(element),
+        Op::PushFront(element) => deque.push_front(element),
+        Op::Insert(index, element) => deque
+            .try_insert(index % (len + 1), element)
+            .err()
+            .map(|err| err.element),
+        Op::PopBack => deque.pop_back(),
+        Op::PopFront => deque.pop_front(),
+        Op::Remove(_) if len == 0 => None,
+        Op::Remove(index) => deque.remove(index % len),
+        Op::Swap(..) if len == 0 => None,
+        Op::Swap(i, j) => {
+            deque.swap(i % len, j % len);
+            None
+        }
+        Op::Clear => {
+            deque.clear();
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, apply_wrapping, Model, Op};
+    use {ArrayDeque, Wrapping};
+
+    /// Decodes a pseudo-random operation from `seed`.
+    fn op(seed: u64) -> Op<u64> {
+        let arg = (seed >> 8) as usize;
+        match seed % 16 {
+            0..=3 => Op::PushBack(seed),
+            4..=6 => Op::PushFront(seed),
+            7 | 8 => Op::PopBack,
+            9 | 10 => Op::PopFront,
+            11 | 12 => Op::Insert(arg, seed),
+            13 => Op::Remove(arg),
+            14 => Op::Swap(arg, arg >> 8),
+            _ => Op::Clear,
+        }
+    }
+
+    /// Advances the xorshift generator.
+    fn next(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    #[test]
+    fn test_differential() {
+        const CAP: usize = 7;
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        for padding in 0..CAP {
+            let mut deque: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+            unsafe { deque.set_tail(padding) }
+            let mut model = Model::new(CAP);
+            for _ in 0..2000 {
+                let op = op(next(&mut seed));
+                assert_eq!(apply(&mut deque, op.clone()), model.apply(op));
+                assert!(deque.iter().eq(model.as_slice()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_differential_wrapping() {
+        const CAP: usize = 7;
+        let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+        for padding in 0..CAP {
+            let mut deque: ArrayDeque<[_; CAP], Wrapping> = ArrayDeque::new();
+            unsafe { deque.set_tail(padding) }
+            let mut model = Model::wrapping(CAP);
+            for _ in 0..2000 {
+                let op = op(next(&mut seed));
+                assert_eq!(apply_wrapping(&mut deque, op.clone()), model.apply(op));
+                assert!(deque.iter().eq(model.as_slice()));
+            }
+        }
+
+        let mut empty: ArrayDeque<[u64; 0], Wrapping> = ArrayDeque::new();
+        let mut model = Model::wrapping(0);
+        for seed in 0..16 {
+            let op = op(seed);
+            assert_eq!(apply_wrapping(&mut empty, op.clone()), model.apply(op));
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! A guard over the front element that only removes it on commit.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use array::Array;
+use behavior::Behavior;
+use ArrayDeque;
+
+/// A guard that derefs to the front element of an `ArrayDeque`.
+///
+/// The element is only removed from the deque if `commit` is called. If the
+/// guard is dropped instead, for example because processing the element
+/// panicked, the element is left in place.
+///
+/// This `struct` is created by the `peek_front_guard` method on `ArrayDeque`.
+pub struct PeekGuard<'a, A: 'a + Array, B: 'a + Behavior> {
+    deque: &'a mut ArrayDeque<A, B>,
+}
+
+impl<'a, A: Array, B: Behavior> PeekGuard<'a, A, B> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, B>) -> Self {
+        debug_assert!(!deque.is_empty());
+        PeekGuard { deque }
+    }
+
+    /// Removes the guarded element from the front of the deque and returns it.
+    #[inline]
+    pub fn commit(self) -> A::Item {
+        match self.deque.pop_front() {
+            Some(element) => element,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<'a, A: Array, B: Behavior> Deref for PeekGuard<'a, A, B> {
+    type Target = A::Item;
+
+    #[inline]
+    fn deref(&self) -> &A::Item {
+        &self.deque[0]
+    }
+}
+
+impl<'a, A: Array, B: Behavior> DerefMut for PeekGuard<'a, A, B> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut A::Item {
+        &mut self.deque[0]
+    }
+}
+
+impl<'a, A: Array, B: Behavior> fmt::Debug for PeekGuard<'a, A, B>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PeekGuard").field(&**self).finish()
+    }
+}
@@ -1,7 +1,7 @@
-use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
 /// **RangeArgument** is implemented by Rust's built-in range types, produced
-/// by range syntax like `..`, `a..`, `..b` or `c..d`.
+/// by range syntax like `..`, `a..`, `..b`, `..=b`, `c..d` or `c..=d`.
 pub trait RangeArgument<T = usize> {
     #[inline]
     /// Start index (inclusive)
@@ -41,3 +41,24 @@ impl<T: Copy> RangeArgument<T> for Range<T> {
         Some(self.end)
     }
 }
+
+// An inclusive end of `usize::MAX` saturates, which is out of bounds of any
+// deque anyway.
+
+impl RangeArgument<usize> for RangeToInclusive<usize> {
+    #[inline]
+    fn end(&self) -> Option<usize> {
+        Some(self.end.saturating_add(1))
+    }
+}
+
+impl RangeArgument<usize> for RangeInclusive<usize> {
+    #[inline]
+    fn start(&self) -> Option<usize> {
+        Some(*RangeInclusive::start(self))
+    }
+    #[inline]
+    fn end(&self) -> Option<usize> {
+        Some(RangeInclusive::end(self).saturating_add(1))
+    }
+}
@@ -0,0 +1,202 @@
+//! A byte deque that indexes its records as they are pushed.
+
+use std::fmt;
+
+use array::Array;
+use behavior::Saturating;
+use error::CapacityError;
+use {ArrayDeque, Drain};
+
+/// A byte deque split into records by a delimiter byte, such as `b'\n'` for
+/// line-oriented input.
+///
+/// The position of every delimiter is recorded in a second deque of
+/// capacity `R` as bytes are pushed, so `record_count` and `pop_record`
+/// never rescan the buffered bytes. Bytes after the last delimiter form an
+/// incomplete record, which is kept until its delimiter arrives.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::RecordDeque;
+///
+/// let mut lines: RecordDeque<[u8; 16], [usize; 4]> = RecordDeque::new(b'\n');
+///
+/// lines.extend_from_slice(b"ab\ncd\ne").unwrap();
+/// assert_eq!(lines.record_count(), 2);
+///
+/// let line: Vec<u8> = lines.pop_record().unwrap().collect();
+/// assert_eq!(line, b"ab\n");
+/// assert_eq!(lines.record_count(), 1);
+/// ```
+pub struct RecordDeque<A: Array<Item = u8>, R: Array<Item = usize>> {
+    bytes: ArrayDeque<A>,
+    // positions of the buffered delimiters, counted since creation
+    ends: ArrayDeque<R>,
+    delimiter: u8,
+    pushed: usize,
+}
+
+impl<A: Array<Item = u8>, R: Array<Item = usize>> RecordDeque<A, R> {
+    /// Creates an empty `RecordDeque` whose records end with `delimiter`.
+    #[inline]
+    pub fn new(delimiter: u8) -> Self {
+        RecordDeque {
+            bytes: ArrayDeque::new(),
+            ends: ArrayDeque::new(),
+            delimiter,
+            pushed: 0,
+        }
+    }
+
+    /// Returns the delimiter byte.
+    #[inline]
+    pub fn delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    /// Returns the capacity of the deque, in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Returns the number of buffered bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns true if the deque contains no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the number of complete records.
+    #[inline]
+    pub fn record_count(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns the buffered bytes.
+    #[inline]
+    pub fn bytes(&self) -> &ArrayDeque<A> {
+        &self.bytes
+    }
+
+    /// Appends a byte to the back of the deque.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *byte* })`
+    /// if the deque is full, or if `byte` is the delimiter and `R` records
+    /// are already buffered.
+    pub fn push_back(&mut self, byte: u8) -> Result<(), CapacityError<u8>> {
+        if byte == self.delimiter && self.ends.is_full() {
+            return Err(CapacityError { element: byte });
+        }
+        self.bytes.push_back(byte)?;
+        if byte == self.delimiter {
+            unsafe { self.ends.push_back_unchecked(self.pushed) }
+        }
+        self.pushed = self.pushed.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Appends all bytes of `src` to the back of the deque.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return
+    /// `Err(CapacityError { *src* })`, leaving the deque unchanged, if there
+    /// is not enough room for all bytes or all records of `src`.
+    pub fn extend_from_slice<'a>(&mut self, src: &'a [u8]) -> Result<(), CapacityError<&'a [u8]>> {
+        let records = src.iter().filter(|&&byte| byte == self.delimiter).count();
+        if self.capacity() - self.len() < src.len()
+            || self.ends.capacity() - self.ends.len() < records
+        {
+            return Err(CapacityError { element: src });
+        }
+        for &byte in src {
+            let _ = self.push_back(byte);
+        }
+        Ok(())
+    }
+
+    /// Returns the length of the first complete record, delimiter included,
+    /// or `None` if there is none.
+    #[inline]
+    pub fn front_record_len(&self) -> Option<usize> {
+        let front = self.pushed.wrapping_sub(self.len());
+        self.ends.front().map(|&end| end.wrapping_sub(front) + 1)
+    }
+
+    /// Removes the first complete record, delimiter included, returning a
+    /// draining iterator over its bytes, or `None` if there is none.
+    pub fn pop_record(&mut self) -> Option<Drain<'_, A, Saturating>> {
+        let len = self.front_record_len()?;
+        self.ends.pop_front();
+        Some(self.bytes.drain(..len))
+    }
+
+    /// Removes every byte, complete records and incomplete one alike.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.ends.clear();
+    }
+}
+
+impl<A: Array<Item = u8>, R: Array<Item = usize>> fmt::Debug for RecordDeque<A, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RecordDeque")
+            .field("bytes", &self.bytes)
+            .field("record_count", &self.record_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RecordDeque;
+    use CapacityError;
+
+    #[test]
+    fn test_records() {
+        let mut lines: RecordDeque<[u8; 8], [usize; 2]> = RecordDeque::new(b'\n');
+        // go around the buffer a few times
+        for _ in 0..5 {
+            lines.extend_from_slice(b"a\nbc").unwrap();
+            assert_eq!(lines.record_count(), 1);
+            assert_eq!(lines.front_record_len(), Some(2));
+            lines.extend_from_slice(b"\n").unwrap();
+            assert_eq!(lines.record_count(), 2);
+
+            assert_eq!(
+                lines.push_back(b'\n'),
+                Err(CapacityError { element: b'\n' })
+            );
+            assert!(lines.extend_from_slice(b"d\n").is_err());
+            assert_eq!(lines.len(), 5);
+
+            assert!(lines.pop_record().unwrap().eq(b"a\n".iter().cloned()));
+            assert!(lines.pop_record().unwrap().eq(b"bc\n".iter().cloned()));
+            assert!(lines.pop_record().is_none());
+            assert!(lines.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_incomplete_record() {
+        let mut lines: RecordDeque<[u8; 4], [usize; 4]> = RecordDeque::new(0);
+        lines.extend_from_slice(&[1, 2, 3]).unwrap();
+        assert!(lines.pop_record().is_none());
+        assert!(lines.extend_from_slice(&[4, 0]).is_err());
+        lines.push_back(0).unwrap();
+        assert_eq!(lines.pop_record().unwrap().len(), 4);
+        assert_eq!(lines.record_count(), 0);
+
+        lines.extend_from_slice(&[5, 0, 6]).unwrap();
+        lines.clear();
+        assert_eq!(lines.record_count(), 0);
+        lines.extend_from_slice(&[0]).unwrap();
+        assert_eq!(lines.front_record_len(), Some(1));
+    }
+}
@@ -0,0 +1,270 @@
+//! Guards over the contiguous regions at either end of an `ArrayDeque`, for
+//! publishing the results of DMA-style transfers.
+
+use std::cmp;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+use array::Array;
+use behavior::Behavior;
+use error::CapacityError;
+use ArrayDeque;
+
+/// A guard over the contiguous free slots following the back element of an
+/// `ArrayDeque`.
+///
+/// The guard derefs to the slots, which can be filled in place. Filled slots
+/// only become part of the deque when `commit_back` is called, and `n` is
+/// checked against the length of this region, not of whatever the buffer
+/// holds by then. Dropping the guard publishes nothing.
+///
+/// This `struct` is created by the `spare_capacity_mut` and `reserve_back`
+/// methods on `ArrayDeque`.
+pub struct SpareRegion<'a, A: 'a + Array, B: 'a + Behavior> {
+    deque: &'a mut ArrayDeque<A, B>,
+    start: usize,
+    len: usize,
+}
+
+impl<'a, A: Array, B: Behavior> SpareRegion<'a, A, B>
+where
+    A::Item: Copy + Default,
+{
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, B>) -> Self {
+        if deque.is_empty() {
+            unsafe { deque.set_tail(0) }
+        }
+        let tail = deque.tail();
+        let start = deque.head();
+        let len = if deque.is_full() {
+            0
+        } else if start >= tail {
+            deque.capacity() - start
+        } else {
+            tail - start
+        };
+        SpareRegion::fill(deque, start, len)
+    }
+
+    /// Returns a region of exactly `n` slots, or `Err(CapacityError { *n* })`
+    /// if fewer are contiguous after the back element.
+    #[inline]
+    pub(crate) fn reserve(
+        deque: &'a mut ArrayDeque<A, B>,
+        n: usize,
+    ) -> Result<Self, CapacityError<usize>> {
+        let region = SpareRegion::new(deque);
+        if region.len < n {
+            return Err(CapacityError { element: n });
+        }
+        Ok(SpareRegion { len: n, ..region })
+    }
+
+    #[inline]
+    fn fill(deque: &'a mut ArrayDeque<A, B>, start: usize, len: usize) -> Self {
+        for i in start..start + len {
+            unsafe { deque.buffer_write(i, A::Item::default()) }
+        }
+        SpareRegion { deque, start, len }
+    }
+
+    /// Appends the first `n` slots of the region to the back of the deque.
+    ///
+    /// Return `Ok(())` if the commit succeeds, or return
+    /// `Err(CapacityError { *n* })`, leaving the deque unchanged, if the
+    /// region is shorter than `n`.
+    #[inline]
+    pub fn commit_back(self, n: usize) -> Result<(), CapacityError<usize>> {
+        if n > self.len {
+            return Err(CapacityError { element: n });
+        }
+        let len = self.deque.len() + n;
+        unsafe { self.deque.set_len(len) }
+        Ok(())
+    }
+}
+
+impl<'a, A: Array, B: Behavior> Deref for SpareRegion<'a, A, B> {
+    type Target = [A::Item];
+
+    #[inline]
+    fn deref(&self) -> &[A::Item] {
+        unsafe { slice::from_raw_parts(self.deque.ptr().add(self.start), self.len) }
+    }
+}
+
+impl<'a, A: Array, B: Behavior> DerefMut for SpareRegion<'a, A, B> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [A::Item] {
+        unsafe { slice::from_raw_parts_mut(self.deque.ptr_mut().add(self.start), self.len) }
+    }
+}
+
+impl<'a, A: Array, B: Behavior> fmt::Debug for SpareRegion<'a, A, B>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SpareRegion").field(&&**self).finish()
+    }
+}
+
+/// A guard over contiguous free slots preceding the front element of an
+/// `ArrayDeque`.
+///
+/// The guard derefs to the slots, which can be filled in place. Filled slots
+/// only become part of the deque when `commit_front` is called, which
+/// prepends the *last* `n` slots, those adjacent to the front element.
+/// Dropping the guard publishes nothing.
+///
+/// This `struct` is created by the `reserve_front` method on `ArrayDeque`.
+pub struct SpareFrontRegion<'a, A: 'a + Array, B: 'a + Behavior> {
+    deque: &'a mut ArrayDeque<A, B>,
+    start: usize,
+    len: usize,
+}
+
+impl<'a, A: Array, B: Behavior> SpareFrontRegion<'a, A, B>
+where
+    A::Item: Copy + Default,
+{
+    #[inline]
+    pub(crate) fn reserve(
+        deque: &'a mut ArrayDeque<A, B>,
+        n: usize,
+    ) -> Result<Self, CapacityError<usize>> {
+        if deque.is_empty() {
+            unsafe { deque.set_tail(0) }
+        }
+        let tail = deque.tail();
+        let free = deque.capacity() - deque.len();
+        // the free slots before the front run down to index 0, and if the
+        // front is at index 0, down from the end of the buffer
+        let available = if tail == 0 {
+            free
+        } else {
+            cmp::min(free, tail)
+        };
+        if available < n {
+            return Err(CapacityError { element: n });
+        }
+        let start = if tail == 0 {
+            deque.capacity() - n
+        } else {
+            tail - n
+        };
+        for i in start..start + n {
+            unsafe { deque.buffer_write(i, A::Item::default()) }
+        }
+        Ok(SpareFrontRegion {
+            deque,
+            start,
+            len: n,
+        })
+    }
+
+    /// Prepends the last `n` slots of the region to the front of the deque.
+    ///
+    /// Return `Ok(())` if the commit succeeds, or return
+    /// `Err(CapacityError { *n* })`, leaving the deque unchanged, if the
+    /// region is shorter than `n`.
+    #[inline]
+    pub fn commit_front(self, n: usize) -> Result<(), CapacityError<usize>> {
+        if n > self.len {
+            return Err(CapacityError { element: n });
+        }
+        if n == 0 {
+            return Ok(());
+        }
+        let len = self.deque.len() + n;
+        unsafe {
+            self.deque.set_tail(self.start + self.len - n);
+            self.deque.set_len(len);
+        }
+        Ok(())
+    }
+}
+
+impl<'a, A: Array, B: Behavior> Deref for SpareFrontRegion<'a, A, B> {
+    type Target = [A::Item];
+
+    #[inline]
+    fn deref(&self) -> &[A::Item] {
+        unsafe { slice::from_raw_parts(self.deque.ptr().add(self.start), self.len) }
+    }
+}
+
+impl<'a, A: Array, B: Behavior> DerefMut for SpareFrontRegion<'a, A, B> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [A::Item] {
+        unsafe { slice::from_raw_parts_mut(self.deque.ptr_mut().add(self.start), self.len) }
+    }
+}
+
+impl<'a, A: Array, B: Behavior> fmt::Debug for SpareFrontRegion<'a, A, B>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SpareFrontRegion").field(&&**self).finish()
+    }
+}
+
+/// A guard over the contiguous elements starting at the front of an
+/// `ArrayDeque`.
+///
+/// The guard derefs to the elements, which can be read out in place. They
+/// are only removed from the deque when `commit_front` is called, and `n` is
+/// checked against the length of this region. Dropping the guard removes
+/// nothing.
+///
+/// This `struct` is created by the `filled_region` method on `ArrayDeque`.
+pub struct FilledRegion<'a, A: 'a + Array, B: 'a + Behavior> {
+    deque: &'a mut ArrayDeque<A, B>,
+    len: usize,
+}
+
+impl<'a, A: Array, B: Behavior> FilledRegion<'a, A, B> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, B>) -> Self {
+        let len = deque.as_slices().0.len();
+        FilledRegion { deque, len }
+    }
+
+    /// Removes the first `n` elements of the region from the front of the
+    /// deque and drops them.
+    ///
+    /// Return `Ok(())` if the commit succeeds, or return
+    /// `Err(CapacityError { *n* })`, leaving the deque unchanged, if the
+    /// region is shorter than `n`.
+    #[inline]
+    pub fn commit_front(self, n: usize) -> Result<(), CapacityError<usize>> {
+        if n > self.len {
+            return Err(CapacityError { element: n });
+        }
+        for _ in 0..n {
+            self.deque.pop_front();
+        }
+        Ok(())
+    }
+}
+
+impl<'a, A: Array, B: Behavior> Deref for FilledRegion<'a, A, B> {
+    type Target = [A::Item];
+
+    #[inline]
+    fn deref(&self) -> &[A::Item] {
+        &self.deque.as_slices().0[..self.len]
+    }
+}
+
+impl<'a, A: Array, B: Behavior> fmt::Debug for FilledRegion<'a, A, B>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("FilledRegion").field(&&**self).finish()
+    }
+}
@@ -0,0 +1,95 @@
+//! An iterator that removes the elements rejected by a predicate.
+
+use std::fmt;
+
+use array::Array;
+use behavior::Behavior;
+use ArrayDeque;
+
+/// An iterator which uses a predicate to decide which elements to keep, and
+/// yields the removed ones.
+///
+/// The retained elements keep their order. If the iterator is dropped before
+/// being fully consumed, the elements not yet visited are retained.
+///
+/// This `struct` is created by the `retain_drain` method on `ArrayDeque`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct RetainDrain<'a, A: 'a + Array, B: 'a + Behavior, F> {
+    deque: &'a mut ArrayDeque<A, B>,
+    f: F,
+    // index of the next element to visit
+    idx: usize,
+    // number of elements removed so far
+    del: usize,
+    old_len: usize,
+}
+
+impl<'a, A: Array, B: Behavior, F> RetainDrain<'a, A, B, F> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, B>, f: F) -> Self {
+        let old_len = deque.len();
+        // Guard against the deque getting leaked (leak amplification)
+        unsafe { deque.set_len(0) }
+        RetainDrain {
+            deque,
+            f,
+            idx: 0,
+            del: 0,
+            old_len,
+        }
+    }
+}
+
+impl<'a, A, B, F> Iterator for RetainDrain<'a, A, B, F>
+where
+    A: Array,
+    B: Behavior,
+    F: FnMut(&A::Item) -> bool,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        while self.idx < self.old_len {
+            let tail = self.deque.tail();
+            let src = ArrayDeque::<A, B>::wrap_add(tail, self.idx);
+            let keep = unsafe { (self.f)(&*self.deque.ptr().add(src)) };
+            self.idx += 1;
+            if !keep {
+                self.del += 1;
+                return Some(unsafe { self.deque.buffer_read(src) });
+            } else if self.del > 0 {
+                let dst = ArrayDeque::<A, B>::wrap_sub(src, self.del);
+                unsafe { self.deque.copy(dst, src, 1) };
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<'a, A: Array, B: Behavior, F> Drop for RetainDrain<'a, A, B, F> {
+    fn drop(&mut self) {
+        let tail = self.deque.tail();
+        let remaining = self.old_len - self.idx;
+        if self.del > 0 && remaining > 0 {
+            let src = ArrayDeque::<A, B>::wrap_add(tail, self.idx);
+            let dst = ArrayDeque::<A, B>::wrap_sub(src, self.del);
+            unsafe { self.deque.wrap_copy(dst, src, remaining) };
+        }
+        unsafe { self.deque.set_len(self.old_len - self.del) }
+    }
+}
+
+impl<'a, A: Array, B: Behavior, F> fmt::Debug for RetainDrain<'a, A, B, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RetainDrain")
+            .field("idx", &self.idx)
+            .field("del", &self.del)
+            .field("old_len", &self.old_len)
+            .finish()
+    }
+}
@@ -0,0 +1,246 @@
+//! A wrapping deque that numbers its elements in push order.
+
+use std::fmt;
+use std::iter;
+use std::ops::RangeFrom;
+
+use array::Array;
+use behavior::{Hooks, NoHooks, Wrapping};
+use {ArrayDeque, Iter};
+
+/// A wrapping `ArrayDeque` that tracks the absolute sequence number of
+/// every element, counted from 0 for the first element ever pushed.
+///
+/// Sequence numbers stay attached to their element when older ones are
+/// kicked out, so a log consumer can remember the number of the last entry
+/// it read and resume after it, even once the ring has wrapped.
+///
+/// Numbers are only kept contiguous by kicking out the front element, so
+/// the hooks must not pin any: `Hooks::PINNED` has to be 0.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::SequencedDeque;
+///
+/// let mut log: SequencedDeque<[_; 3]> = SequencedDeque::new();
+///
+/// for line in &["a", "b", "c", "d", "e"] {
+///     log.push_back(*line);
+/// }
+///
+/// // "a" and "b" were kicked out
+/// assert_eq!(log.front_sequence(), 2);
+/// assert!(log.enumerate_absolute().eq(vec![(2, &"c"), (3, &"d"), (4, &"e")]));
+///
+/// // resume after entry 3
+/// assert!(log.enumerate_from(4).eq(vec![(4, &"e")]));
+/// ```
+pub struct SequencedDeque<A: Array, H: Hooks<A::Item> = NoHooks> {
+    deque: ArrayDeque<A, Wrapping<H>>,
+    front: u64,
+}
+
+impl<A: Array, H: Hooks<A::Item>> SequencedDeque<A, H> {
+    /// Creates an empty `SequencedDeque`, whose first element will be
+    /// numbered 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `H::PINNED` is not 0.
+    #[inline]
+    pub fn new() -> Self {
+        SequencedDeque::starting_at(0)
+    }
+
+    /// Creates an empty `SequencedDeque`, whose first element will be
+    /// numbered `sequence`, for example to continue a persisted log.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `H::PINNED` is not 0.
+    #[inline]
+    pub fn starting_at(sequence: u64) -> Self {
+        assert!(H::PINNED == 0, "a SequencedDeque cannot pin elements");
+        SequencedDeque {
+            deque: ArrayDeque::new(),
+            front: sequence,
+        }
+    }
+
+    /// Returns the underlying deque.
+    #[inline]
+    pub fn as_deque(&self) -> &ArrayDeque<A, Wrapping<H>> {
+        &self.deque
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Returns the sequence number of the front element, or of the next
+    /// element pushed if there are none.
+    #[inline]
+    pub fn front_sequence(&self) -> u64 {
+        self.front
+    }
+
+    /// Returns the sequence number the next element pushed will get.
+    #[inline]
+    pub fn next_sequence(&self) -> u64 {
+        self.front + self.len() as u64
+    }
+
+    /// Appends an element, kicking out the front element if the deque is
+    /// full, and returns the sequence number of the new element along with
+    /// the kicked out one.
+    pub fn push_back(&mut self, element: A::Item) -> (u64, Option<A::Item>) {
+        let sequence = self.next_sequence();
+        let existing = self.deque.push_back(element);
+        // a deque without capacity hands `element` back, kicked out at once
+        if existing.is_some() {
+            self.front += 1;
+        }
+        (sequence, existing)
+    }
+
+    /// Removes the front element and returns it with its sequence number,
+    /// or `None` if the deque is empty.
+    pub fn pop_front(&mut self) -> Option<(u64, A::Item)> {
+        let element = self.deque.pop_front()?;
+        let sequence = self.front;
+        self.front += 1;
+        Some((sequence, element))
+    }
+
+    /// Returns the element numbered `sequence`, or `None` if it was not
+    /// pushed yet or is no longer in the deque.
+    #[inline]
+    pub fn get(&self, sequence: u64) -> Option<&A::Item> {
+        let offset = sequence.checked_sub(self.front)?;
+        if offset >= self.len() as u64 {
+            return None;
+        }
+        self.deque.get(offset as usize)
+    }
+
+    /// Returns a front-to-back iterator over the elements and their sequence
+    /// numbers.
+    #[inline]
+    pub fn enumerate_absolute(&self) -> iter::Zip<RangeFrom<u64>, Iter<'_, A::Item>> {
+        (self.front..).zip(self.deque.iter())
+    }
+
+    /// Returns a front-to-back iterator over the elements numbered at least
+    /// `sequence` and their sequence numbers.
+    ///
+    /// Elements that were kicked out are skipped silently; compare `sequence`
+    /// with `front_sequence` to detect them.
+    #[inline]
+    pub fn enumerate_from(&self, sequence: u64) -> iter::Zip<RangeFrom<u64>, Iter<'_, A::Item>> {
+        let skip = sequence.saturating_sub(self.front);
+        let skip = if skip > self.len() as u64 {
+            self.len()
+        } else {
+            skip as usize
+        };
+        let mut iter = self.deque.iter();
+        iter.advance(skip);
+        (self.front + skip as u64..).zip(iter)
+    }
+
+    /// Removes every element. Sequence numbers continue from where they
+    /// were.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.front = self.next_sequence();
+        self.deque.clear();
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> Default for SequencedDeque<A, H> {
+    #[inline]
+    fn default() -> Self {
+        SequencedDeque::new()
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> fmt::Debug for SequencedDeque<A, H>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.enumerate_absolute()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SequencedDeque;
+    use behavior::Hooks;
+
+    #[test]
+    fn test_sequences() {
+        const CAP: usize = 4;
+        let mut tester: SequencedDeque<[u64; CAP]> = SequencedDeque::starting_at(10);
+        assert_eq!(tester.get(10), None);
+
+        for i in 0..3 * CAP as u64 {
+            let (sequence, existing) = tester.push_back(i);
+            assert_eq!(sequence, 10 + i);
+            assert_eq!(existing, i.checked_sub(CAP as u64));
+            assert_eq!(tester.next_sequence(), 11 + i);
+            assert!(tester
+                .enumerate_absolute()
+                .all(|(sequence, &x)| sequence == 10 + x));
+            assert_eq!(tester.get(10 + i), Some(&i));
+            assert_eq!(tester.get(11 + i), None);
+        }
+        assert_eq!(tester.front_sequence(), 10 + 2 * CAP as u64);
+        assert_eq!(tester.get(9 + 2 * CAP as u64), None);
+
+        let front = tester.front_sequence();
+        assert_eq!(tester.enumerate_from(0).count(), CAP);
+        assert!(tester
+            .enumerate_from(front + 1)
+            .eq(tester.enumerate_absolute().skip(1)));
+        assert_eq!(tester.enumerate_from(!0).count(), 0);
+
+        assert_eq!(tester.pop_front(), Some((front, 2 * CAP as u64)));
+        tester.clear();
+        assert_eq!(tester.front_sequence(), 10 + 3 * CAP as u64);
+        assert_eq!(tester.push_back(0).0, 10 + 3 * CAP as u64);
+        assert_eq!(format!("{:?}", tester), "{22: 0}");
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut tester: SequencedDeque<[u8; 0]> = SequencedDeque::new();
+        assert_eq!(tester.push_back(1), (0, Some(1)));
+        assert_eq!(tester.push_back(2), (1, Some(2)));
+        assert_eq!(tester.next_sequence(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "a SequencedDeque cannot pin elements")]
+    fn test_pinned_hooks() {
+        struct Header;
+
+        impl<T> Hooks<T> for Header {
+            const PINNED: usize = 1;
+        }
+
+        let mut tester: SequencedDeque<[&str; 3], Header> = SequencedDeque::new();
+        for line in &["h", "a", "b", "c", "d"] {
+            tester.push_back(*line);
+        }
+    }
+}
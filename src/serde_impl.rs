@@ -0,0 +1,101 @@
+//! `Serialize` and `Deserialize` implementations, as sequences.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Error, IgnoredAny, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use array::Array;
+use behavior::Behavior;
+use ArrayDeque;
+
+impl<A: Array, B: Behavior> Serialize for ArrayDeque<A, B>
+where
+    A::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+struct DequeVisitor<A: Array, B: Behavior>(PhantomData<ArrayDeque<A, B>>);
+
+impl<'de, A: Array, B: Behavior> Visitor<'de> for DequeVisitor<A, B>
+where
+    A::Item: Deserialize<'de>,
+{
+    type Value = ArrayDeque<A, B>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of at most {} elements", A::capacity())
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut deque = ArrayDeque::new();
+        while let Some(element) = seq.next_element()? {
+            if deque.try_push_back(element).is_err() {
+                // count the rest too, to report the actual length
+                let mut len = A::capacity() + 1;
+                while seq.next_element::<IgnoredAny>()?.is_some() {
+                    len += 1;
+                }
+                return Err(S::Error::invalid_length(len, &self));
+            }
+        }
+        Ok(deque)
+    }
+}
+
+/// Fails with an invalid length error if the sequence has more elements than
+/// the capacity, whatever the behavior.
+impl<'de, A: Array, B: Behavior> Deserialize<'de> for ArrayDeque<A, B>
+where
+    A::Item: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(DequeVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    use {ArrayDeque, Wrapping};
+
+    #[test]
+    fn test_round_trip() {
+        let mut tester: ArrayDeque<[u8; 3]> = ArrayDeque::new();
+        assert_tokens(&tester, &[Token::Seq { len: Some(0) }, Token::SeqEnd]);
+
+        tester.push_back(2).unwrap();
+        tester.push_back(3).unwrap();
+        tester.push_front(1).unwrap();
+        assert_tokens(
+            &tester,
+            &[
+                Token::Seq { len: Some(3) },
+                Token::U8(1),
+                Token::U8(2),
+                Token::U8(3),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_too_long() {
+        assert_de_tokens_error::<ArrayDeque<[u8; 2], Wrapping>>(
+            &[
+                Token::Seq { len: Some(4) },
+                Token::U8(1),
+                Token::U8(2),
+                Token::U8(3),
+                Token::U8(4),
+                Token::SeqEnd,
+            ],
+            "invalid length 4, expected a sequence of at most 2 elements",
+        );
+    }
+}
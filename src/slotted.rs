@@ -0,0 +1,278 @@
+//! A deque handing out generation-checked handles to its elements.
+
+use std::fmt;
+
+use error::CapacityError;
+use {ConstDeque, Iter};
+
+/// A stable reference to an element of a `SlottedDeque`.
+///
+/// A handle stays valid until its element is popped, evicted or cleared, and
+/// does not resolve to another element pushed into the same slot later,
+/// unless the slot has been reused a multiple of `1 << 32` times
+/// since, as its 32-bit generation then wraps back to the same value.
+///
+/// This `struct` is returned by the push methods on `SlottedDeque`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SlotHandle {
+    slot: usize,
+    generation: u32,
+}
+
+/// An `ArrayDeque` of capacity `N` that pairs every slot with a generation
+/// counter, so that elements can be referred to by `SlotHandle`s.
+///
+/// The generation of a slot is bumped whenever its element leaves the deque,
+/// which invalidates every handle to it, until the generation wraps after
+/// `1 << 32` reuses of the slot. Elements can only be pushed and
+/// popped at either end, as moving them between slots would break their
+/// handles.
+///
+/// Requires Rust 1.63+.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::SlottedDeque;
+///
+/// let mut queue: SlottedDeque<&str, 2> = SlottedDeque::new();
+///
+/// let a = queue.push_back("a").unwrap();
+/// let (b, _) = queue.push_back_overwrite("b");
+/// let (c, evicted) = queue.push_back_overwrite("c");
+///
+/// assert_eq!(evicted, Some("a"));
+/// assert_eq!(queue.get(a), None);
+/// assert_eq!(queue.get(b), Some(&"b"));
+/// assert_eq!(queue.get(c), Some(&"c"));
+/// ```
+pub struct SlottedDeque<T, const N: usize> {
+    deque: ConstDeque<T, N>,
+    generations: [u32; N],
+}
+
+impl<T, const N: usize> SlottedDeque<T, N> {
+    /// Creates an empty `SlottedDeque`.
+    #[inline]
+    pub fn new() -> Self {
+        SlottedDeque {
+            deque: ConstDeque::new(),
+            generations: [0; N],
+        }
+    }
+
+    /// Returns the capacity of the deque.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    /// Returns true if the deque contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Returns true if the deque is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.deque.is_full()
+    }
+
+    #[inline]
+    fn handle(&self, slot: usize) -> SlotHandle {
+        SlotHandle {
+            slot,
+            generation: self.generations[slot],
+        }
+    }
+
+    #[inline]
+    fn release(&mut self, slot: usize) {
+        self.generations[slot] = self.generations[slot].wrapping_add(1);
+    }
+
+    /// Add an element to the front of the deque.
+    ///
+    /// Return `Ok(handle)` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the deque is full.
+    pub fn push_front(&mut self, element: T) -> Result<SlotHandle, CapacityError<T>> {
+        self.deque.push_front(element)?;
+        Ok(self.handle(self.deque.tail()))
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// Return `Ok(handle)` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the deque is full.
+    pub fn push_back(&mut self, element: T) -> Result<SlotHandle, CapacityError<T>> {
+        let slot = self.deque.head();
+        self.deque.push_back(element)?;
+        Ok(self.handle(slot))
+    }
+
+    /// Appends an element to the back of the deque, kicking out the frontmost
+    /// element if the deque is full.
+    ///
+    /// Returns the handle of the new element, along with the kicked out one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    pub fn push_back_overwrite(&mut self, element: T) -> (SlotHandle, Option<T>) {
+        assert!(N > 0, "capacity must not be 0");
+        let existing = if self.is_full() {
+            self.pop_front()
+        } else {
+            None
+        };
+        match self.push_back(element) {
+            Ok(handle) => (handle, existing),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is
+    /// empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let slot = self.deque.tail();
+        let element = self.deque.pop_front()?;
+        self.release(slot);
+        Some(element)
+    }
+
+    /// Removes the last element and returns it, or `None` if the deque is
+    /// empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let element = self.deque.pop_back()?;
+        let slot = self.deque.head();
+        self.release(slot);
+        Some(element)
+    }
+
+    /// Removes every element, invalidating all handles.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Returns true if `handle` still refers to an element of the deque.
+    #[inline]
+    pub fn contains(&self, handle: SlotHandle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    #[inline]
+    fn index(&self, handle: SlotHandle) -> Option<usize> {
+        match self.generations.get(handle.slot) {
+            Some(&generation) if generation == handle.generation => {
+                let index = (handle.slot + N - self.deque.tail()) % N;
+                if index < self.len() {
+                    Some(index)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the element `handle` refers to, or `None` if it
+    /// has left the deque.
+    #[inline]
+    pub fn get(&self, handle: SlotHandle) -> Option<&T> {
+        self.index(handle)
+            .and_then(move |index| self.deque.get(index))
+    }
+
+    /// Returns a mutable reference to the element `handle` refers to, or
+    /// `None` if it has left the deque.
+    #[inline]
+    pub fn get_mut(&mut self, handle: SlotHandle) -> Option<&mut T> {
+        match self.index(handle) {
+            Some(index) => self.deque.get_mut(index),
+            None => None,
+        }
+    }
+
+    /// Returns a reference to the front element, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.deque.front()
+    }
+
+    /// Returns a reference to the back element, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.deque.back()
+    }
+
+    /// Returns a front-to-back iterator.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.deque.iter()
+    }
+}
+
+impl<T, const N: usize> Default for SlottedDeque<T, N> {
+    #[inline]
+    fn default() -> Self {
+        SlottedDeque::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SlottedDeque<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SlottedDeque;
+
+    #[test]
+    fn test_handles() {
+        let mut queue: SlottedDeque<usize, 3> = SlottedDeque::new();
+        // go around the buffer a few times
+        for lap in 0..4 {
+            let a = queue.push_back(lap).unwrap();
+            let b = queue.push_front(lap + 10).unwrap();
+            assert_eq!(queue.get(a), Some(&lap));
+            assert_eq!(queue.get(b), Some(&(lap + 10)));
+
+            *queue.get_mut(a).unwrap() += 100;
+            assert_eq!(queue.pop_back(), Some(lap + 100));
+            assert!(!queue.contains(a));
+            assert!(queue.contains(b));
+
+            // the slot of `a` is reused, but not its handle
+            let c = queue.push_back(lap + 20).unwrap();
+            assert_eq!(queue.get(a), None);
+            assert_eq!(queue.get(c), Some(&(lap + 20)));
+
+            queue.clear();
+            assert_eq!(queue.get(b), None);
+            assert_eq!(queue.get(c), None);
+            queue.push_back(0).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let mut queue: SlottedDeque<usize, 2> = SlottedDeque::new();
+        let handles: Vec<_> = (0..5).map(|i| queue.push_back_overwrite(i).0).collect();
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&3, &4]);
+        for (i, &handle) in handles.iter().enumerate() {
+            assert_eq!(queue.get(handle), if i < 3 { None } else { Some(&i) });
+        }
+        assert!(queue.push_back(5).is_err());
+    }
+}
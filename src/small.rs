@@ -0,0 +1,390 @@
+//! A deque stored inline until it outgrows its fixed capacity.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::{Chain, FromIterator};
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use array::Array;
+use behavior::Saturating;
+use {index_out_of_bounds, ArrayDeque};
+
+enum Storage<A: Array> {
+    Inline(ArrayDeque<A, Saturating>),
+    Heap(VecDeque<A::Item>),
+}
+
+/// A double-ended queue that stores its elements inline in an `ArrayDeque`,
+/// and transparently moves them to a heap allocated `VecDeque` once the
+/// inline capacity is exceeded.
+///
+/// This suits workloads which are usually small but occasionally spike. A
+/// spilled deque stays on the heap, even if it shrinks again.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::SmallDeque;
+///
+/// let mut buf: SmallDeque<[_; 2]> = SmallDeque::new();
+///
+/// buf.push_back(1);
+/// buf.push_back(2);
+/// assert!(buf.is_inline());
+///
+/// buf.push_back(3);
+/// assert!(!buf.is_inline());
+/// assert_eq!(buf.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+/// ```
+pub struct SmallDeque<A: Array> {
+    storage: Storage<A>,
+}
+
+impl<A: Array> SmallDeque<A> {
+    /// Creates an empty `SmallDeque`, stored inline.
+    #[inline]
+    pub fn new() -> SmallDeque<A> {
+        SmallDeque {
+            storage: Storage::Inline(ArrayDeque::new()),
+        }
+    }
+
+    /// Returns true if the elements are still stored inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        match self.storage {
+            Storage::Inline(_) => true,
+            Storage::Heap(_) => false,
+        }
+    }
+
+    /// Returns the number of elements the deque can hold without moving them
+    /// to a new location.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match self.storage {
+            Storage::Inline(ref deque) => deque.capacity(),
+            Storage::Heap(ref deque) => deque.capacity(),
+        }
+    }
+
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.storage {
+            Storage::Inline(ref deque) => deque.len(),
+            Storage::Heap(ref deque) => deque.len(),
+        }
+    }
+
+    /// Returns true if the deque contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Moves the elements to the heap, if they are not there already.
+    fn spill(&mut self) -> &mut VecDeque<A::Item> {
+        let heap = match self.storage {
+            Storage::Inline(ref mut deque) => {
+                let mut heap = VecDeque::with_capacity(2 * deque.capacity() + 1);
+                deque.spill_into(&mut heap);
+                heap
+            }
+            Storage::Heap(ref mut deque) => return deque,
+        };
+        self.storage = Storage::Heap(heap);
+        match self.storage {
+            Storage::Heap(ref mut deque) => deque,
+            Storage::Inline(_) => unreachable!(),
+        }
+    }
+
+    /// Add an element to the front of the deque, moving every element to the
+    /// heap if the inline storage is full.
+    pub fn push_front(&mut self, element: A::Item) {
+        let element = match self.storage {
+            Storage::Inline(ref mut deque) => match deque.push_front(element) {
+                Ok(()) => return,
+                Err(err) => err.element,
+            },
+            Storage::Heap(ref mut deque) => return deque.push_front(element),
+        };
+        self.spill().push_front(element);
+    }
+
+    /// Add an element to the back of the deque, moving every element to the
+    /// heap if the inline storage is full.
+    pub fn push_back(&mut self, element: A::Item) {
+        let element = match self.storage {
+            Storage::Inline(ref mut deque) => match deque.push_back(element) {
+                Ok(()) => return,
+                Err(err) => err.element,
+            },
+            Storage::Heap(ref mut deque) => return deque.push_back(element),
+        };
+        self.spill().push_back(element);
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<A::Item> {
+        match self.storage {
+            Storage::Inline(ref mut deque) => deque.pop_front(),
+            Storage::Heap(ref mut deque) => deque.pop_front(),
+        }
+    }
+
+    /// Removes the last element and returns it, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<A::Item> {
+        match self.storage {
+            Storage::Inline(ref mut deque) => deque.pop_back(),
+            Storage::Heap(ref mut deque) => deque.pop_back(),
+        }
+    }
+
+    /// Provides a reference to the front element, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn front(&self) -> Option<&A::Item> {
+        self.get(0)
+    }
+
+    /// Provides a mutable reference to the front element, or `None` if the
+    /// deque is empty.
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut A::Item> {
+        self.get_mut(0)
+    }
+
+    /// Provides a reference to the back element, or `None` if the deque is
+    /// empty.
+    #[inline]
+    pub fn back(&self) -> Option<&A::Item> {
+        match self.len() {
+            0 => None,
+            len => self.get(len - 1),
+        }
+    }
+
+    /// Provides a mutable reference to the back element, or `None` if the
+    /// deque is empty.
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut A::Item> {
+        match self.len() {
+            0 => None,
+            len => self.get_mut(len - 1),
+        }
+    }
+
+    /// Retrieves an element in the deque by index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&A::Item> {
+        match self.storage {
+            Storage::Inline(ref deque) => deque.get(index),
+            Storage::Heap(ref deque) => deque.get(index),
+        }
+    }
+
+    /// Retrieves an element in the deque mutably by index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut A::Item> {
+        match self.storage {
+            Storage::Inline(ref mut deque) => deque.get_mut(index),
+            Storage::Heap(ref mut deque) => deque.get_mut(index),
+        }
+    }
+
+    /// Clears the deque, removing all values. The storage stays where it is.
+    #[inline]
+    pub fn clear(&mut self) {
+        match self.storage {
+            Storage::Inline(ref mut deque) => deque.clear(),
+            Storage::Heap(ref mut deque) => deque.clear(),
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// deque.
+    #[inline]
+    pub fn as_slices(&self) -> (&[A::Item], &[A::Item]) {
+        match self.storage {
+            Storage::Inline(ref deque) => deque.as_slices(),
+            Storage::Heap(ref deque) => deque.as_slices(),
+        }
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the contents
+    /// of the deque.
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [A::Item], &mut [A::Item]) {
+        match self.storage {
+            Storage::Inline(ref mut deque) => deque.as_mut_slices(),
+            Storage::Heap(ref mut deque) => deque.as_mut_slices(),
+        }
+    }
+
+    /// Returns a front-to-back iterator.
+    #[inline]
+    pub fn iter(&self) -> Chain<slice::Iter<'_, A::Item>, slice::Iter<'_, A::Item>> {
+        let (a, b) = self.as_slices();
+        a.iter().chain(b.iter())
+    }
+
+    /// Returns a front-to-back iterator that returns mutable references.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, A::Item>, slice::IterMut<'_, A::Item>> {
+        let (a, b) = self.as_mut_slices();
+        a.iter_mut().chain(b.iter_mut())
+    }
+}
+
+impl<A: Array> Default for SmallDeque<A> {
+    #[inline]
+    fn default() -> Self {
+        SmallDeque::new()
+    }
+}
+
+impl<A: Array> Extend<A::Item> for SmallDeque<A> {
+    fn extend<T: IntoIterator<Item = A::Item>>(&mut self, iter: T) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl<A: Array> FromIterator<A::Item> for SmallDeque<A> {
+    fn from_iter<T: IntoIterator<Item = A::Item>>(iter: T) -> Self {
+        let mut deque = SmallDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<A: Array> Clone for SmallDeque<A>
+where
+    A::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        SmallDeque {
+            storage: match self.storage {
+                Storage::Inline(ref deque) => Storage::Inline(deque.clone()),
+                Storage::Heap(ref deque) => Storage::Heap(deque.clone()),
+            },
+        }
+    }
+}
+
+impl<A: Array> PartialEq for SmallDeque<A>
+where
+    A::Item: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<A: Array> Eq for SmallDeque<A> where A::Item: Eq {}
+
+impl<A: Array> Index<usize> for SmallDeque<A> {
+    type Output = A::Item;
+
+    #[inline]
+    fn index(&self, index: usize) -> &A::Item {
+        let len = self.len();
+        match self.get(index) {
+            Some(element) => element,
+            None => index_out_of_bounds(len, index),
+        }
+    }
+}
+
+impl<A: Array> IndexMut<usize> for SmallDeque<A> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut A::Item {
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => index_out_of_bounds(len, index),
+        }
+    }
+}
+
+impl<A: Array> fmt::Debug for SmallDeque<A>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use SmallDeque;
+
+    #[test]
+    fn test_spill() {
+        const CAP: usize = 4;
+        let mut tester: SmallDeque<[String; CAP]> = SmallDeque::new();
+
+        for i in 0..CAP {
+            if i % 2 == 0 {
+                tester.push_back(i.to_string());
+            } else {
+                tester.push_front(i.to_string());
+            }
+        }
+        assert!(tester.is_inline());
+        assert_eq!(tester.capacity(), CAP);
+        tester.push_front("x".to_string());
+        assert!(!tester.is_inline());
+        assert!(tester.capacity() > CAP);
+        tester.push_back("y".to_string());
+
+        let expected = ["x", "3", "1", "0", "2", "y"];
+        assert_eq!(tester.len(), expected.len());
+        assert!(tester.iter().eq(expected.iter()));
+        assert_eq!(tester[1], "3");
+        assert_eq!(tester.back().map(|s| &s[..]), Some("y"));
+
+        let cloned = tester.clone();
+        assert_eq!(cloned, tester);
+
+        tester.clear();
+        assert!(tester.is_empty());
+        assert!(!tester.is_inline());
+    }
+
+    #[test]
+    fn test_inline() {
+        let mut tester: SmallDeque<[_; 3]> = (0..3).collect();
+        assert!(tester.is_inline());
+        for x in tester.iter_mut() {
+            *x *= 2;
+        }
+        tester[0] = 7;
+        assert_eq!(format!("{:?}", tester), "[7, 2, 4]");
+        assert_eq!(tester.pop_front(), Some(7));
+        assert_eq!(tester.pop_back(), Some(4));
+        assert_eq!(tester.front(), Some(&2));
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 1 but the index is 1")]
+    #[cfg(not(feature = "index_trap"))]
+    fn test_index_overflow() {
+        let mut tester: SmallDeque<[u8; 2]> = SmallDeque::new();
+        tester.push_back(0);
+        let _ = tester[1];
+    }
+}
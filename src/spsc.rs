@@ -0,0 +1,385 @@
+//! A lock-free single-producer single-consumer queue.
+//!
+//! A `Queue` is `split` into a `Producer` and a `Consumer`, which can be moved
+//! to different threads, or an interrupt handler and the main loop. The
+//! producer only writes the position of the next push, and the consumer only
+//! writes the position of the next pop, so neither ever waits for the other.
+//!
+//! Requires Rust 1.63+ and a target with pointer-sized atomics; elsewhere
+//! the module does not exist.
+//!
+//! ```
+//! use std::thread;
+//! use arraydeque::spsc::Queue;
+//!
+//! let mut queue: Queue<[u32; 16]> = Queue::new();
+//! let (mut producer, mut consumer) = queue.split();
+//!
+//! thread::scope(|s| {
+//!     s.spawn(move || {
+//!         for sample in 0..100 {
+//!             while producer.push(sample).is_err() {
+//!                 thread::yield_now();
+//!             }
+//!         }
+//!     });
+//!
+//!     let mut expected = 0;
+//!     while expected < 100 {
+//!         if let Some(sample) = consumer.pop() {
+//!             assert_eq!(sample, expected);
+//!             expected += 1;
+//!         }
+//!     }
+//! });
+//! ```
+
+use std::cell::UnsafeCell;
+use std::cmp;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use array::Array;
+use error::CapacityError;
+
+/// A fixed-capacity queue, with the storage of an `ArrayDeque`, that can be
+/// split into a `Producer` and a `Consumer`.
+pub struct Queue<A: Array> {
+    buf: UnsafeCell<MaybeUninit<A>>,
+    // positions of the next push and of the next pop, in `0..2 * capacity`,
+    // so that a full queue can be told from an empty one
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+unsafe impl<A: Array> Send for Queue<A> where A::Item: Send {}
+
+impl<A: Array> Queue<A> {
+    /// Creates an empty `Queue`.
+    #[inline]
+    pub const fn new() -> Self {
+        Queue {
+            buf: UnsafeCell::new(MaybeUninit::uninit()),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the capacity of the queue.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// While the queue is split, the other half may push or pop at the same
+    /// time, so this is only a snapshot, at most the capacity, which may be
+    /// stale by the time it is returned.
+    #[inline]
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        // both halves may have gone around the queue between the two loads
+        cmp::min(distance(tail, head, A::capacity()), A::capacity())
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Splits the queue into its producer and consumer halves.
+    ///
+    /// The elements already in the queue stay there, for the consumer.
+    #[inline]
+    pub fn split(&mut self) -> (Producer<'_, A>, Consumer<'_, A>) {
+        (Producer { queue: self }, Consumer { queue: self })
+    }
+
+    #[inline]
+    fn slot(&self, pos: usize) -> *mut A::Item {
+        let index = if pos >= A::capacity() {
+            pos - A::capacity()
+        } else {
+            pos
+        };
+        unsafe { (self.buf.get() as *mut A::Item).add(index) }
+    }
+}
+
+/// Returns the number of pushes needed to get from position `tail` to
+/// position `head`.
+#[inline]
+fn distance(tail: usize, head: usize, cap: usize) -> usize {
+    if head >= tail {
+        head - tail
+    } else {
+        head + 2 * cap - tail
+    }
+}
+
+/// Returns the position after `pos`.
+#[inline]
+fn next(pos: usize, cap: usize) -> usize {
+    if pos + 1 == 2 * cap {
+        0
+    } else {
+        pos + 1
+    }
+}
+
+impl<A: Array> Default for Queue<A> {
+    #[inline]
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+impl<A: Array> Drop for Queue<A> {
+    fn drop(&mut self) {
+        let (_, mut consumer) = self.split();
+        while consumer.pop().is_some() {}
+    }
+}
+
+impl<A: Array> fmt::Debug for Queue<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Queue")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// The pushing half of a `Queue`.
+///
+/// This `struct` is created by the `split` method on `Queue`.
+pub struct Producer<'a, A: 'a + Array> {
+    queue: &'a Queue<A>,
+}
+
+unsafe impl<'a, A: Array> Send for Producer<'a, A> where A::Item: Send {}
+
+impl<'a, A: Array> Producer<'a, A> {
+    /// Add an element to the back of the queue.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the queue is full.
+    pub fn push(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if distance(tail, head, A::capacity()) == A::capacity() {
+            return Err(CapacityError { element });
+        }
+        unsafe { ptr::write(self.queue.slot(head), element) }
+        self.queue
+            .head
+            .store(next(head, A::capacity()), Ordering::Release);
+        Ok(())
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// This is a snapshot, see `Queue::len`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns `true` if the queue is full, so that the next push would fail
+    /// unless the consumer pops first.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.queue.len() == A::capacity()
+    }
+}
+
+impl<'a, A: Array> fmt::Debug for Producer<'a, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Producer").field(self.queue).finish()
+    }
+}
+
+/// The popping half of a `Queue`.
+///
+/// This `struct` is created by the `split` method on `Queue`.
+pub struct Consumer<'a, A: 'a + Array> {
+    queue: &'a Queue<A>,
+}
+
+unsafe impl<'a, A: Array> Send for Consumer<'a, A> where A::Item: Send {}
+
+impl<'a, A: Array> Consumer<'a, A> {
+    /// Removes the first element and returns it, or `None` if the queue is
+    /// empty.
+    pub fn pop(&mut self) -> Option<A::Item> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        if tail == head {
+            return None;
+        }
+        let element = unsafe { ptr::read(self.queue.slot(tail)) };
+        self.queue
+            .tail
+            .store(next(tail, A::capacity()), Ordering::Release);
+        Some(element)
+    }
+
+    /// Provides a reference to the first element, or `None` if the queue is
+    /// empty.
+    pub fn peek(&self) -> Option<&A::Item> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        if tail == head {
+            return None;
+        }
+        unsafe { Some(&*self.queue.slot(tail)) }
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// This is a snapshot, see `Queue::len`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+impl<'a, A: Array> fmt::Debug for Consumer<'a, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Consumer").field(self.queue).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::Queue;
+    use CapacityError;
+
+    #[test]
+    fn test_push_pop() {
+        // a capacity that is not a power of two
+        let mut queue: Queue<[usize; 3]> = Queue::new();
+        let (mut producer, mut consumer) = queue.split();
+        // go around the queue a few times
+        for lap in 0..4 {
+            for i in 0..3 {
+                producer.push(lap * 3 + i).unwrap();
+            }
+            assert!(producer.is_full());
+            assert_eq!(producer.push(99), Err(CapacityError { element: 99 }));
+            assert_eq!(consumer.len(), 3);
+            assert_eq!(consumer.peek(), Some(&(lap * 3)));
+            for i in 0..3 {
+                assert_eq!(consumer.pop(), Some(lap * 3 + i));
+            }
+            assert_eq!(consumer.pop(), None);
+            assert!(producer.is_empty());
+        }
+
+        producer.push(1).unwrap();
+        assert_eq!(
+            format!("{:?}", producer),
+            "Producer(Queue { len: 1, capacity: 3 })"
+        );
+        assert_eq!(queue.len(), 1);
+        let (_, mut consumer) = queue.split();
+        assert_eq!(consumer.pop(), Some(1));
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut queue: Queue<[u8; 0]> = Queue::new();
+        let (mut producer, mut consumer) = queue.split();
+        assert!(producer.is_full());
+        assert_eq!(producer.push(1), Err(CapacityError { element: 1 }));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_len_snapshot() {
+        let queue: Queue<[u8; 3]> = Queue::new();
+        // as if the tail was loaded before both halves went around the queue
+        queue.head.store(5, Ordering::Relaxed);
+        assert_eq!(queue.len(), 3);
+        queue.head.store(0, Ordering::Relaxed);
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[test]
+    fn test_drop_remaining() {
+        struct D(Arc<AtomicUsize>);
+        impl Drop for D {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        {
+            let mut queue: Queue<[D; 4]> = Queue::new();
+            let (mut producer, mut consumer) = queue.split();
+            for _ in 0..3 {
+                producer.push(D(dropped.clone())).ok().unwrap();
+            }
+            drop(consumer.pop());
+            assert_eq!(dropped.load(Ordering::SeqCst), 1);
+        }
+        assert_eq!(dropped.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_concurrent() {
+        const COUNT: usize = 10000;
+
+        let mut queue: Queue<[usize; 7]> = Queue::new();
+        let (mut producer, mut consumer) = queue.split();
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..COUNT {
+                    let mut element = i;
+                    while let Err(err) = producer.push(element) {
+                        element = err.element;
+                        thread::yield_now();
+                    }
+                }
+            });
+            s.spawn(move || {
+                let mut expected = 0;
+                while expected < COUNT {
+                    match consumer.pop() {
+                        Some(i) => {
+                            assert_eq!(i, expected);
+                            expected += 1;
+                        }
+                        None => {
+                            assert!(consumer.len() <= 7);
+                            thread::yield_now()
+                        }
+                    }
+                }
+            });
+        });
+        assert!(queue.is_empty());
+    }
+}
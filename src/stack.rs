@@ -0,0 +1,216 @@
+//! A fixed-capacity LIFO stack on top of `ArrayDeque`.
+
+use std::fmt;
+
+use array::Array;
+use behavior::{Behavior, Hooks, Saturating, Wrapping};
+use error::CapacityError;
+use {ArrayDeque, Iter};
+
+/// A fixed-capacity stack, pushing and popping at its top only.
+///
+/// The behavior `B` is the overflow policy: a `Saturating` stack rejects
+/// pushes once full, and a `Wrapping` stack kicks out its bottom element to
+/// make room, keeping the most recent ones.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::{ArrayStack, CapacityError, Wrapping};
+///
+/// let mut stack: ArrayStack<[_; 2]> = ArrayStack::new();
+/// stack.push(1).unwrap();
+/// stack.push(2).unwrap();
+/// assert_eq!(stack.push(3), Err(CapacityError { element: 3 }));
+/// assert_eq!(stack.pop(), Some(2));
+///
+/// let mut undo: ArrayStack<[_; 2], Wrapping> = ArrayStack::new();
+/// undo.push("type");
+/// undo.push("delete");
+/// assert_eq!(undo.push("paste"), Some("type"));
+/// assert_eq!(undo.peek(), Some(&"paste"));
+/// ```
+pub struct ArrayStack<A: Array, B: Behavior = Saturating> {
+    // the bottom of the stack is the front of the deque
+    deque: ArrayDeque<A, B>,
+}
+
+impl<A: Array, B: Behavior> ArrayStack<A, B> {
+    /// Creates an empty `ArrayStack`.
+    #[inline]
+    pub fn new() -> Self {
+        ArrayStack {
+            deque: ArrayDeque::new(),
+        }
+    }
+
+    /// Returns the underlying deque, whose front is the bottom of the stack.
+    #[inline]
+    pub fn into_deque(self) -> ArrayDeque<A, B> {
+        self.deque
+    }
+
+    /// Returns the capacity of the stack.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.deque.capacity()
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Returns true if the stack is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.deque.is_full()
+    }
+
+    /// Provides a reference to the top element, or `None` if the stack is
+    /// empty.
+    #[inline]
+    pub fn peek(&self) -> Option<&A::Item> {
+        self.deque.back()
+    }
+
+    /// Provides a mutable reference to the top element, or `None` if the
+    /// stack is empty.
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<&mut A::Item> {
+        self.deque.back_mut()
+    }
+
+    /// Removes the top element and returns it, or `None` if the stack is
+    /// empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<A::Item> {
+        self.deque.pop_back()
+    }
+
+    /// Removes every element.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.deque.clear();
+    }
+
+    /// Returns a bottom-to-top iterator.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, A::Item> {
+        self.deque.iter()
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> ArrayStack<A, Saturating<H>> {
+    /// Pushes an element on top of the stack.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the stack is full.
+    #[inline]
+    pub fn push(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.deque.push_back(element)
+    }
+}
+
+impl<A: Array, H: Hooks<A::Item>> ArrayStack<A, Wrapping<H>> {
+    /// Pushes an element on top of the stack.
+    ///
+    /// Return `None` if the push succeeds, or kicks out and returns the
+    /// bottom element if the stack is full.
+    #[inline]
+    pub fn push(&mut self, element: A::Item) -> Option<A::Item> {
+        self.deque.push_back(element)
+    }
+}
+
+impl<A: Array, B: Behavior> From<ArrayDeque<A, B>> for ArrayStack<A, B> {
+    /// Creates a stack whose bottom is the front of `deque`.
+    #[inline]
+    fn from(deque: ArrayDeque<A, B>) -> Self {
+        ArrayStack { deque }
+    }
+}
+
+impl<A: Array, B: Behavior> Clone for ArrayStack<A, B>
+where
+    ArrayDeque<A, B>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        ArrayStack {
+            deque: self.deque.clone(),
+        }
+    }
+}
+
+impl<A: Array, B: Behavior> Default for ArrayStack<A, B> {
+    #[inline]
+    fn default() -> Self {
+        ArrayStack::new()
+    }
+}
+
+impl<'a, A: Array, B: Behavior> IntoIterator for &'a ArrayStack<A, B> {
+    type Item = &'a A::Item;
+    type IntoIter = Iter<'a, A::Item>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<A: Array, B: Behavior> fmt::Debug for ArrayStack<A, B>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArrayStack;
+    use {ArrayDeque, CapacityError, Wrapping};
+
+    #[test]
+    fn test_saturating() {
+        let mut tester: ArrayStack<[_; 3]> = ArrayStack::new();
+        assert_eq!(tester.pop(), None);
+        for i in 0..3 {
+            assert_eq!(tester.push(i), Ok(()));
+            assert_eq!(tester.peek(), Some(&i));
+        }
+        assert!(tester.is_full());
+        assert_eq!(tester.push(3), Err(CapacityError { element: 3 }));
+
+        *tester.peek_mut().unwrap() = 20;
+        let cloned = tester.clone();
+        assert_eq!(tester.pop(), Some(20));
+        assert_eq!(tester.len(), 2);
+        assert_eq!(format!("{:?}", cloned), "[0, 1, 20]");
+
+        tester.clear();
+        assert!(tester.is_empty());
+        assert_eq!(tester.capacity(), 3);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let deque: ArrayDeque<[_; 3], Wrapping> = vec![1, 2].into();
+        let mut tester = ArrayStack::from(deque);
+        assert_eq!(tester.push(3), None);
+        assert_eq!(tester.push(4), Some(1));
+        assert!(tester.iter().eq(&[2, 3, 4]));
+        assert_eq!(tester.pop(), Some(4));
+        assert_eq!(tester.into_deque(), vec![2, 3].into());
+    }
+}
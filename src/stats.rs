@@ -0,0 +1,154 @@
+//! One-pass summaries of numeric deques.
+
+use std::fmt;
+
+use array::Array;
+use behavior::Behavior;
+use ArrayDeque;
+
+/// Primitive numeric types that can be summarized by `ArrayDeque::stats`.
+pub trait Numeric: Copy + PartialOrd {
+    /// The type the sum is accumulated in, wide enough not to overflow.
+    type Sum: Copy + PartialEq + fmt::Debug;
+
+    /// Returns an empty sum.
+    fn zero() -> Self::Sum;
+
+    /// Adds `self` to `sum`.
+    fn accumulate(self, sum: Self::Sum) -> Self::Sum;
+
+    /// Returns the mean of `count` values adding up to `sum`.
+    fn mean(sum: Self::Sum, count: usize) -> f64;
+}
+
+macro_rules! numeric_impl {
+    ($sum:ty, $zero:expr; $($ty:ty)*) => {
+        $(
+            impl Numeric for $ty {
+                type Sum = $sum;
+
+                #[inline]
+                fn zero() -> $sum {
+                    $zero
+                }
+
+                #[inline]
+                fn accumulate(self, sum: $sum) -> $sum {
+                    sum + self as $sum
+                }
+
+                #[inline]
+                fn mean(sum: $sum, count: usize) -> f64 {
+                    sum as f64 / count as f64
+                }
+            }
+        )*
+    };
+}
+
+numeric_impl!(u128, 0; u8 u16 u32 u64 usize);
+numeric_impl!(i128, 0; i8 i16 i32 i64 isize);
+numeric_impl!(f64, 0.0; f32 f64);
+
+/// The minimum, maximum, sum and mean of the elements of an `ArrayDeque`.
+///
+/// This `struct` is created by the `stats` method on `ArrayDeque`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats<T: Numeric> {
+    /// The minimum element.
+    pub min: T,
+    /// The maximum element.
+    pub max: T,
+    /// The sum of all elements.
+    pub sum: T::Sum,
+    /// The arithmetic mean of all elements.
+    pub mean: f64,
+}
+
+impl<A: Array, B: Behavior> ArrayDeque<A, B>
+where
+    A::Item: Numeric,
+{
+    /// Returns the minimum, maximum, sum and mean of the elements, computed
+    /// in a single pass, or `None` if the deque is empty.
+    ///
+    /// Integers are summed in `u128` or `i128` and floats in `f64`. NaN
+    /// values are skipped by the minimum and maximum, unless they come first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Stats};
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    /// assert_eq!(buf.stats(), None);
+    ///
+    /// buf.extend_back(vec![200, 100, 250, 50]);
+    ///
+    /// assert_eq!(buf.stats(), Some(Stats { min: 50, max: 250, sum: 600, mean: 150.0 }));
+    /// ```
+    pub fn stats(&self) -> Option<Stats<A::Item>> {
+        let first = match self.front() {
+            Some(&first) => first,
+            None => return None,
+        };
+        let mut min = first;
+        let mut max = first;
+        let mut sum = A::Item::zero();
+        let (a, b) = self.as_slices();
+        for &x in a.iter().chain(b) {
+            if x < min {
+                min = x;
+            }
+            if x > max {
+                max = x;
+            }
+            sum = x.accumulate(sum);
+        }
+        Some(Stats {
+            min,
+            max,
+            sum,
+            mean: A::Item::mean(sum, self.len()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_must_use)]
+    use {ArrayDeque, Stats, Wrapping};
+
+    #[test]
+    fn test_stats_wrap() {
+        const CAP: usize = 5;
+        let mut tester: ArrayDeque<[i64; CAP], Wrapping> = ArrayDeque::new();
+
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            tester.extend_back(vec![i64::MAX, i64::MAX, -4, 7, 0, 1]);
+            let stats = tester.stats().unwrap();
+            assert_eq!(stats.min, -4);
+            assert_eq!(stats.max, i64::MAX);
+            assert_eq!(stats.sum, i64::MAX as i128 + 4);
+        }
+    }
+
+    #[test]
+    fn test_stats_float() {
+        let mut tester: ArrayDeque<[f32; 4]> = ArrayDeque::new();
+        tester.extend_back(vec![1.5, -0.5, 2.0]);
+        assert_eq!(
+            tester.stats(),
+            Some(Stats {
+                min: -0.5,
+                max: 2.0,
+                sum: 3.0,
+                mean: 1.0,
+            })
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! A strided iterator over an `ArrayDeque`.
+
+use std::iter::FusedIterator;
+
+use Iter;
+
+/// An iterator over every `k`-th element of an `ArrayDeque`, front to back.
+///
+/// Skipped elements are jumped over by index instead of being visited.
+///
+/// This `struct` is created by the `iter_step` and `latest_every` methods on
+/// `ArrayDeque`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct IterStep<'a, T: 'a> {
+    iter: Iter<'a, T>,
+    step: usize,
+}
+
+impl<'a, T> IterStep<'a, T> {
+    #[inline]
+    pub(crate) fn new(iter: Iter<'a, T>, step: usize) -> Self {
+        assert!(step != 0, "step must be non-zero");
+        IterStep { iter, step }
+    }
+}
+
+impl<'a, T> Iterator for IterStep<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        let element = self.iter.next();
+        self.iter.advance(self.step - 1);
+        element
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.iter.len() {
+            0 => 0,
+            remaining => (remaining - 1) / self.step + 1,
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterStep<'a, T> {}
+
+impl<'a, T> FusedIterator for IterStep<'a, T> {}
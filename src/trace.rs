@@ -0,0 +1,251 @@
+//! Trace events for diagnosing queue behavior in production.
+//!
+//! With the `trace` feature, pushes, insertions, pops, evictions, rejected
+//! pushes and drains of every `ArrayDeque` report an `Event` to a process-wide
+//! subscriber function. Until one is installed, reporting costs a single
+//! atomic load.
+//!
+//! Extending a deque reports every element pushed, and every element kicked
+//! out to spare room is reported as evicted, whichever method pushed.
+//!
+//! The subscriber is a plain function, so the events can be forwarded to the
+//! `tracing` or `log` crates, or to a counter, without this crate depending
+//! on them:
+//!
+//! ```
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use arraydeque::{trace, ArrayDeque, Wrapping};
+//!
+//! static EVICTIONS: AtomicUsize = AtomicUsize::new(0);
+//!
+//! fn subscriber(event: &trace::Event) {
+//!     // or `tracing::trace!(op = ?event.op, len = event.len)`
+//!     if event.op == trace::Op::Evict {
+//!         EVICTIONS.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! trace::set_subscriber(subscriber);
+//!
+//! let mut buf: ArrayDeque<[_; 2], Wrapping> = ArrayDeque::new();
+//! buf.extend_back(0..5);
+//! assert_eq!(EVICTIONS.load(Ordering::Relaxed), 3);
+//!
+//! trace::clear_subscriber();
+//! ```
+
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// An operation reported by a deque.
+///
+/// More operations may be added in the future.
+#[cfg_attr(has_non_exhaustive, non_exhaustive)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Op {
+    /// An element was pushed to the back.
+    PushBack,
+    /// An element was pushed to the front.
+    PushFront,
+    /// An element was popped from the back.
+    PopBack,
+    /// An element was popped from the front.
+    PopFront,
+    /// A push or an insertion to a full deque was rejected.
+    Reject,
+    /// An element was inserted at this index.
+    Insert(usize),
+    /// An element was kicked out to spare room.
+    Evict,
+    /// A range of this many elements was drained.
+    Drain(usize),
+}
+
+/// An operation and the state of the deque right after it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Event {
+    /// The operation.
+    pub op: Op,
+    /// The number of elements in the deque after the operation.
+    pub len: usize,
+    /// The capacity of the deque.
+    pub capacity: usize,
+}
+
+static SUBSCRIBER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Installs `subscriber` to receive the events of every deque, replacing the
+/// previous one.
+///
+/// The subscriber is called synchronously, from the thread operating on the
+/// deque, so it should be quick and must not operate on deques itself.
+#[inline]
+pub fn set_subscriber(subscriber: fn(&Event)) {
+    SUBSCRIBER.store(subscriber as *mut (), Ordering::Release);
+}
+
+/// Removes the subscriber, so events are no longer reported.
+#[inline]
+pub fn clear_subscriber() {
+    SUBSCRIBER.store(ptr::null_mut(), Ordering::Release);
+}
+
+/// Reports an event to the subscriber, if any.
+#[inline]
+pub(crate) fn emit(op: Op, len: usize, capacity: usize) {
+    let subscriber = SUBSCRIBER.load(Ordering::Acquire);
+    if !subscriber.is_null() {
+        let subscriber: fn(&Event) = unsafe { mem::transmute(subscriber) };
+        subscriber(&Event { op, len, capacity });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::{clear_subscriber, set_subscriber, Event, Op};
+    use {ArrayDeque, Decimating, Wrapping};
+
+    thread_local! {
+        static EVENTS: RefCell<Vec<Event>> = RefCell::new(Vec::new());
+    }
+
+    fn record(event: &Event) {
+        EVENTS.with(|events| events.borrow_mut().push(*event));
+    }
+
+    fn take() -> Vec<(Op, usize)> {
+        EVENTS.with(|events| {
+            events
+                .borrow_mut()
+                .drain(..)
+                .map(|e| (e.op, e.len))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_events() {
+        set_subscriber(record);
+        take();
+
+        let mut saturating: ArrayDeque<[_; 2]> = ArrayDeque::new();
+        saturating.push_back(1).unwrap();
+        saturating.push_front(0).unwrap();
+        saturating.push_back(2).unwrap_err();
+        saturating.pop_back();
+        saturating.pop_front();
+        saturating.pop_front();
+        assert_eq!(
+            take(),
+            vec![
+                (Op::PushBack, 1),
+                (Op::PushFront, 2),
+                (Op::Reject, 2),
+                (Op::PopBack, 1),
+                (Op::PopFront, 0),
+            ]
+        );
+
+        let mut wrapping: ArrayDeque<[_; 2], Wrapping> = ArrayDeque::new();
+        wrapping.extend_back(0..3);
+        wrapping.push_front(9);
+        wrapping.drain(..1);
+        assert_eq!(
+            take(),
+            vec![
+                (Op::PushBack, 1),
+                (Op::PushBack, 2),
+                (Op::Evict, 1),
+                (Op::PushBack, 2),
+                (Op::Evict, 1),
+                (Op::PushFront, 2),
+                (Op::Drain(1), 1),
+            ]
+        );
+
+        let mut decimating: ArrayDeque<[_; 4], Decimating> = ArrayDeque::new();
+        decimating.extend_back(0..4);
+        take();
+        decimating.push_back(4);
+        assert_eq!(
+            take(),
+            vec![(Op::Evict, 2), (Op::Evict, 2), (Op::PushBack, 3)]
+        );
+
+        let mut shared: ArrayDeque<[_; 2]> = ArrayDeque::new();
+        shared.push_back_overwrite(1);
+        shared.push_front_overwrite(0);
+        shared.push_back_overwrite(2);
+        shared.push_front_overwrite(3);
+        assert_eq!(
+            take(),
+            vec![
+                (Op::PushBack, 1),
+                (Op::PushFront, 2),
+                (Op::Evict, 1),
+                (Op::PushBack, 2),
+                (Op::Evict, 1),
+                (Op::PushFront, 2),
+            ]
+        );
+
+        shared.clear();
+        take();
+        shared.try_push_back(1).unwrap();
+        shared.try_push_front(0).unwrap();
+        shared.try_push_back(2).unwrap_err();
+        shared.pop_back();
+        shared.try_insert(0, 5).unwrap();
+        shared.insert(0, 6).unwrap_err();
+        assert_eq!(
+            take(),
+            vec![
+                (Op::PushBack, 1),
+                (Op::PushFront, 2),
+                (Op::Reject, 2),
+                (Op::PopBack, 1),
+                (Op::Insert(0), 2),
+                (Op::Reject, 2),
+            ]
+        );
+
+        let mut slice: ArrayDeque<[u8; 3]> = ArrayDeque::new();
+        slice.extend_from_copy_slice(&[1, 2]).unwrap();
+        slice.try_extend_from_slice(&[3]).unwrap();
+        slice.extend_from_copy_slice(&[5]).unwrap_err();
+        assert_eq!(
+            take(),
+            vec![
+                (Op::PushBack, 1),
+                (Op::PushBack, 2),
+                (Op::PushBack, 3),
+                (Op::Reject, 3),
+            ]
+        );
+
+        #[cfg(feature = "bytes")]
+        {
+            let mut text: ArrayDeque<[u8; 3], Wrapping> = ArrayDeque::new();
+            text.push_str("ab");
+            text.push_str("cd");
+            assert_eq!(
+                take(),
+                vec![
+                    (Op::PushBack, 1),
+                    (Op::PushBack, 2),
+                    (Op::Evict, 1),
+                    (Op::PushBack, 2),
+                    (Op::PushBack, 3),
+                ]
+            );
+        }
+
+        take();
+        clear_subscriber();
+        decimating.pop_back();
+        assert!(take().is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+//! All-or-nothing batches of pushes.
+
+use std::fmt;
+use std::ops::Deref;
+
+use array::Array;
+use behavior::{Hooks, NoHooks, Saturating};
+use error::CapacityError;
+use ArrayDeque;
+
+/// A batch of pushes into an `ArrayDeque` that is either kept as a whole or
+/// rolled back.
+///
+/// Elements pushed through the transaction are only kept if `commit` is
+/// called. If the transaction is dropped instead, for example because a push
+/// failed and the error was propagated with `?`, every element it pushed is
+/// removed again and dropped, leaving the deque as it was.
+///
+/// The elements inserted among the ones already in the deque, rather than
+/// next to the ones pushed to its front or back, must form a single run,
+/// so the transaction knows which elements to roll back without recording
+/// every index. See `insert`.
+///
+/// This `struct` is created by the `transaction` method on `ArrayDeque`.
+pub struct Transaction<'a, A: 'a + Array, H: 'a = NoHooks> {
+    deque: &'a mut ArrayDeque<A, Saturating<H>>,
+    front: usize,
+    back: usize,
+    /// The number of elements found in the deque before the inserted run.
+    after: usize,
+    /// The number of elements in the inserted run.
+    middle: usize,
+}
+
+impl<'a, A: Array, H: Hooks<A::Item>> Transaction<'a, A, H> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, Saturating<H>>) -> Self {
+        Transaction {
+            deque,
+            front: 0,
+            back: 0,
+            after: 0,
+            middle: 0,
+        }
+    }
+
+    /// Add an element to the front of the deque.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the deque is full.
+    #[inline]
+    pub fn push_front(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.deque.push_front(element)?;
+        self.front += 1;
+        Ok(())
+    }
+
+    /// Add an element to the back of the deque.
+    ///
+    /// Return `Ok(())` if the push succeeds, or return `Err(CapacityError { *element* })`
+    /// if the deque is full.
+    #[inline]
+    pub fn push_back(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.deque.push_back(element)?;
+        self.back += 1;
+        Ok(())
+    }
+
+    /// Inserts an element at `index` within the deque.
+    ///
+    /// Return `Ok(())` if the insertion succeeds, or return `Err(CapacityError { *element* })`
+    /// if the deque is full.
+    ///
+    /// An element inserted among the ones pushed to the front or back by the
+    /// transaction joins them. Otherwise every element inserted among the
+    /// ones found in the deque must be next to the previous ones, so that
+    /// they form a single run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of the deque, or if
+    /// inserting there would start a second run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    /// buf.extend_back(vec![1, 4]);
+    ///
+    /// {
+    ///     let mut tx = buf.transaction();
+    ///     tx.insert(1, 2).unwrap();
+    ///     tx.insert(2, 3).unwrap();
+    ///     assert!(tx.insert(0, 0).is_err());
+    /// }
+    /// assert_eq!(buf, vec![1, 4].into());
+    /// ```
+    pub fn insert(&mut self, index: usize, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        let len = self.deque.len();
+        assert!(index <= len, "index out of bounds");
+
+        let start = self.front + self.after;
+        let joins_run = start <= index && index <= start + self.middle;
+        assert!(
+            index <= self.front || index >= len - self.back || self.middle == 0 || joins_run,
+            "a transaction inserts a single run among the elements found in the deque"
+        );
+
+        self.deque.insert(index, element)?;
+        if index <= self.front {
+            self.front += 1;
+        } else if index >= len - self.back {
+            self.back += 1;
+        } else if self.middle == 0 {
+            self.after = index - self.front;
+            self.middle = 1;
+        } else {
+            self.middle += 1;
+        }
+        Ok(())
+    }
+
+    /// Add every element of an iterator to the back of the deque.
+    ///
+    /// Stops at the first element that does not fit, returning it in
+    /// `Err(CapacityError { *element* })`.
+    pub fn extend_back<I>(&mut self, iter: I) -> Result<(), CapacityError<A::Item>>
+    where
+        I: IntoIterator<Item = A::Item>,
+    {
+        for element in iter {
+            self.push_back(element)?;
+        }
+        Ok(())
+    }
+
+    /// Keeps every element pushed by the transaction.
+    #[inline]
+    pub fn commit(mut self) {
+        self.front = 0;
+        self.back = 0;
+        self.middle = 0;
+    }
+}
+
+impl<'a, A: Array, H> Deref for Transaction<'a, A, H> {
+    type Target = ArrayDeque<A, Saturating<H>>;
+
+    #[inline]
+    fn deref(&self) -> &ArrayDeque<A, Saturating<H>> {
+        self.deque
+    }
+}
+
+impl<'a, A: Array, H> Drop for Transaction<'a, A, H> {
+    fn drop(&mut self) {
+        for _ in 0..self.back {
+            self.deque.pop_back();
+        }
+        for _ in 0..self.front {
+            self.deque.pop_front();
+        }
+        self.deque.drain(self.after..self.after + self.middle);
+    }
+}
+
+impl<'a, A: Array, H> fmt::Debug for Transaction<'a, A, H>
+where
+    A::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Transaction")
+            .field("deque", &self.deque)
+            .field("front", &self.front)
+            .field("back", &self.back)
+            .field("after", &self.after)
+            .field("middle", &self.middle)
+            .finish()
+    }
+}
@@ -0,0 +1,290 @@
+//! A read-only deque view over borrowed slices.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Chain;
+use std::slice;
+
+use index;
+use range::RangeArgument;
+
+/// A read-only view of a ring buffer whose contents are split over two
+/// borrowed slices, front part first.
+///
+/// The view provides the read API of `ArrayDeque` without owning any data,
+/// so the same logic can parse buffers owned elsewhere, such as a DMA region
+/// or a ring shared with another process.
+///
+/// This `struct` is created by `RingView::new`, or by the `view` method on
+/// `ArrayDeque`.
+///
+/// # Examples
+///
+/// ```
+/// use arraydeque::RingView;
+///
+/// // a ring of 6 bytes whose head wrapped around to index 2
+/// let raw = [4, 5, 0, 0, 2, 3];
+/// let view = RingView::new(&raw[4..], &raw[..2]);
+///
+/// assert_eq!(view.len(), 4);
+/// assert_eq!(view.get(2), Some(&4));
+/// assert!(view.iter().eq(&[2, 3, 4, 5]));
+/// assert_eq!(view.binary_search(&4), Ok(2));
+/// ```
+pub struct RingView<'a, T: 'a> {
+    front: &'a [T],
+    back: &'a [T],
+}
+
+impl<'a, T> RingView<'a, T> {
+    /// Creates a view whose elements are those of `front`, followed by those
+    /// of `back`.
+    #[inline]
+    pub fn new(front: &'a [T], back: &'a [T]) -> Self {
+        RingView { front, back }
+    }
+
+    /// Returns the number of elements in the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Returns true if the view contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the pair of slices the view was made of.
+    #[inline]
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        (self.front, self.back)
+    }
+
+    /// Returns a reference to the front element, or `None` if the view is
+    /// empty.
+    #[inline]
+    pub fn front(&self) -> Option<&'a T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the back element, or `None` if the view is
+    /// empty.
+    #[inline]
+    pub fn back(&self) -> Option<&'a T> {
+        match self.back.last() {
+            Some(element) => Some(element),
+            None => self.front.last(),
+        }
+    }
+
+    /// Retrieves an element by index, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.front.len() {
+            self.front.get(index)
+        } else {
+            self.back.get(index - self.front.len())
+        }
+    }
+
+    /// Retrieves the pair of slices which contain, in order, the elements in
+    /// a range, or `None` if the range is out of bounds.
+    #[inline]
+    pub fn get_range<R>(&self, range: R) -> Option<(&'a [T], &'a [T])>
+    where
+        R: RangeArgument<usize>,
+    {
+        index::get_range(self.as_slices(), range)
+    }
+
+    /// Returns a view of the elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the
+    /// end point is greater than the length of the view.
+    pub fn range<R>(&self, range: R) -> RingView<'a, T>
+    where
+        R: RangeArgument<usize>,
+    {
+        let len = self.len();
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(len);
+        assert!(start <= end, "range lower bound was too large");
+        assert!(end <= len, "range upper bound was too large");
+
+        match self.get_range(start..end) {
+            Some((front, back)) => RingView::new(front, back),
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns a front-to-back iterator.
+    #[inline]
+    pub fn iter(&self) -> Chain<slice::Iter<'a, T>, slice::Iter<'a, T>> {
+        self.front.iter().chain(self.back.iter())
+    }
+
+    /// Binary searches the sorted view for `x`.
+    ///
+    /// Return `Ok(index)` of a matching element, or `Err(index)` where `x`
+    /// could be inserted while keeping the order.
+    #[inline]
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|element| element.cmp(x))
+    }
+
+    /// Binary searches the sorted view with a comparator function.
+    ///
+    /// The comparator returns the order of its argument relative to the
+    /// target. Return `Ok(index)` of a matching element, or `Err(index)`
+    /// where the target could be inserted while keeping the order.
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        // the target can only be in `front` if it is not past its end
+        match self.front.last().map(&mut f) {
+            Some(Ordering::Less) | None => self
+                .back
+                .binary_search_by(f)
+                .map(|i| i + self.front.len())
+                .map_err(|i| i + self.front.len()),
+            Some(_) => self.front.binary_search_by(f),
+        }
+    }
+
+    /// Binary searches the sorted view for the key extracted by `f`.
+    #[inline]
+    pub fn binary_search_by_key<K, F>(&self, key: &K, mut f: F) -> Result<usize, usize>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.binary_search_by(|element| f(element).cmp(key))
+    }
+
+    /// Returns the index of the first element for which `pred` is false,
+    /// assuming that the view is partitioned by `pred`: true for every
+    /// element before that index, and false for every element after it.
+    #[inline]
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.binary_search_by(|element| {
+            if pred(element) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_or_else(|i| i)
+    }
+}
+
+impl<'a, T> Clone for RingView<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for RingView<'a, T> {}
+
+impl<'a, T> IntoIterator for RingView<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, 'b, T, U> PartialEq<RingView<'b, U>> for RingView<'a, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &RingView<'b, U>) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<'a, T: Eq> Eq for RingView<'a, T> {}
+
+impl<'a, T: fmt::Debug> fmt::Debug for RingView<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RingView;
+    use ArrayDeque;
+
+    #[test]
+    fn test_binary_search() {
+        let raw = [6, 8, 9, 1, 3, 3, 5];
+        for split in 0..raw.len() + 1 {
+            // rotate so that the sorted contents start at `split`
+            let mut rotated = raw.to_vec();
+            rotated.sort();
+            rotated.rotate_right(split);
+            let view = RingView::new(&rotated[split..], &rotated[..split]);
+            for x in 0..11 {
+                let sorted: Vec<_> = view.iter().cloned().collect();
+                match (view.binary_search(&x), sorted.binary_search(&x)) {
+                    (Ok(i), Ok(_)) => assert_eq!(sorted[i], x),
+                    (left, right) => assert_eq!(left, right),
+                }
+                let point = sorted.iter().take_while(|&&y| y < x).count();
+                assert_eq!(view.partition_point(|&y| y < x), point);
+            }
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        let raw = [4, 5, 0, 1, 2, 3];
+        let view = RingView::new(&raw[2..], &raw[..2]);
+        assert_eq!(view.range(1..5), RingView::new(&[1, 2, 3, 4], &[]));
+        assert!(view.range(3..).iter().eq(&[3, 4, 5]));
+        assert!(view.range(..0).is_empty());
+        assert_eq!(view.front(), Some(&0));
+        assert_eq!(view.back(), Some(&5));
+        assert_eq!(view.range(..4).back(), Some(&3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_out_of_bounds() {
+        let raw = [0, 1];
+        RingView::new(&raw, &[]).range(1..3);
+    }
+
+    #[test]
+    fn test_view_of_deque() {
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<[_; CAP]> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_tail(padding);
+                tester.set_len(0);
+            }
+            tester.extend_back(0..3);
+            let view = tester.view();
+            assert_eq!(view.len(), 3);
+            assert!(view.iter().eq(tester.iter()));
+            assert_eq!(view.get(1), tester.get(1));
+            assert_eq!(view.binary_search(&2), Ok(2));
+        }
+    }
+}
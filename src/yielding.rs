@@ -0,0 +1,190 @@
+//! Futures that move elements in bounded chunks, yielding to the executor
+//! in between.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use array::Array;
+use behavior::Behavior;
+use ArrayDeque;
+
+/// A future that extends the back of an `ArrayDeque` from an iterator,
+/// `chunk` elements per poll.
+///
+/// Between chunks it wakes its own task and returns `Poll::Pending`, so the
+/// executor can run other tasks while hundreds of thousands of elements are
+/// moved. It resolves to the number of elements appended, once the iterator
+/// runs dry or the deque is full, whatever the behavior.
+///
+/// This `struct` is created by the `extend_yielding` method on `ArrayDeque`.
+///
+/// Requires the `futures` feature and Rust 1.36+.
+#[must_use = "futures do nothing unless polled"]
+pub struct ExtendYielding<'a, A: 'a + Array, B: 'a + Behavior, I> {
+    deque: &'a mut ArrayDeque<A, B>,
+    iter: I,
+    chunk: usize,
+    count: usize,
+}
+
+impl<'a, A: Array, B: Behavior, I> ExtendYielding<'a, A, B, I> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, B>, iter: I, chunk: usize) -> Self {
+        assert!(chunk > 0, "chunk size must be non-zero");
+        ExtendYielding {
+            deque,
+            iter,
+            chunk,
+            count: 0,
+        }
+    }
+}
+
+impl<'a, A, B, I> Future for ExtendYielding<'a, A, B, I>
+where
+    A: Array,
+    B: Behavior,
+    I: Iterator<Item = A::Item> + Unpin,
+{
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        let this = self.get_mut();
+        for _ in 0..this.chunk {
+            if this.deque.is_full() {
+                return Poll::Ready(this.count);
+            }
+            match this.iter.next() {
+                Some(element) => unsafe { this.deque.push_back_unchecked(element) },
+                None => return Poll::Ready(this.count),
+            }
+            this.count += 1;
+        }
+        if this.deque.is_full() {
+            return Poll::Ready(this.count);
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// A future that pops every element from the front of an `ArrayDeque` and
+/// hands it to a closure, `chunk` elements per poll.
+///
+/// Between chunks it wakes its own task and returns `Poll::Pending`. It
+/// resolves to the number of elements drained once the deque is empty.
+/// Elements not yet handed over when the future is dropped stay in the
+/// deque.
+///
+/// This `struct` is created by the `drain_yielding` method on `ArrayDeque`.
+///
+/// Requires the `futures` feature and Rust 1.36+.
+#[must_use = "futures do nothing unless polled"]
+pub struct DrainYielding<'a, A: 'a + Array, B: 'a + Behavior, F> {
+    deque: &'a mut ArrayDeque<A, B>,
+    f: F,
+    chunk: usize,
+    count: usize,
+}
+
+impl<'a, A: Array, B: Behavior, F> DrainYielding<'a, A, B, F> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDeque<A, B>, chunk: usize, f: F) -> Self {
+        assert!(chunk > 0, "chunk size must be non-zero");
+        DrainYielding {
+            deque,
+            f,
+            chunk,
+            count: 0,
+        }
+    }
+}
+
+impl<'a, A, B, F> Future for DrainYielding<'a, A, B, F>
+where
+    A: Array,
+    B: Behavior,
+    F: FnMut(A::Item) + Unpin,
+{
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        let this = self.get_mut();
+        for _ in 0..this.chunk {
+            match this.deque.pop_front() {
+                Some(element) => (this.f)(element),
+                None => return Poll::Ready(this.count),
+            }
+            this.count += 1;
+        }
+        if this.deque.is_empty() {
+            return Poll::Ready(this.count);
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::ptr;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    use {ArrayDeque, Wrapping};
+
+    /// Polls `future` to completion, returning its output and the number of
+    /// polls it took.
+    fn block_on<F: Future + Unpin>(mut future: F) -> (F::Output, usize) {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        let waker = unsafe { Waker::from_raw(clone(ptr::null())) };
+        let mut cx = Context::from_waker(&waker);
+        let mut polls = 0;
+        loop {
+            polls += 1;
+            if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+                return (output, polls);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extend_yielding() {
+        let mut tester: ArrayDeque<[usize; 10]> = ArrayDeque::new();
+        assert_eq!(block_on(tester.extend_yielding(0..7, 3)), (7, 3));
+        assert!(tester.iter().cloned().eq(0..7));
+
+        // stops once full, even in wrapping mode
+        let mut tester: ArrayDeque<[usize; 10], Wrapping> = ArrayDeque::new();
+        assert_eq!(block_on(tester.extend_yielding(0.., 5)), (10, 2));
+        assert!(tester.iter().cloned().eq(0..10));
+    }
+
+    #[test]
+    fn test_drain_yielding() {
+        let mut tester: ArrayDeque<[usize; 10]> = ArrayDeque::new();
+        tester.extend_back(0..6);
+        let mut drained = Vec::new();
+        assert_eq!(
+            block_on(tester.drain_yielding(3, |x| drained.push(x))),
+            (6, 2)
+        );
+        assert_eq!(drained, vec![0, 1, 2, 3, 4, 5]);
+        assert!(tester.is_empty());
+        assert_eq!(block_on(tester.drain_yielding(3, |_| ())), (0, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_chunk() {
+        let mut tester: ArrayDeque<[usize; 10]> = ArrayDeque::new();
+        drop(tester.extend_yielding(0..1, 0));
+    }
+}
@@ -0,0 +1,90 @@
+//! A lockstep iterator over two `ArrayDeque`s.
+
+use std::iter::FusedIterator;
+
+/// An iterator over the elements of two `ArrayDeque`s of equal length, in
+/// pairs, front to back.
+///
+/// Both deques are walked as contiguous slices, so each step only bumps a
+/// slice position instead of wrapping two indices into their buffers.
+///
+/// This `struct` is created by the `zip` method on `ArrayDeque`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct Zip<'a, T: 'a, U: 'a> {
+    a: (&'a [T], &'a [T]),
+    b: (&'a [U], &'a [U]),
+}
+
+impl<'a, T, U> Zip<'a, T, U> {
+    #[inline]
+    pub(crate) fn new(a: (&'a [T], &'a [T]), b: (&'a [U], &'a [U])) -> Self {
+        assert!(
+            a.0.len() + a.1.len() == b.0.len() + b.1.len(),
+            "deques must have the same length"
+        );
+        Zip { a, b }
+    }
+}
+
+/// Moves on to the second slice once the first one is used up.
+#[inline]
+fn refill<'a, T>(slices: &mut (&'a [T], &'a [T])) {
+    if slices.0.is_empty() {
+        slices.0 = slices.1;
+        slices.1 = &[];
+    }
+}
+
+/// Moves back to the first slice once the second one is used up.
+#[inline]
+fn refill_back<'a, T>(slices: &mut (&'a [T], &'a [T])) {
+    if slices.1.is_empty() {
+        slices.1 = slices.0;
+        slices.0 = &[];
+    }
+}
+
+impl<'a, T, U> Iterator for Zip<'a, T, U> {
+    type Item = (&'a T, &'a U);
+
+    #[inline]
+    fn next(&mut self) -> Option<(&'a T, &'a U)> {
+        refill(&mut self.a);
+        refill(&mut self.b);
+        match (self.a.0.split_first(), self.b.0.split_first()) {
+            (Some((x, a)), Some((y, b))) => {
+                self.a.0 = a;
+                self.b.0 = b;
+                Some((x, y))
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.0.len() + self.a.1.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, U> DoubleEndedIterator for Zip<'a, T, U> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(&'a T, &'a U)> {
+        refill_back(&mut self.a);
+        refill_back(&mut self.b);
+        match (self.a.1.split_last(), self.b.1.split_last()) {
+            (Some((x, a)), Some((y, b))) => {
+                self.a.1 = a;
+                self.b.1 = b;
+                Some((x, y))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, T, U> ExactSizeIterator for Zip<'a, T, U> {}
+
+impl<'a, T, U> FusedIterator for Zip<'a, T, U> {}